@@ -9,12 +9,16 @@ menu-open_recent=Zuletzt geöffnet
 menu-open_recent_clear=Liste leeren
 menu-save=Speichern
 menu-edit-sauce=SAUCE Info bearbeiten…
+menu-batch-sauce=SAUCE Stapelbearbeitung…
 menu-9px-font=9px Font
 menu-aspect-ratio=Klassisches Seitenverhältnis
 menu-set-canvas-size=Leinwandgröße ändern…
 menu-close=Schließen
 menu-save-as=Speichern unter…
 menu-export=Exportieren…
+menu-compare=Vergleichen…
+menu-document_history=Dokumentverlauf…
+menu-release-pack=Release Pack erstellen…
 menu-edit-font-outline=Font Outline…
 menu-show_settings=Einstellungen…
 
@@ -32,9 +36,14 @@ menu-rename=Umbenennen
 menu-paste-as=Einfügen als
 menu-paste-as-new-image=Neues Bild
 menu-paste-as-brush=Neuer Pinsel
+menu-find_replace=Suchen und Ersetzen…
+menu-save-brush=Als Pinsel speichern…
 menu-erase=Löschen
 menu-flipx=X Spiegeln
 menu-flipy=Y Spiegeln
+menu-rotate90=90° drehen
+menu-rotate180=180° drehen
+menu-rotate270=270° drehen
 menu-justifyleft=Linksbündig
 menu-justifyright=Rechtsbündig
 menu-justifycenter=Zentrieren
@@ -56,7 +65,15 @@ menu-scroll_area_up=Hoch scrollen
 menu-scroll_area_down=Runter scrollen
 menu-scroll_area_left=Links scrollen
 menu-scroll_area_right=Rechts scrollen
+menu-offset_by_half=Um die Hälfte verschieben
 menu-mirror_mode=Spiegelmodus
+menu-symmetry=Symmetrie
+menu-symmetry-off=Aus
+menu-symmetry-horizontal=Horizontal
+menu-symmetry-vertical=Vertikal
+menu-symmetry-quad=Beide Achsen
+menu-symmetry-radial=Radial { $count }-fach
+menu-symmetry-reset-center=Zentrum zurücksetzen
 menu-area_operations=Bereichsoperationen
 
 menu-selection=Auswahl
@@ -79,6 +96,8 @@ menu-color-mode-ext-colors=256 Farben
 menu-color-mode-ext-font=16 Farben, ext font, blink
 menu-color-mode-ext-font-ice=16 Farben, ext font, ice
 menu-select_palette=Palette auswählen
+menu-remap_colors=Farben umwandeln…
+menu-convert_palette=Palette konvertieren…
 menu-open_palette_directory=Öffne Palettenverzeichnis…
 menu-next_fg_color=Nächste Vordergrundfarbe
 menu-next_bg_color=Nächste Hintergrundfarbe
@@ -100,6 +119,7 @@ menu-guides-off=Aus
 menu-zoom-fit_size=Größe anpassen
 menu-show_layer_borders=Ebenenrahmen anzeigen
 menu-show_line_numbers=Zeilennummern anzeigen
+menu-tile_mode=Kachelmodus
 menu-toggle_grid=Gitter/Linien umschalten
 
 menu-pick_attribute_under_caret=Attribut aufheben
@@ -139,6 +159,26 @@ tool-full-block=Block
 tool-half-block=Halbblock
 tool-outline=Outline
 tool-custom-brush=Benutzerdefinierter Pinsel
+tool-brush-shape-label=Form:
+tool-brush-shape-square=Quadrat
+tool-brush-shape-round=Rund
+tool-brush-shape-diamond=Raute
+tool-brush-aspect-correction=Seitenverhältnis korrigieren
+tool-brush-soft-edge=Weicher Rand
+tool-brush-ramp-label=Verlauf:
+tool-brush-ramp-shade=Schattierungen
+tool-brush-ramp-half-block=Halbblöcke
+tool-brush-ramp-custom=Eigener
+tool-brush-rotate=Drehen
+tool-brush-flip-x=Spiegeln X
+tool-brush-flip-y=Spiegeln Y
+tool-brush-stamp=Stempel
+tool-brush-scatter=Streuen
+tool-brush-line=Linie
+tool-brush-library-label=Pinselbibliothek
+tool-brush-library-refresh=Pinsel neu laden
+tool-brush-library-empty=Keine Pinsel gespeichert. Auswahl → Als Pinsel speichern.
+tool-brush-library-delete=Pinsel löschen
 
 tool-select-label=Auswahlmodus:
 tool-select-normal=Rechteck
@@ -202,6 +242,44 @@ edit-sauce-group-label-length=(20 Zeichen)
 edit-sauce-comments-label=Kommentare (64 Zeichen pro Zeile):
 edit-sauce-letter-spacing=9 Pixel Modus:
 edit-sauce-aspect-ratio=Klassisches Seitenverhältnis:
+edit-sauce-too-long={ $length }/{ $max } Zeichen, wird gekürzt
+edit-sauce-tinfo-label=Größe (TInfo):
+edit-sauce-tinfo={ $width } Spalten x { $height } Zeilen
+edit-sauce-font-label=Font (TInfoS):
+edit-sauce-font-custom=<eigener Font>
+edit-sauce-ice-colors=iCE Farben:
+edit-sauce-letter-spacing-warning=Der 9 Pixel Modus benötigt einen 8 Pixel breiten Font
+edit-sauce-too-many-comments={ $lines } Kommentarzeilen, SAUCE erlaubt { $max }
+edit-sauce-comments-too-long={ $lines } Zeilen sind länger als { $max } Zeichen und werden gekürzt
+batch-sauce-title=SAUCE Stapelbearbeitung
+batch-sauce-path-label=Verzeichnis oder Zip:
+batch-sauce-choose-directory=Verzeichnis…
+batch-sauce-choose-zip=Zip…
+batch-sauce-date-label=Datum:
+batch-sauce-template-help=Vorlagen: {"{"}filename{"}"} {"{"}ext{"}"} {"{"}title{"}"} {"{"}author{"}"} {"{"}group{"}"} {"{"}date{"}"} (heute, JJJJMMTT)
+batch-sauce-update-preview=Vorschau aktualisieren
+batch-sauce-preview-label={ $files } Dateien, { $changed } werden geändert
+batch-sauce-file-column=Datei
+batch-sauce-new-record={ $name } (neu)
+batch-sauce-write-button=Schreiben
+batch-sauce-written=SAUCE von { $files } Dateien aktualisiert
+release-pack-title=Release Pack erstellen
+release-pack-source-label=Dateien:
+release-pack-open-documents=Offene Dokumente
+release-pack-folder=Verzeichnis…
+release-pack-name-label=Name:
+release-pack-prefix-label=Dateipräfix:
+release-pack-output-label=Ausgabe:
+release-pack-issue-no-sauce=kein SAUCE
+release-pack-issue-too-wide={ $width } Spalten breit
+release-pack-issue-format=ungewöhnliches Format
+release-pack-file-id-label=FILE_ID.DIZ:
+release-pack-generate-file-id=Erzeugen
+release-pack-file-id-too-big={ $width }x{ $lines }, eine FILE_ID.DIZ darf höchstens { $max_width }x{ $max_lines } groß sein
+release-pack-build-button=Erstellen
+release-pack-overwrite-warning={ $file } existiert bereits.
+release-pack-overwrite-button=Überschreiben
+release-pack-written={ $file } geschrieben
 
 edit-canvas-size-title=Leinwandgröße
 edit-canvas-size-width-label=Breite:
@@ -241,6 +319,13 @@ export-video-preparation-Home=Cursor zurücksetzen
 export-utf8-output-label=UTF-8 Ausgabe
 export-save-sauce-label=SAUCE Info speichern
 export-compression-label=Ausgabe komprimieren
+export-html-inline-styles-label=Inline Styles statt CSS Klassen
+export-html-embed-font-label=Zeichensatz als Webfont einbetten
+export-html-blink-label=Blinkende Zeichen animieren
+export-terminal-color-mode-label=Farben:
+export-terminal-truecolor=24 Bit
+export-terminal-xterm256=xterm 256 Farben
+export-terminal-clip-label=Auf Breite abschneiden
 export-compression-level-off=Keine
 export-compression-level-medium=Mittel
 export-compression-level-high=Hoch
@@ -266,6 +351,90 @@ about-dialog-created_by =
     Erstellt von { $authors }
     Help & testing: NuSkooler, Grymmjack
 edit-layer-dialog-title=Ebene bearbeiten
+save-brush-title=Pinsel speichern
+save-brush-name-label=Name:
+save-brush-save=Speichern
+save-brush-overwrite-warning=Ein Pinsel namens { $name } existiert bereits.
+save-brush-overwrite=Überschreiben
+find-replace-title=Suchen und Ersetzen
+find-replace-find-heading=Suchen
+find-replace-replace-heading=Ersetzen durch
+find-replace-char-label=Zeichen:
+find-replace-fg-label=Vordergrund:
+find-replace-bg-label=Hintergrund:
+find-replace-font-page-label=Fontseite:
+find-replace-blink-label=Blinken:
+find-replace-any=Beliebig
+find-replace-keep=Beibehalten
+find-replace-on=An
+find-replace-off=Aus
+find-replace-class-any=Beliebiges Zeichen
+find-replace-class-char=Zeichen
+find-replace-class-shade=Beliebige Schattierung
+find-replace-class-block=Beliebiger Block
+find-replace-class-box=Rahmenzeichen
+find-replace-class-whitespace=Leerraum
+find-replace-class-letter=Buchstabe
+find-replace-class-digit=Ziffer
+find-replace-scope-label=Suchen in:
+find-replace-scope-selection=Auswahl
+find-replace-scope-layer=Aktuelle Ebene
+find-replace-scope-all-layers=Alle Ebenen
+find-replace-matches=Treffer: { $count }
+find-replace-replace-all=Alle ersetzen
+remap-colors-title=Farben umwandeln
+remap-colors-color=Farbe
+remap-colors-fg-cells=Vordergrund
+remap-colors-fg-target=Vordergrund zu
+remap-colors-bg-cells=Hintergrund
+remap-colors-bg-target=Hintergrund zu
+remap-colors-scope-document=Dokument
+remap-colors-live-preview=Live-Vorschau
+remap-colors-reset=Zurücksetzen
+remap-colors-apply=Anwenden
+convert-palette-title=Palette konvertieren
+convert-palette-target-label=Zielpalette:
+convert-palette-target-dos=DOS Standard
+convert-palette-target-current=Erste 16 Farben der aktuellen Palette
+convert-palette-background-label=Hintergrundfarben:
+convert-palette-ice=Ice Farben (16)
+convert-palette-blink=Blinksicher (8)
+convert-palette-dither=Mit Schattierungszeichen rastern
+convert-palette-before=Vorher
+convert-palette-after=Nachher
+convert-palette-convert=Konvertieren
+compare-title=Vergleichen
+compare-left-label=Links:
+compare-right-label=Rechts:
+compare-source-none=Nichts ausgewählt
+compare-source-document=Aktuelles Dokument
+compare-source-last-saved=Zuletzt gespeicherte Version
+compare-source-autosave=Automatische Sicherung
+compare-source-file=Datei…
+compare-view-label=Ansicht:
+compare-view-side-by-side=Nebeneinander
+compare-view-overlay=Überlagert
+compare-overlay-opacity=Deckkraft
+compare-summary=Unterschiede: { $count } Zellen in { $lines } Zeilen
+compare-line=Zeile { $line }: { $count } Zellen ({ $kinds })
+compare-diff-char=Zeichen
+compare-diff-colors=Farben
+compare-diff-font-page=Zeichensatz
+compare-copy-to-right=Zeile nach rechts kopieren
+compare-copy-to-left=Zeile nach links kopieren
+compare-copy-all-to-right=Alles kopieren →
+compare-copy-all-to-left=← Alles kopieren
+compare-copy-cell-to-right=Zelle kopieren →
+compare-copy-cell-to-left=← Zelle kopieren
+compare-apply=Übernehmen
+compare-apply-tooltip=Änderungen am aktuellen Dokument können rückgängig gemacht werden, andere geänderte Seiten werden als neue Dokumente geöffnet
+document-history-title=Dokumentverlauf
+document-history-empty=Keine automatischen Sicherungen für dieses Dokument.
+document-history-no-preview=Keine Vorschau
+document-history-restore=Wiederherstellen
+document-history-open-copy=Als Kopie öffnen
+document-history-cleanup=Verwaiste Sicherungen entfernen
+document-history-cleanup-result=Entfernt: { $count }
 edit-layer-dialog-name-label=Name:
 edit-layer-dialog-is-visible-checkbox=Sichtbar
 edit-layer-dialog-is-edit-locked-checkbox=Edit gesperrt
@@ -310,6 +479,10 @@ font_tool_no_fonts_label=
     Keine Fonts gefunden
     Installiere Fonts in das Fontverzeichnis
 font_tool_open_directory_button=Fontverzeichnis öffnen
+font_tool_layout_label=Layout
+font_tool_layout_full_width=Volle Breite
+font_tool_layout_kerning=Unterschneidung
+font_tool_layout_smushing=Verschmelzen
 
 pipette_tool_char_code=Code { $code }
 pipette_tool_foreground=Vordergrund { $fg }
@@ -332,6 +505,7 @@ undo-paint-brush=Pinsel
 undo-pencil=Stift
 undo-eraser=Radierer
 undo-bucket-fill=Füllen
+undo-find-replace=Alle ersetzen
 undo-line=Linie
 undo-cut=Ausschneiden
 undo-paste-glyph=Zeichen einfügen
@@ -345,6 +519,10 @@ undo-bitfont-inverse=Invertieren
 undo-bitfont-clear=Leeren
 undo-bitfont-edit=Editieren
 undo-bitfont-resize=Größe ändern
+undo-bitfont-import=Zeichen importieren
+undo-bitfont-bold=Fett
+undo-bitfont-italic=Kursiv
+undo-bitfont-underline=Unterstreichen
 undo-delete=Entfernen
 undo-backspace=Rücktaste
 
@@ -352,6 +530,19 @@ undo-render_character=Zeichen rendern
 undo-delete_character=Zeichen löschen
 undo-select=Auswahl
 undo-plugin=Erweiterung { $title }
+undo-purge-fonts=Unbenutzte Fonts entfernen
+undo-merge-fonts=Gleiche Fonts zusammenführen
+undo-edit-sauce=SAUCE bearbeiten
+undo-tdf-add-font=Neuer Font
+undo-tdf-rename-font=Font umbenennen
+undo-tdf-font-spacing=Abstand ändern
+undo-tdf-font-type=Fonttyp ändern
+undo-tdf-import-glyphs=Zeichen importieren
+undo-tdf-clone-font=Font klonen
+undo-tdf-delete-font=Font löschen
+undo-tdf-edit-glyph=Zeichen '{ $glyph }' bearbeiten
+undo-tdf-clear-glyph=Zeichen '{ $glyph }' löschen
+undo-tdf-import-figlet=FIGlet Font importieren
 
 font_selector-ansi_font=ANSI
 font_selector-library_font=LIBRARY
@@ -367,6 +558,7 @@ autosave-dialog-description=Icy Draw hat eine Autosave Datei gefunden.
 autosave-dialog-question=Was möchtest du tun?
 autosave-dialog-load_autosave_button=Autosave laden
 autosave-dialog-discard_autosave_button=Verwerfen
+autosave-dialog-history_button=Dokumentverlauf…
 
 paste_mode-description=Einfügemodos. Im Layer Tool neue Ebene hinzufügen oder verankern.
 paste_mode-stamp=Stempel
@@ -386,6 +578,7 @@ tab-context-menu-close_all=Alle schließen
 tab-context-menu-copy_path=Pfad kopieren
 
 font-view-char_label=Zeichen
+font-view-petscii_label=PETSCII
 font-view-ascii_label=ASCII
 font-view-font_label=Font
 font-view-font_page_label=Font Seite:
@@ -395,6 +588,39 @@ font-editor-clear=Löschen
 font-editor-inverse=Invertieren
 font-editor-flip_x=X Spiegeln
 font-editor-flip_y=Y Spiegeln
+font-editor-bold=Fett
+font-editor-italic=Kursiv
+font-editor-underline=Unterstreichen
+font-editor-tool-pencil=Stift
+font-editor-tool-line=Linie
+font-editor-tool-rectangle=Rechteck
+font-editor-tool-filled_rectangle=Gefülltes Rechteck
+font-editor-tool-fill=Füllen
+font-editor-preview=Vorschau
+font-editor-preview_sample_text=Franz jagt im komplett verwahrlosten Taxi quer durch Bayern 0123456789
+font-editor-preview_load_sample=Beispieldatei laden…
+font-editor-coverage=Zeichensatzabdeckung
+font-editor-coverage-code_page=Codepage
+font-editor-coverage-group={ $name }: { $covered }/{ $total }
+font-editor-coverage-latin=Buchstaben & Ziffern
+font-editor-coverage-punctuation=Satzzeichen
+font-editor-coverage-accented=Akzentbuchstaben
+font-editor-coverage-box_drawing=Rahmenzeichen
+font-editor-coverage-block_elements=Blockelemente
+font-editor-coverage-symbols=Symbole
+font-editor-scale_mode=Skalierung
+font-editor-scale-crop=Abschneiden / auffüllen
+font-editor-scale-nearest=Nächster Nachbar
+font-editor-scale-line_preserving=Linien erhalten
+font-editor-scale-row_dropping=Zeilen auslassen (Rahmenzeichen)
+font-editor-lga_preview=Vorschau mit 9. Spalte (LGA)
+font-editor-import_png=PNG Zeichentabelle importieren
+font-editor-cell_size=Zellgröße
+font-editor-grid_offset=Rasterversatz
+font-editor-threshold=Schwellwert
+font-editor-invert=Dunkle Zeichen auf hellem Hintergrund
+font-editor-import_png_button=Importieren…
+font-editor-export=Exportieren:
 
 animation_editor_path_label=Datei:
 animation_editor_export_button=Export
@@ -403,6 +629,22 @@ animation_encoding_frame=Berechne Bild { $cur } von { $total }
 animation_of_frame_count=von { $total }
 animation_icy_play_note=Für Animationen in der Konsole/BBS (oder zum Ansi honvertieren) braucht man:
 
+import-asciicast-title=Asciicast Aufnahme importieren
+import-asciicast-size-label=Terminalgröße:
+import-asciicast-duration-label=Länge:
+import-asciicast-duration={ $seconds }s, { $events } Ausgabeereignisse
+import-asciicast-event-bursts=Bild pro Ausgabeblock
+import-asciicast-burst-gap-label=Blockabstand:
+import-asciicast-time-step=Bild pro Zeitschritt
+import-asciicast-time-step-label=Zeitschritt:
+import-asciicast-limit-idle-time=Wartezeit begrenzen
+import-asciicast-idle-time-label=Max. Wartezeit:
+import-asciicast-output-label=Animation:
+import-asciicast-frames-label=Bilder:
+import-asciicast-overwrite-warning={ $file } existiert bereits.
+import-asciicast-overwrite-button=Überschreiben
+import-asciicast-import-button=Importieren
+
 new-file-template-cp437-title=CP437 ANSI
 new-file-template-cp437-description=
     Ein neues DOS Ansi erstellen
@@ -426,6 +668,8 @@ new-file-template-ansi-description=
 new-file-template-atascii-title=Atascii
 new-file-template-atascii-description=
     Ein neues Atascii file erstellen
+new-file-template-petscii-title=PETSCII
+new-file-template-petscii-description=Commodore 64 Bildschirm mit den Groß- und Kleinbuchstaben Zeichensätzen als Font-Seiten. Kann als Bildschirmdump (.scr), PETSCII Stream (.seq) oder selbst anzeigendes Programm (.prg) gespeichert werden.
 
 new-file-template-file_id-title=FILE_ID.DIZ
 new-file-template-file_id-description=FILE_ID.DIZ erstellen
@@ -462,6 +706,13 @@ manage-font-not_used_label=nicht verwendet
 manage-font-replace_label=Ersetze Font mit:
 manage-font-replace_font_button=Ersetzen
 manage-font-change_font_slot_button=Slot ändern
+manage-font-purge_button=Unbenutzte entfernen
+manage-font-purge_button-tooltip=Entfernt alle Fontslots, die von keiner Zelle verwendet werden.
+manage-font-merge_button=Gleiche zusammenführen
+manage-font-merge_button-tooltip=Zellen mit einem Font, der identisch zu einem Font in einem niedrigeren Slot ist, verwenden danach diesen Font. Das Duplikat wird entfernt.
+manage-font-usage_report_label=Verwendung von Fontslot { $font }:
+manage-font-usage_report_layer={ $layer }: { $cells } Zellen
+manage-font-usage_report_unused=Keine Zelle verwendet diesen Font.
 
 palette_selector-dos_default_palette=VGA 16 Farben
 palette_selector-dos_default_low_palette=VGA 8 Farben
@@ -485,6 +736,14 @@ tdf-editor-clear_char_button=Zeichen Löschen
 tdf-editor-cheat_sheet_key=Taste
 tdf-editor-cheat_sheet_code=Code
 tdf-editor-cheat_sheet_res=Res
+tdf-editor-new_font_name=Neuer Font
+tdf-editor-export_font_button=Font exportieren…
+tdf-editor-export_all_button=Alle exportieren…
+tdf-editor-import_glyphs_button=Zeichen importieren
+tdf-editor-import_glyphs_button-tooltip=Importiert die Zeichen aus einer in einem ANSI Dokument kopierten Auswahl.
+    Die Zeichen werden durch leere Spalten getrennt und ab dem ausgewählten Zeichen gespeichert.
+tdf-editor-import_figlet_button=FIGlet Font importieren…
+tdf-editor-import_figlet_button-tooltip=Konvertiert einen FIGlet (.flf) oder TOIlet (.tlf) Font in einen Block Font.
 
 settings-heading=Einstellungen
 settings-reset_button=Reset
@@ -500,3 +759,14 @@ settings-guide-label=Hilfslinienfarbe:
 settings-set-label=Set { $set }
 settings-key_filter_preview_text=Tastenzuweisung filtern
 settings-char_set_list_label=Zeichnsatzliste:
+settings-glyph-map-category=Zeichenzuordnung
+settings-autosave-category=Automatische Sicherung
+settings-autosave-count-label=Sicherungen pro Dokument:
+settings-autosave-interval-label=Neue Sicherung alle:
+settings-autosave-minutes={" "}Min.
+settings-glyph-map-list-label=Zuordnungen:
+settings-glyph-map-code-page-label=Codepage:
+settings-glyph-map-mirror-x-label=Horizontale Spiegelpaare:
+settings-glyph-map-mirror-y-label=Vertikale Spiegelpaare:
+settings-glyph-map-rotate-label=Rotationszyklen:
+settings-glyph-map-description=Spiegelpaare bestehen aus zwei Zeichen, Rotationszyklen aus einer Zeichenfolge im Uhrzeigersinn. Spiegeln und Drehen tauschen Zeichen anhand der Zuordnung des aktuellen Fonts.
@@ -9,12 +9,16 @@ menu-open_recent=Open Recent
 menu-open_recent_clear=Clear
 menu-save=Save
 menu-edit-sauce=Edit Sauce Info…
+menu-batch-sauce=Batch SAUCE Tagging…
 menu-9px-font=9px Font
 menu-aspect-ratio=Legacy Aspect Ratio
 menu-set-canvas-size=Set Canvas Size…
 menu-close=Close
 menu-save-as=Save As…
 menu-export=Export…
+menu-compare=Compare…
+menu-document_history=Document History…
+menu-release-pack=Build Release Pack…
 menu-edit-font-outline=Font Outline…
 menu-show_settings=Settings…
 
@@ -32,9 +36,14 @@ menu-rename=Rename
 menu-paste-as=Paste as
 menu-paste-as-new-image=New image
 menu-paste-as-brush=Brush
+menu-find_replace=Find and Replace…
+menu-save-brush=Save as brush…
 menu-erase=Erase
 menu-flipx=Flip X
 menu-flipy=Flip Y
+menu-rotate90=Rotate 90°
+menu-rotate180=Rotate 180°
+menu-rotate270=Rotate 270°
 menu-justifyleft=Justify Left
 menu-justifyright=Justify Right
 menu-justifycenter=Center
@@ -56,7 +65,15 @@ menu-scroll_area_up=Scroll Area Up
 menu-scroll_area_down=Scroll Area Down
 menu-scroll_area_left=Scroll Area Left
 menu-scroll_area_right=Scroll Area Right
+menu-offset_by_half=Offset by Half
 menu-mirror_mode=Mirror Mode
+menu-symmetry=Symmetry
+menu-symmetry-off=Off
+menu-symmetry-horizontal=Horizontal
+menu-symmetry-vertical=Vertical
+menu-symmetry-quad=Both axes
+menu-symmetry-radial=Radial { $count }-way
+menu-symmetry-reset-center=Reset center
 menu-area_operations=Area
 
 menu-selection=Selection
@@ -76,6 +93,8 @@ menu-palette-mode-free=Free 16
 menu-palette-mode-free8=Free 8
 
 menu-select_palette=Select Palette
+menu-remap_colors=Remap Colors…
+menu-convert_palette=Convert Palette…
 menu-next_fg_color=Next Foreground Color
 menu-next_bg_color=Next Background Color
 menu-prev_fg_color=Previous Foreground Color
@@ -96,6 +115,7 @@ menu-guides-off=Off
 menu-zoom-fit_size=Fit Size
 menu-show_layer_borders=Show Layer Borders
 menu-show_line_numbers=Show Line Numbers
+menu-tile_mode=Tile Mode
 menu-toggle_grid=Toggle Grid/Guides
 
 menu-pick_attribute_under_caret=Pick up Attribute
@@ -136,6 +156,26 @@ tool-full-block=Block
 tool-half-block=Half Block
 tool-outline=Outline
 tool-custom-brush=Custom brush
+tool-brush-shape-label=Shape:
+tool-brush-shape-square=Square
+tool-brush-shape-round=Round
+tool-brush-shape-diamond=Diamond
+tool-brush-aspect-correction=Aspect correction
+tool-brush-soft-edge=Soft edge
+tool-brush-ramp-label=Ramp:
+tool-brush-ramp-shade=Shades
+tool-brush-ramp-half-block=Half blocks
+tool-brush-ramp-custom=Custom
+tool-brush-rotate=Rotate
+tool-brush-flip-x=Flip X
+tool-brush-flip-y=Flip Y
+tool-brush-stamp=Stamp
+tool-brush-scatter=Scatter
+tool-brush-line=Line
+tool-brush-library-label=Brush library
+tool-brush-library-refresh=Reload brushes
+tool-brush-library-empty=No brushes saved. Use Selection → Save as brush.
+tool-brush-library-delete=Delete brush
 
 tool-select-label=Selection mode:
 tool-select-normal=Rectangle
@@ -199,6 +239,44 @@ edit-sauce-group-label-length=(20 chars)
 edit-sauce-comments-label=Comments (64 chars in line limit)
 edit-sauce-letter-spacing=Use 9px mode:
 edit-sauce-aspect-ratio=Simulate classic aspect ratio:
+edit-sauce-too-long={ $length }/{ $max } chars, will be cut
+edit-sauce-tinfo-label=Size (TInfo):
+edit-sauce-tinfo={ $width } columns x { $height } lines
+edit-sauce-font-label=Font (TInfoS):
+edit-sauce-font-custom=<custom font>
+edit-sauce-ice-colors=iCE colors:
+edit-sauce-letter-spacing-warning=9px mode needs an 8px wide font
+edit-sauce-too-many-comments={ $lines } comment lines, SAUCE allows { $max }
+edit-sauce-comments-too-long={ $lines } lines exceed { $max } chars and will be cut
+batch-sauce-title=Batch SAUCE Tagging
+batch-sauce-path-label=Directory or zip:
+batch-sauce-choose-directory=Directory…
+batch-sauce-choose-zip=Zip…
+batch-sauce-date-label=Date:
+batch-sauce-template-help=Templates: {"{"}filename{"}"} {"{"}ext{"}"} {"{"}title{"}"} {"{"}author{"}"} {"{"}group{"}"} {"{"}date{"}"} (today, CCYYMMDD)
+batch-sauce-update-preview=Update preview
+batch-sauce-preview-label={ $files } files, { $changed } will be changed
+batch-sauce-file-column=File
+batch-sauce-new-record={ $name } (new)
+batch-sauce-write-button=Write
+batch-sauce-written=Updated SAUCE of { $files } files
+release-pack-title=Build Release Pack
+release-pack-source-label=Files:
+release-pack-open-documents=Open documents
+release-pack-folder=Folder…
+release-pack-name-label=Pack name:
+release-pack-prefix-label=File prefix:
+release-pack-output-label=Output:
+release-pack-issue-no-sauce=no SAUCE
+release-pack-issue-too-wide={ $width } columns wide
+release-pack-issue-format=unusual format
+release-pack-file-id-label=FILE_ID.DIZ:
+release-pack-generate-file-id=Generate
+release-pack-file-id-too-big={ $width }x{ $lines }, a FILE_ID.DIZ may be at most { $max_width }x{ $max_lines }
+release-pack-build-button=Build
+release-pack-overwrite-warning={ $file } already exists.
+release-pack-overwrite-button=Overwrite
+release-pack-written={ $file } written
 
 edit-canvas-size-title=Set Canvas Size
 edit-canvas-size-width-label=Width:
@@ -237,6 +315,13 @@ export-video-preparation-Home=Home Cursor
 export-utf8-output-label=Modern terminal format (utf8)
 export-save-sauce-label=Save sauce info
 export-compression-label=Compress output
+export-html-inline-styles-label=Inline styles instead of CSS classes
+export-html-embed-font-label=Embed font as web font
+export-html-blink-label=Animate blinking characters
+export-terminal-color-mode-label=Colors:
+export-terminal-truecolor=24 bit
+export-terminal-xterm256=xterm 256 colors
+export-terminal-clip-label=Clip to width
 export-limit-output-line-length-label=Limit output line length
 export-maximum_line_length=Maximum line length
 export-use_repeat_sequences=Use CSI Pn b repeat sequences
@@ -260,6 +345,90 @@ about-dialog-created_by =
     Help & testing: NuSkooler, Grymmjack
 
 edit-layer-dialog-title=Layer properties
+save-brush-title=Save brush
+save-brush-name-label=Name:
+save-brush-save=Save
+save-brush-overwrite-warning=A brush named { $name } already exists.
+save-brush-overwrite=Overwrite
+find-replace-title=Find and Replace
+find-replace-find-heading=Find
+find-replace-replace-heading=Replace with
+find-replace-char-label=Character:
+find-replace-fg-label=Foreground:
+find-replace-bg-label=Background:
+find-replace-font-page-label=Font page:
+find-replace-blink-label=Blink:
+find-replace-any=Any
+find-replace-keep=Keep
+find-replace-on=On
+find-replace-off=Off
+find-replace-class-any=Any character
+find-replace-class-char=Character
+find-replace-class-shade=Any shade
+find-replace-class-block=Any block
+find-replace-class-box=Box drawing
+find-replace-class-whitespace=Whitespace
+find-replace-class-letter=Letter
+find-replace-class-digit=Digit
+find-replace-scope-label=Search in:
+find-replace-scope-selection=Selection
+find-replace-scope-layer=Current layer
+find-replace-scope-all-layers=All layers
+find-replace-matches=Matches: { $count }
+find-replace-replace-all=Replace all
+remap-colors-title=Remap Colors
+remap-colors-color=Color
+remap-colors-fg-cells=Foreground
+remap-colors-fg-target=Foreground to
+remap-colors-bg-cells=Background
+remap-colors-bg-target=Background to
+remap-colors-scope-document=Document
+remap-colors-live-preview=Live preview
+remap-colors-reset=Reset
+remap-colors-apply=Apply
+convert-palette-title=Convert Palette
+convert-palette-target-label=Target palette:
+convert-palette-target-dos=DOS default
+convert-palette-target-current=First 16 colors of the current palette
+convert-palette-background-label=Background colors:
+convert-palette-ice=Ice colors (16)
+convert-palette-blink=Blink safe (8)
+convert-palette-dither=Dither with shade characters
+convert-palette-before=Before
+convert-palette-after=After
+convert-palette-convert=Convert
+compare-title=Compare
+compare-left-label=Left:
+compare-right-label=Right:
+compare-source-none=Nothing selected
+compare-source-document=Current document
+compare-source-last-saved=Last saved version
+compare-source-autosave=Autosave
+compare-source-file=File…
+compare-view-label=View:
+compare-view-side-by-side=Side by side
+compare-view-overlay=Overlay
+compare-overlay-opacity=Opacity
+compare-summary=Differences: { $count } cells in { $lines } lines
+compare-line=Line { $line }: { $count } cells ({ $kinds })
+compare-diff-char=character
+compare-diff-colors=colors
+compare-diff-font-page=font page
+compare-copy-to-right=Copy line to the right side
+compare-copy-to-left=Copy line to the left side
+compare-copy-all-to-right=Copy all →
+compare-copy-all-to-left=← Copy all
+compare-copy-cell-to-right=Copy cell →
+compare-copy-cell-to-left=← Copy cell
+compare-apply=Apply
+compare-apply-tooltip=Changes to the current document can be undone, other changed sides are opened as new documents
+document-history-title=Document History
+document-history-empty=No autosaves for this document.
+document-history-no-preview=No preview
+document-history-restore=Restore
+document-history-open-copy=Open as copy
+document-history-cleanup=Remove orphaned autosaves
+document-history-cleanup-result=Removed: { $count }
 edit-layer-dialog-name-label=Name:
 edit-layer-dialog-is-visible-checkbox=Visible
 edit-layer-dialog-is-edit-locked-checkbox=Edit locked
@@ -304,6 +473,10 @@ font_tool_no_fonts_label=
     No tdf fonts found.
     Install new fonts in the font directory
 font_tool_open_directory_button=Open font directory
+font_tool_layout_label=Layout
+font_tool_layout_full_width=Full width
+font_tool_layout_kerning=Kerning
+font_tool_layout_smushing=Smushing
 
 pipette_tool_char_code=Code { $code }
 pipette_tool_foreground=Foreground { $fg }
@@ -326,6 +499,7 @@ undo-paint-brush=Paintbrush
 undo-pencil=Pencil
 undo-eraser=Eraser
 undo-bucket-fill=Bucket fill
+undo-find-replace=Replace all
 undo-line=Line
 undo-cut=Cut
 undo-paste-glyph=Paste glyph
@@ -339,6 +513,10 @@ undo-bitfont-inverse=Inverse
 undo-bitfont-clear=Clear
 undo-bitfont-edit=Edit
 undo-bitfont-resize=Resize
+undo-bitfont-import=Import glyphs
+undo-bitfont-bold=Bold
+undo-bitfont-italic=Italic
+undo-bitfont-underline=Underline
 undo-delete=Delete
 undo-backspace=Backspace
 
@@ -346,6 +524,19 @@ undo-render_character=Render character
 undo-delete_character=Delete character
 undo-select=Select
 undo-plugin=Plugin { $title }
+undo-purge-fonts=Purge unused fonts
+undo-merge-fonts=Merge identical fonts
+undo-edit-sauce=Edit SAUCE
+undo-tdf-add-font=New font
+undo-tdf-rename-font=Rename font
+undo-tdf-font-spacing=Change spacing
+undo-tdf-font-type=Change font type
+undo-tdf-import-glyphs=Import glyphs
+undo-tdf-clone-font=Clone font
+undo-tdf-delete-font=Delete font
+undo-tdf-edit-glyph=Edit glyph '{ $glyph }'
+undo-tdf-clear-glyph=Clear glyph '{ $glyph }'
+undo-tdf-import-figlet=Import FIGlet font
 
 font_selector-ansi_font=ANSI
 font_selector-library_font=LIBRARY
@@ -361,6 +552,7 @@ autosave-dialog-description=Found an autosave for this file.
 autosave-dialog-question=Do you want to use the original file, or load the autosave?
 autosave-dialog-load_autosave_button=Load from autosave
 autosave-dialog-discard_autosave_button=Discard autosave
+autosave-dialog-history_button=Document history…
 
 paste_mode-description=You're now in paste mode. Use layer tool to add or anchor the layer.
 paste_mode-stamp=Stamp
@@ -380,6 +572,7 @@ tab-context-menu-close_all=Close all
 tab-context-menu-copy_path=Copy path
 
 font-view-char_label=Char
+font-view-petscii_label=PETSCII
 font-view-ascii_label=ASCII
 font-view-font_label=Font
 font-view-font_page_label=Font Page:
@@ -389,6 +582,39 @@ font-editor-clear=Clear
 font-editor-inverse=Inverse
 font-editor-flip_x=Flip X
 font-editor-flip_y=Flip Y
+font-editor-bold=Bold
+font-editor-italic=Italic
+font-editor-underline=Underline
+font-editor-tool-pencil=Pencil
+font-editor-tool-line=Line
+font-editor-tool-rectangle=Rectangle
+font-editor-tool-filled_rectangle=Filled rectangle
+font-editor-tool-fill=Fill
+font-editor-preview=Preview
+font-editor-preview_sample_text=The quick brown fox jumps over the lazy dog 0123456789
+font-editor-preview_load_sample=Load sample file…
+font-editor-coverage=Character set coverage
+font-editor-coverage-code_page=Code page
+font-editor-coverage-group={ $name }: { $covered }/{ $total }
+font-editor-coverage-latin=Letters & digits
+font-editor-coverage-punctuation=Punctuation
+font-editor-coverage-accented=Accented letters
+font-editor-coverage-box_drawing=Box drawing
+font-editor-coverage-block_elements=Block elements
+font-editor-coverage-symbols=Symbols
+font-editor-scale_mode=Scaling
+font-editor-scale-crop=Crop / pad
+font-editor-scale-nearest=Nearest
+font-editor-scale-line_preserving=Line preserving
+font-editor-scale-row_dropping=Row dropping (box drawing)
+font-editor-lga_preview=9th column (LGA) preview
+font-editor-import_png=Import PNG sheet
+font-editor-cell_size=Cell size
+font-editor-grid_offset=Grid offset
+font-editor-threshold=Threshold
+font-editor-invert=Dark glyphs on bright background
+font-editor-import_png_button=Import…
+font-editor-export=Export:
 
 animation_editor_path_label=Path:
 animation_editor_export_button=Export
@@ -397,6 +623,22 @@ animation_encoding_frame=Encoding frame { $cur } of { $total }
 animation_of_frame_count=of { $total }
 animation_icy_play_note=Note: For playing the animation in the console/bbs or ansi conversion use:
 
+import-asciicast-title=Import asciicast recording
+import-asciicast-size-label=Terminal size:
+import-asciicast-duration-label=Length:
+import-asciicast-duration={ $seconds }s, { $events } output events
+import-asciicast-event-bursts=Frame per output burst
+import-asciicast-burst-gap-label=Burst gap:
+import-asciicast-time-step=Frame per time step
+import-asciicast-time-step-label=Time step:
+import-asciicast-limit-idle-time=Limit idle time
+import-asciicast-idle-time-label=Max. idle time:
+import-asciicast-output-label=Animation:
+import-asciicast-frames-label=Frames:
+import-asciicast-overwrite-warning={ $file } already exists.
+import-asciicast-overwrite-button=Overwrite
+import-asciicast-import-button=Import
+
 new-file-template-cp437-title=CP437 ANSI
 new-file-template-cp437-description=
     Create a new DOS 16 color ANSI file
@@ -420,6 +662,8 @@ new-file-template-ansi-description=
 new-file-template-atascii-title=Atascii
 new-file-template-atascii-description=
     Create a new Atascii file
+new-file-template-petscii-title=PETSCII
+new-file-template-petscii-description=Commodore 64 screen with upper and lower case char sets as font pages. Can be saved as screen dump (.scr), PETSCII stream (.seq) or a self displaying program (.prg).
 
 new-file-template-file_id-title=FILE_ID.DIZ
 new-file-template-file_id-description=Create a new FILE_ID.DIZ file
@@ -455,6 +699,13 @@ manage-font-not_used_label=not used
 manage-font-replace_label=Replace usage with slot
 manage-font-replace_font_button=Replace
 manage-font-change_font_slot_button=Change font slot
+manage-font-purge_button=Purge unused
+manage-font-purge_button-tooltip=Removes all font slots that aren't used by any cell.
+manage-font-merge_button=Merge identical
+manage-font-merge_button-tooltip=Cells using a font that is identical to a font in a lower slot are changed to use that font, the duplicate is removed.
+manage-font-usage_report_label=Usage of font slot { $font }:
+manage-font-usage_report_layer={ $layer }: { $cells } cells
+manage-font-usage_report_unused=No cell uses this font.
 
 palette_selector-dos_default_palette=VGA 16 colors
 palette_selector-dos_default_low_palette=VGA 8 colors
@@ -478,6 +729,14 @@ tdf-editor-clear_char_button=Clear Char
 tdf-editor-cheat_sheet_key=Key
 tdf-editor-cheat_sheet_code=Code
 tdf-editor-cheat_sheet_res=Res
+tdf-editor-new_font_name=New Font
+tdf-editor-export_font_button=Export font…
+tdf-editor-export_all_button=Export all…
+tdf-editor-import_glyphs_button=Import glyphs
+tdf-editor-import_glyphs_button-tooltip=Imports the glyphs from a selection copied in an ANSI document.
+    Glyphs are separated by empty columns and stored starting with the selected character.
+tdf-editor-import_figlet_button=Import FIGlet font…
+tdf-editor-import_figlet_button-tooltip=Converts a FIGlet (.flf) or TOIlet (.tlf) font to a block font.
 
 settings-heading=Settings
 settings-reset_button=Reset
@@ -493,3 +752,14 @@ settings-guide-label=Guide color:
 settings-set-label=Set { $set }
 settings-key_filter_preview_text=Filter key bindings
 settings-char_set_list_label=Character sets:
+settings-glyph-map-category=Glyph Mappings
+settings-autosave-category=Autosave
+settings-autosave-count-label=Snapshots per document:
+settings-autosave-interval-label=New snapshot every:
+settings-autosave-minutes={" "}min
+settings-glyph-map-list-label=Glyph mappings:
+settings-glyph-map-code-page-label=Code page:
+settings-glyph-map-mirror-x-label=Horizontal mirror pairs:
+settings-glyph-map-mirror-y-label=Vertical mirror pairs:
+settings-glyph-map-rotate-label=Rotation cycles:
+settings-glyph-map-description=Each mirror pair is written as two glyphs, each rotation cycle as a run of glyphs in clockwise order. Flip and rotate swap glyphs according to the mapping of the current font.
@@ -61,6 +61,54 @@ use clap::Parser;
 #[derive(Parser, Debug)]
 pub struct Cli {
     path: Option<PathBuf>,
+
+    /// Sets the SAUCE fields of all files in a directory or zip and exits. Fields may use {filename}, {ext}, {title}, {author}, {group} and {date}.
+    #[arg(long, value_name = "DIR_OR_ZIP")]
+    sauce_batch: Option<PathBuf>,
+    #[arg(long, requires = "sauce_batch")]
+    title: Option<String>,
+    #[arg(long, requires = "sauce_batch")]
+    author: Option<String>,
+    #[arg(long, requires = "sauce_batch")]
+    group: Option<String>,
+    #[arg(long, requires = "sauce_batch")]
+    date: Option<String>,
+    /// Only print the changes of --sauce-batch
+    #[arg(long, requires = "sauce_batch")]
+    dry_run: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run_sauce_batch(args: &Cli, path: &std::path::Path) -> TerminalResult<()> {
+    use util::sauce_batch::{preview_batch, write_batch, SauceTemplate};
+    let template = SauceTemplate {
+        title: args.title.clone(),
+        author: args.author.clone(),
+        group: args.group.clone(),
+        date: args.date.clone(),
+    };
+    for entry in preview_batch(path, &template)? {
+        if !entry.is_changed() {
+            continue;
+        }
+        let old = entry.old.clone().unwrap_or_default();
+        println!("{}{}", entry.name, if entry.old.is_none() { " (new)" } else { "" });
+        for (label, old, new) in [
+            ("title", &old.title, &entry.new.title),
+            ("author", &old.author, &entry.new.author),
+            ("group", &old.group, &entry.new.group),
+            ("date", &old.date, &entry.new.date),
+        ] {
+            if old != new {
+                println!("  {label}: '{old}' -> '{new}'");
+            }
+        }
+    }
+    if !args.dry_run {
+        let changed = write_batch(path, &template)?;
+        println!("updated {changed} files");
+    }
+    Ok(())
 }
 
 // When compiling natively:
@@ -69,6 +117,13 @@ fn main() {
     use std::fs;
 
     let args = Cli::parse();
+    if let Some(path) = &args.sauce_batch {
+        if let Err(err) = run_sauce_batch(&args, path) {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
 
     use crate::plugins::Plugin;
     let options = eframe::NativeOptions {
@@ -148,6 +203,16 @@ fn main() {
         }
     }
 
+    if let Ok(settings_file) = GlyphMappings::get_glyph_mappings_file() {
+        if settings_file.exists() {
+            if let Ok(glyph_mappings) = GlyphMappings::load(&settings_file) {
+                unsafe {
+                    GLYPH_MAPPINGS = glyph_mappings;
+                }
+            }
+        }
+    }
+
     if let Ok(settings_file) = MostRecentlyUsedFiles::get_mru_file() {
         if settings_file.exists() {
             if let Ok(character_sets) = MostRecentlyUsedFiles::load(&settings_file) {
@@ -166,8 +231,14 @@ fn main() {
         if CHARACTER_SETS.character_sets.is_empty() {
             CHARACTER_SETS.character_sets.push(CharSetMapping::default());
         }
+
+        if GLYPH_MAPPINGS.glyph_mappings.is_empty() {
+            GLYPH_MAPPINGS.glyph_mappings = util::glyph_map::GlyphMapping::defaults();
+        }
     }
 
+    util::autosave::expire_orphaned_autosaves();
+
     log::info!("Starting iCY DRAW {}", *VERSION);
     Plugin::read_plugin_directory();
     if let Err(err) = eframe::run_native(
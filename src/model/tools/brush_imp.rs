@@ -1,13 +1,18 @@
 use eframe::egui::Response;
-use egui::{load::SizedTexture, Image, TextureHandle, Widget};
+use egui::{load::SizedTexture, Image, RichText, Sense, TextureHandle, Widget};
 use i18n_embed_fl::fl;
-use icy_engine::{editor::AtomicUndoGuard, AttributedChar, Layer, TextPane};
+use icy_engine::{editor::AtomicUndoGuard, AttributedChar, BufferType, Layer, Size, TextPane};
 use icy_engine_gui::TerminalCalc;
 use std::{cell::RefCell, rc::Rc};
 
 use crate::{
     create_image,
-    paint::{BrushMode, ColorMode},
+    paint::{BrushMode, ColorMode, SHADE_GRADIENT},
+    util::{
+        brush_library::{delete_brush, load_brushes, LibraryBrush},
+        glyph_map::{flip_layer_x, flip_layer_y, rotate_layer_cw},
+        unicode::from_unicode,
+    },
     AnsiEditor, Event, Message,
 };
 
@@ -15,26 +20,90 @@ use super::{Position, Tool};
 
 pub static mut CUSTOM_BRUSH: Option<Layer> = None;
 
+pub static HALF_BLOCK_RAMP: [char; 3] = ['\u{00FE}', '\u{00DC}', '\u{00DB}'];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BrushShape {
+    Square,
+    Round,
+    Diamond,
+}
+
+/// How a custom brush is applied while dragging.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CustomBrushMode {
+    Stamp,
+    Scatter,
+    Line,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShadeRamp {
+    Shade,
+    HalfBlock,
+    Custom,
+}
+
+fn ramp_label(ramp: ShadeRamp) -> String {
+    match ramp {
+        ShadeRamp::Shade => fl!(crate::LANGUAGE_LOADER, "tool-brush-ramp-shade"),
+        ShadeRamp::HalfBlock => fl!(crate::LANGUAGE_LOADER, "tool-brush-ramp-half-block"),
+        ShadeRamp::Custom => fl!(crate::LANGUAGE_LOADER, "tool-brush-ramp-custom"),
+    }
+}
+
+/// Steps one notch up the ramp but never beyond `max_level`, darker chars are kept.
+fn next_shade(ramp: &[char], cur: char, max_level: usize) -> char {
+    match ramp.iter().position(|c| *c == cur) {
+        Some(i) if i >= max_level => cur,
+        Some(i) => ramp[i + 1],
+        None => ramp[0],
+    }
+}
+
 pub struct BrushTool {
     color_mode: ColorMode,
     size: i32,
+    shape: BrushShape,
+    /// Scales the brush height by the cell aspect ratio so round brushes look round on 8x16 cells.
+    aspect_correction: bool,
+    /// Shade mode: the reachable shade level falls off towards the brush edge.
+    soft_edge: bool,
+    shade_ramp: ShadeRamp,
+    custom_ramp: String,
     char_code: Rc<RefCell<char>>,
 
     undo_op: Option<AtomicUndoGuard>,
     cur_pos: Position,
     custom_brush: Option<Layer>,
+    custom_mode: CustomBrushMode,
+    last_stamp: Option<Position>,
+    random_seed: u32,
     image: Option<TextureHandle>,
     brush_mode: BrushMode,
+
+    library: Option<Vec<LibraryBrush>>,
+    library_images: Vec<TextureHandle>,
 }
 
 impl Default for BrushTool {
     fn default() -> Self {
         Self {
             size: 3,
+            shape: BrushShape::Square,
+            aspect_correction: false,
+            soft_edge: false,
+            shade_ramp: ShadeRamp::Shade,
+            custom_ramp: "·░▒▓█".to_string(),
             color_mode: ColorMode::Both,
             undo_op: None,
             custom_brush: None,
+            custom_mode: CustomBrushMode::Stamp,
+            last_stamp: None,
+            random_seed: 0x2545_F491,
             image: None,
+            library: None,
+            library_images: Vec::new(),
             brush_mode: BrushMode::Shade,
             char_code: Rc::new(RefCell::new('\u{00B0}')),
             cur_pos: Position::default(),
@@ -42,16 +111,125 @@ impl Default for BrushTool {
     }
 }
 impl BrushTool {
-    fn paint_brush(&self, editor: &mut AnsiEditor, pos: Position) {
-        let mid = Position::new(-(self.size / 2), -(self.size / 2));
+    /// Cells covered by the brush relative to the cursor together with their distance from the center (0.0 center - 1.0 edge).
+    fn footprint(&self, font_size: Size) -> Vec<(Position, f32)> {
+        let width = self.size;
+        let height = if self.aspect_correction && font_size.height > 0 {
+            ((self.size * font_size.width) as f32 / font_size.height as f32).round().max(1.0) as i32
+        } else {
+            self.size
+        };
+        let rx = width as f32 / 2.0;
+        let ry = height as f32 / 2.0;
 
-        let center = pos + mid;
-        let gradient = ['\u{00B0}', '\u{00B1}', '\u{00B2}', '\u{00DB}'];
-        let caret_attr = editor.buffer_view.lock().get_caret().get_attribute();
+        let mut result = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let dx = (x as f32 + 0.5 - rx) / rx;
+                let dy = (y as f32 + 0.5 - ry) / ry;
+                let dist = match self.shape {
+                    BrushShape::Square => dx.abs().max(dy.abs()),
+                    BrushShape::Round => (dx * dx + dy * dy).sqrt(),
+                    BrushShape::Diamond => dx.abs() + dy.abs(),
+                };
+                if dist <= 1.0 {
+                    result.push((Position::new(x - width / 2, y - height / 2), dist));
+                }
+            }
+        }
+        result
+    }
+
+    fn get_ramp(&self, buffer_type: BufferType) -> Vec<char> {
+        match self.shade_ramp {
+            ShadeRamp::Shade => SHADE_GRADIENT.to_vec(),
+            ShadeRamp::HalfBlock => HALF_BLOCK_RAMP.to_vec(),
+            ShadeRamp::Custom => {
+                let ramp: Vec<char> = self.custom_ramp.chars().map(|ch| from_unicode(buffer_type, ch)).collect();
+                if ramp.is_empty() {
+                    SHADE_GRADIENT.to_vec()
+                } else {
+                    ramp
+                }
+            }
+        }
+    }
+
+    fn custom_brush_size(&self) -> (i32, i32) {
+        self.custom_brush
+            .as_ref()
+            .map_or((1, 1), |brush| (brush.get_width().max(1), brush.get_height().max(1)))
+    }
+
+    /// xorshift, good enough to scatter stamps.
+    fn next_random(&mut self, range: i32) -> i32 {
+        self.random_seed ^= self.random_seed << 13;
+        self.random_seed ^= self.random_seed >> 17;
+        self.random_seed ^= self.random_seed << 5;
+        (self.random_seed % (2 * range.max(0) as u32 + 1)) as i32 - range.max(0)
+    }
+
+    fn stamp_custom_brush(&self, editor: &mut AnsiEditor, pos: Position) {
+        let Some(brush) = &self.custom_brush else {
+            return;
+        };
+        let mid = Position::new(-(brush.get_width() / 2), -(brush.get_height() / 2));
+        for y in 0..brush.get_height() {
+            for x in 0..brush.get_width() {
+                let ch = brush.get_char((x, y));
+                if ch.is_visible() {
+                    editor.set_char(pos + mid + Position::new(x, y), ch);
+                }
+            }
+        }
+    }
+
+    /// Stamps along the stroke spaced by the brush size, so the stamps line up without overlapping.
+    fn line_custom_brush(&mut self, editor: &mut AnsiEditor, pos: Position) {
+        let Some(last) = self.last_stamp else {
+            self.stamp_custom_brush(editor, pos);
+            self.last_stamp = Some(pos);
+            return;
+        };
+        let (width, height) = self.custom_brush_size();
+        let dx = pos.x - last.x;
+        let dy = pos.y - last.y;
+        let steps = (dx.abs() as f32 / width as f32).max(dy.abs() as f32 / height as f32);
+        if steps < 1.0 {
+            return;
+        }
+        let mut cur = last;
+        for i in 1..=steps.floor() as i32 {
+            let t = i as f32 / steps;
+            cur = Position::new(last.x + (dx as f32 * t).round() as i32, last.y + (dy as f32 * t).round() as i32);
+            self.stamp_custom_brush(editor, cur);
+        }
+        self.last_stamp = Some(cur);
+    }
+
+    /// Stamps at a random offset within the brush size each time the cursor moved by half a stamp.
+    fn scatter_custom_brush(&mut self, editor: &mut AnsiEditor, pos: Position) {
+        let (width, height) = self.custom_brush_size();
+        if let Some(last) = self.last_stamp {
+            if (pos.x - last.x).abs() < (width + 1) / 2 && (pos.y - last.y).abs() < (height + 1) / 2 {
+                return;
+            }
+        }
+        let offset = Position::new(self.next_random(self.size), self.next_random(self.size / 2));
+        self.stamp_custom_brush(editor, pos + offset);
+        self.last_stamp = Some(pos);
+    }
+
+    fn paint_brush(&mut self, editor: &mut AnsiEditor, pos: Position) {
         if matches!(self.brush_mode, BrushMode::Custom) {
-            editor.join_overlay("brush");
+            match self.custom_mode {
+                CustomBrushMode::Stamp => editor.join_overlay("brush"),
+                CustomBrushMode::Scatter => self.scatter_custom_brush(editor, pos),
+                CustomBrushMode::Line => self.line_custom_brush(editor, pos),
+            }
             return;
         }
+        let caret_attr = editor.buffer_view.lock().get_caret().get_attribute();
 
         let use_selection = editor.buffer_view.lock().get_edit_state().is_something_selected();
         editor.buffer_view.lock().get_edit_state_mut().set_is_buffer_dirty();
@@ -61,53 +239,127 @@ impl BrushTool {
         } else {
             Position::default()
         };
+        let font_size = editor.buffer_view.lock().get_buffer().get_font_dimensions();
+        let buffer_type = editor.buffer_view.lock().get_buffer().buffer_type;
+        let ramp = self.get_ramp(buffer_type);
 
-        for y in 0..self.size {
-            for x in 0..self.size {
-                let pos = center + Position::new(x, y);
-                if use_selection && !editor.buffer_view.lock().get_edit_state().get_is_selected(pos + offset) {
-                    continue;
-                }
-                let ch = editor.get_char_from_cur_layer(pos);
-                let mut attribute = ch.attribute;
-                attribute.attr &= !icy_engine::attribute::INVISIBLE;
+        for (delta, dist) in self.footprint(font_size) {
+            let mut pos = pos + delta;
+            if editor.tile_mode {
+                pos = editor.wrap_position(pos);
+            }
+            if use_selection && !editor.buffer_view.lock().get_edit_state().get_is_selected(pos + offset) {
+                continue;
+            }
+            let ch = editor.get_char_from_cur_layer(pos);
+            let mut attribute = ch.attribute;
+            attribute.attr &= !icy_engine::attribute::INVISIBLE;
+
+            if self.color_mode.use_fore() {
+                attribute.set_foreground(caret_attr.get_foreground());
+            }
+            if self.color_mode.use_back() {
+                attribute.set_background(caret_attr.get_background());
+            }
 
-                if self.color_mode.use_fore() {
-                    attribute.set_foreground(caret_attr.get_foreground());
+            match &self.brush_mode {
+                BrushMode::Shade => {
+                    let max_level = if self.soft_edge {
+                        (((1.0 - dist) * ramp.len() as f32).ceil() as usize).clamp(1, ramp.len()) - 1
+                    } else {
+                        ramp.len() - 1
+                    };
+                    editor.set_char(pos, AttributedChar::new(next_shade(&ramp, ch.ch, max_level), attribute));
+                }
+                BrushMode::Char(ch) => {
+                    attribute.set_font_page(caret_attr.get_font_page());
+                    editor.set_char(pos, AttributedChar::new(*ch.borrow(), attribute));
                 }
-                if self.color_mode.use_back() {
-                    attribute.set_background(caret_attr.get_background());
+                BrushMode::Colorize => {
+                    editor.set_char(pos, AttributedChar::new(ch.ch, attribute));
                 }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl BrushTool {
+    fn show_library(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "tool-brush-library-label"));
+            if ui
+                .small_button("⟳")
+                .on_hover_text(fl!(crate::LANGUAGE_LOADER, "tool-brush-library-refresh"))
+                .clicked()
+            {
+                self.library = None;
+            }
+        });
+
+        if self.library.is_none() {
+            let library = load_brushes();
+            self.library_images = library.iter().map(|brush| brush_image(ctx, &brush.layer)).collect();
+            self.library = Some(library);
+        }
 
-                match &self.brush_mode {
-                    BrushMode::Shade => {
-                        let mut char_code = gradient[0];
-                        if ch.ch == gradient[gradient.len() - 1] {
-                            char_code = gradient[gradient.len() - 1];
-                        } else {
-                            for i in 0..gradient.len() - 1 {
-                                if ch.ch == gradient[i] {
-                                    char_code = gradient[i + 1];
-                                    break;
-                                }
+        let mut selected = None;
+        let mut delete = None;
+        if let Some(library) = &self.library {
+            if library.is_empty() {
+                ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "tool-brush-library-empty")).small());
+            }
+            egui::ScrollArea::vertical()
+                .id_source("brush_library_scroll_area")
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for (i, brush) in library.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            let sized_texture: SizedTexture = (&self.library_images[i]).into();
+                            let scale = (48.0 / sized_texture.size.y).min(96.0 / sized_texture.size.x);
+                            let response = Image::from_texture(sized_texture).fit_to_original_size(scale).sense(Sense::click()).ui(ui);
+                            if response.clicked() || ui.selectable_label(false, &brush.name).clicked() {
+                                selected = Some(i);
                             }
-                        }
-                        editor.set_char(pos, AttributedChar::new(char_code, attribute));
-                    }
-                    BrushMode::Char(ch) => {
-                        attribute.set_font_page(caret_attr.get_font_page());
-                        editor.set_char(center + Position::new(x, y), AttributedChar::new(*ch.borrow(), attribute));
-                    }
-                    BrushMode::Colorize => {
-                        editor.set_char(pos, AttributedChar::new(ch.ch, attribute));
+                            if ui
+                                .small_button("🗑")
+                                .on_hover_text(fl!(crate::LANGUAGE_LOADER, "tool-brush-library-delete"))
+                                .clicked()
+                            {
+                                delete = Some(i);
+                            }
+                        });
                     }
-                    _ => {}
+                });
+        }
+
+        if let Some(library) = &self.library {
+            if let Some(i) = selected {
+                self.custom_brush = Some(library[i].layer.clone());
+                self.brush_mode = BrushMode::Custom;
+            }
+            if let Some(i) = delete {
+                if let Err(err) = delete_brush(&library[i]) {
+                    log::error!("Error deleting brush {}: {err}", library[i].name);
                 }
+                self.library = None;
             }
         }
     }
 }
 
+fn brush_image(ctx: &egui::Context, brush: &Layer) -> TextureHandle {
+    let mut layer = brush.clone();
+    layer.set_offset((0, 0));
+    layer.role = icy_engine::Role::Normal;
+    let mut buf = icy_engine::Buffer::new(layer.get_size());
+    layer.set_title(buf.layers[0].get_title());
+    buf.layers.clear();
+    buf.layers.push(layer);
+    create_image(ctx, &buf)
+}
+
 impl Tool for BrushTool {
     fn get_icon(&self) -> &egui::Image<'static> {
         &super::icons::BRUSH_SVG
@@ -132,6 +384,30 @@ impl Tool for BrushTool {
             ui.label(fl!(crate::LANGUAGE_LOADER, "tool-size-label"));
             ui.add(egui::DragValue::new(&mut self.size).clamp_range(1..=20).speed(1));
         });
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "tool-brush-shape-label"));
+            ui.selectable_value(&mut self.shape, BrushShape::Square, fl!(crate::LANGUAGE_LOADER, "tool-brush-shape-square"));
+            ui.selectable_value(&mut self.shape, BrushShape::Round, fl!(crate::LANGUAGE_LOADER, "tool-brush-shape-round"));
+            ui.selectable_value(&mut self.shape, BrushShape::Diamond, fl!(crate::LANGUAGE_LOADER, "tool-brush-shape-diamond"));
+        });
+        ui.checkbox(&mut self.aspect_correction, fl!(crate::LANGUAGE_LOADER, "tool-brush-aspect-correction"));
+
+        if matches!(self.brush_mode, BrushMode::Shade) {
+            ui.checkbox(&mut self.soft_edge, fl!(crate::LANGUAGE_LOADER, "tool-brush-soft-edge"));
+            ui.horizontal(|ui| {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "tool-brush-ramp-label"));
+                egui::ComboBox::from_id_source("brush_shade_ramp_combobox")
+                    .selected_text(ramp_label(self.shade_ramp))
+                    .show_ui(ui, |ui| {
+                        for ramp in [ShadeRamp::Shade, ShadeRamp::HalfBlock, ShadeRamp::Custom] {
+                            ui.selectable_value(&mut self.shade_ramp, ramp, ramp_label(ramp));
+                        }
+                    });
+            });
+            if matches!(self.shade_ramp, ShadeRamp::Custom) {
+                ui.text_edit_singleline(&mut self.custom_ramp);
+            }
+        }
         /*
                 ui.radio_value(&mut self.brush_type, BrushType::Shade, fl!(crate::LANGUAGE_LOADER, "tool-shade"));
                 ui.horizontal(|ui| {
@@ -142,23 +418,19 @@ impl Tool for BrushTool {
                 });
                 ui.radio_value(&mut self.brush_type, BrushType::Color, fl!(crate::LANGUAGE_LOADER, "tool-colorize"));
         */
+        let mapping = editor_opt.as_ref().and_then(|editor| editor.get_glyph_mapping());
         let result = self.brush_mode.show_ui(ui, editor_opt, self.char_code.clone(), crate::paint::BrushUi::Brush);
 
         unsafe {
             if CUSTOM_BRUSH.is_some() {
                 self.custom_brush = CUSTOM_BRUSH.take();
+                // a new brush may have been saved to the library
+                self.library = None;
             }
         }
 
         if let Some(custom_brush) = &self.custom_brush {
-            let mut layer = custom_brush.clone();
-            layer.set_offset((0, 0));
-            layer.role = icy_engine::Role::Normal;
-            let mut buf = icy_engine::Buffer::new(layer.get_size());
-            layer.set_title(buf.layers[0].get_title());
-            buf.layers.clear();
-            buf.layers.push(layer);
-            self.image = Some(create_image(ctx, &buf));
+            self.image = Some(brush_image(ctx, custom_brush));
 
             ui.radio_value(&mut self.brush_mode, BrushMode::Custom, fl!(crate::LANGUAGE_LOADER, "tool-custom-brush"));
             if let Some(image) = &self.image {
@@ -168,7 +440,35 @@ impl Tool for BrushTool {
                 let image = Image::from_texture(sized_texture).fit_to_original_size(scale);
                 image.ui(ui);
             }
+
+            let mut transformed = None;
+            ui.horizontal(|ui| {
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "tool-brush-rotate")).clicked() {
+                    transformed = Some(rotate_layer_cw(custom_brush, mapping.as_ref()));
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "tool-brush-flip-x")).clicked() {
+                    transformed = Some(flip_layer_x(custom_brush, mapping.as_ref()));
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "tool-brush-flip-y")).clicked() {
+                    transformed = Some(flip_layer_y(custom_brush, mapping.as_ref()));
+                }
+            });
+            if transformed.is_some() {
+                self.custom_brush = transformed;
+            }
+
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.custom_mode, CustomBrushMode::Stamp, fl!(crate::LANGUAGE_LOADER, "tool-brush-stamp"));
+                ui.radio_value(
+                    &mut self.custom_mode,
+                    CustomBrushMode::Scatter,
+                    fl!(crate::LANGUAGE_LOADER, "tool-brush-scatter"),
+                );
+                ui.radio_value(&mut self.custom_mode, CustomBrushMode::Line, fl!(crate::LANGUAGE_LOADER, "tool-brush-line"));
+            });
         }
+
+        self.show_library(ctx, ui);
         result
     }
 
@@ -204,18 +504,15 @@ impl Tool for BrushTool {
                 }
             }
         } else {
-            let mid = Position::new(-(self.size / 2), -(self.size / 2));
-
-            if self.cur_pos != cur + mid {
-                self.cur_pos = cur + mid;
+            if self.cur_pos != cur {
+                self.cur_pos = cur;
+                let font_size = editor.buffer_view.lock().get_buffer().get_font_dimensions();
+                let footprint = self.footprint(font_size);
                 let lock = &mut editor.buffer_view.lock();
                 let get_tool_overlay_mask_mut = lock.get_edit_state_mut().get_tool_overlay_mask_mut();
                 get_tool_overlay_mask_mut.clear();
-                for y in 0..self.size {
-                    for x in 0..self.size {
-                        let pos = cur_abs + Position::new(x, y) + mid;
-                        get_tool_overlay_mask_mut.set_is_selected(pos, true);
-                    }
+                for (delta, _) in footprint {
+                    get_tool_overlay_mask_mut.set_is_selected(cur_abs + delta, true);
                 }
                 lock.get_edit_state_mut().set_is_buffer_dirty();
             }
@@ -228,7 +525,9 @@ impl Tool for BrushTool {
         if button == 1 {
             let _op: AtomicUndoGuard = editor.begin_atomic_undo(fl!(crate::LANGUAGE_LOADER, "undo-paint-brush"));
 
+            self.last_stamp = None;
             self.paint_brush(editor, pos);
+            self.last_stamp = None;
         }
         None
     }
@@ -240,12 +539,14 @@ impl Tool for BrushTool {
 
     fn handle_drag_begin(&mut self, editor: &mut AnsiEditor, _response: &egui::Response) -> Event {
         self.undo_op = Some(editor.begin_atomic_undo(fl!(crate::LANGUAGE_LOADER, "undo-paint-brush")));
+        self.last_stamp = None;
         self.paint_brush(editor, editor.drag_pos.cur);
         Event::None
     }
 
     fn handle_drag_end(&mut self, _editor: &mut AnsiEditor) -> Option<Message> {
         self.undo_op = None;
+        self.last_stamp = None;
         None
     }
 
@@ -73,7 +73,14 @@ impl Tool for DrawEllipseTool {
         let p1 = editor.drag_pos.start_half_block;
         let start = Position::new(p1.x.min(p2.x), p1.y.min(p2.y));
         let end = Position::new(p1.x.max(p2.x), p1.y.max(p2.y));
-        draw_ellipse(&mut editor.buffer_view.lock(), start, end, self.draw_mode.clone(), self.color_mode);
+        draw_ellipse(
+            &mut editor.buffer_view.lock(),
+            start,
+            end,
+            self.draw_mode.clone(),
+            self.color_mode,
+            editor.tile_mode,
+        );
         response
     }
 
@@ -71,8 +71,13 @@ impl FillOperation {
 
     pub fn fill(&mut self, editor: &mut AnsiEditor, pos: Position) {
         let mut pos_stack = vec![pos];
+        // chars are set after the flood, symmetric copies would otherwise change the area that is still being filled
+        let mut changes = Vec::new();
 
-        while let Some(pos) = pos_stack.pop() {
+        while let Some(mut pos) = pos_stack.pop() {
+            if editor.tile_mode {
+                pos = editor.wrap_position(pos);
+            }
             if pos.x < 0 || pos.y < 0 || pos.x >= self.size.width || pos.y >= self.size.height || !self.visited.insert(pos) {
                 continue;
             }
@@ -108,7 +113,7 @@ impl FillOperation {
 
                 repl_ch.set_font_page(editor.buffer_view.lock().get_caret().get_attribute().get_font_page());
                 repl_ch.attribute.attr &= !icy_engine::attribute::INVISIBLE;
-                editor.set_char(pos, repl_ch);
+                changes.push((pos, repl_ch));
             }
 
             pos_stack.push(pos + Position::new(-1, 0));
@@ -116,6 +121,10 @@ impl FillOperation {
             pos_stack.push(pos + Position::new(0, -1));
             pos_stack.push(pos + Position::new(0, 1));
         }
+
+        for (pos, ch) in changes {
+            editor.set_char(pos, ch);
+        }
     }
 }
 
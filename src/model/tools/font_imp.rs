@@ -1,6 +1,9 @@
 use std::{fs, io::Read, path::Path, sync::Arc, thread};
 
-use crate::{AnsiEditor, Message, Settings};
+use crate::{
+    util::figlet::{is_figlet_extension, load_figlet_font, smush, FigletLayout},
+    AnsiEditor, Message, Settings,
+};
 
 use super::{Event, MKey, MModifiers, Position, Tool};
 use eframe::{
@@ -9,13 +12,14 @@ use eframe::{
 };
 use egui::mutex::Mutex;
 use i18n_embed_fl::fl;
-use icy_engine::{editor::OperationType, Size, TextPane, TheDrawFont};
+use icy_engine::{editor::OperationType, AttributedChar, Size, TextPane, TheDrawFont};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use walkdir::{DirEntry, WalkDir};
 pub struct FontTool {
     pub selected_font: Arc<Mutex<i32>>,
     pub fonts: Arc<Mutex<Vec<TheDrawFont>>>,
     pub sizes: Vec<Size>,
+    pub layout: FigletLayout,
 }
 
 impl FontTool {
@@ -81,6 +85,21 @@ fn load_fonts(tdf_dir: &Path) -> Vec<TheDrawFont> {
             }
         }
 
+        if is_figlet_extension(&extension) {
+            match fs::read(path) {
+                Ok(data) => {
+                    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                    match load_figlet_font(&name, &data) {
+                        Ok(font) => fonts.push(font),
+                        Err(err) => log::error!("Error loading figlet font {}: {err}", path.display()),
+                    }
+                }
+                Err(err) => {
+                    log::error!("Failed to open figlet font: {}", err);
+                }
+            }
+        }
+
         if extension == "zip" {
             match fs::File::open(path) {
                 Ok(mut file) => {
@@ -106,7 +125,15 @@ fn read_zip_archive(data: Vec<u8>, fonts: &mut Vec<TheDrawFont>) {
                 match archive.by_index(i) {
                     Ok(mut file) => {
                         if let Some(name) = file.enclosed_name() {
-                            if name.to_string_lossy().to_ascii_lowercase().ends_with(".tdf") {
+                            let lower_name = name.to_string_lossy().to_ascii_lowercase();
+                            if lower_name.ends_with(".flf") || lower_name.ends_with(".tlf") {
+                                let font_name = name.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                                let mut data = Vec::new();
+                                file.read_to_end(&mut data).unwrap_or_default();
+                                if let Ok(font) = load_figlet_font(&font_name, &data) {
+                                    fonts.push(font);
+                                }
+                            } else if name.to_string_lossy().to_ascii_lowercase().ends_with(".tdf") {
                                 let mut data = Vec::new();
                                 file.read_to_end(&mut data).unwrap_or_default();
 
@@ -255,6 +282,19 @@ impl Tool for FontTool {
 
         if font_count > 0 {
             if let Some(font) = self.fonts.lock().get(selected_font as usize) {
+                if matches!(font.font_type, icy_engine::FontType::Block) {
+                    ui.add_space(16.0);
+                    ui.horizontal(|ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "font_tool_layout_label"));
+                        egui::ComboBox::from_id_source("font_tool_layout_combobox")
+                            .selected_text(layout_label(self.layout))
+                            .show_ui(ui, |ui| {
+                                for layout in [FigletLayout::FullWidth, FigletLayout::Kerning, FigletLayout::Smushing] {
+                                    ui.selectable_value(&mut self.layout, layout, layout_label(layout));
+                                }
+                            });
+                    });
+                }
                 if matches!(font.font_type, icy_engine::FontType::Outline) {
                     ui.add_space(32.0);
                     let mut msg = None;
@@ -281,6 +321,7 @@ impl Tool for FontTool {
         if button == 1 {
             editor.set_caret_position(pos);
             editor.buffer_view.lock().clear_selection();
+            self.sizes.clear();
         }
         None
     }
@@ -417,7 +458,11 @@ impl Tool for FontTool {
 
                 let _ = editor.buffer_view.lock().get_edit_state_mut().undo_caret_position();
 
-                let opt_size: Option<Size> = font.render(editor.buffer_view.lock().get_edit_state_mut(), ch as u8);
+                let opt_size: Option<Size> = if self.layout != FigletLayout::FullWidth && matches!(font.font_type, icy_engine::FontType::Block) {
+                    render_fitted(editor, font, ch as u8, self.sizes.last().map(|s| s.width), self.layout)
+                } else {
+                    font.render(editor.buffer_view.lock().get_edit_state_mut(), ch as u8)
+                };
                 if let Some(size) = opt_size {
                     editor.set_caret(c_pos.x + size.width + font.spaces, c_pos.y);
                     let new_pos = editor.get_caret_position();
@@ -436,6 +481,85 @@ impl Tool for FontTool {
     }
 }
 
+fn layout_label(layout: FigletLayout) -> String {
+    match layout {
+        FigletLayout::FullWidth => fl!(crate::LANGUAGE_LOADER, "font_tool_layout_full_width"),
+        FigletLayout::Kerning => fl!(crate::LANGUAGE_LOADER, "font_tool_layout_kerning"),
+        FigletLayout::Smushing => fl!(crate::LANGUAGE_LOADER, "font_tool_layout_smushing"),
+    }
+}
+
+fn is_blank(ch: AttributedChar) -> bool {
+    !ch.is_visible() || ch.ch == ' ' || ch.ch == '\0'
+}
+
+/// Renders a block font glyph moved left towards the previous glyph (kerning) and optionally merges
+/// the touching chars (smushing). Spaces of the glyph are transparent.
+/// Returns the advance of the caret, like `TheDrawFont::render` the glyph size.
+fn render_fitted(editor: &mut AnsiEditor, font: &TheDrawFont, ch: u8, prev_width: Option<i32>, layout: FigletLayout) -> Option<Size> {
+    let glyph = font.get_glyph(ch as char)?;
+    let rows: Vec<&[u8]> = glyph.data.split(|b| *b == 13).collect();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or_default() as i32;
+    let c_pos = editor.get_caret_position();
+
+    // (row, left char position, right char) pairs that touch after kerning
+    let mut shift = 0;
+    let mut smushed = Vec::new();
+    if let Some(prev_width) = prev_width {
+        let mut touching = Vec::new();
+        let mut min_gap = i32::MAX;
+        for (y, row) in rows.iter().enumerate() {
+            let Some(lead) = row.iter().position(|b| *b != b' ') else {
+                continue;
+            };
+            let y = c_pos.y + y as i32;
+            let left = (c_pos.x - prev_width..c_pos.x)
+                .rev()
+                .find(|x| !is_blank(editor.get_char_from_cur_layer(Position::new(*x, y))));
+            let gap = match left {
+                Some(x) => c_pos.x - 1 - x + lead as i32,
+                None => prev_width + lead as i32,
+            };
+            if gap < min_gap {
+                min_gap = gap;
+                touching.clear();
+            }
+            if gap == min_gap {
+                if let Some(x) = left {
+                    touching.push((Position::new(x, y), row[lead] as char));
+                }
+            }
+        }
+        if min_gap != i32::MAX {
+            shift = min_gap.min(prev_width);
+            if layout == FigletLayout::Smushing && shift < prev_width && !touching.is_empty() {
+                let merged: Option<Vec<(Position, char)>> = touching
+                    .iter()
+                    .map(|(pos, right)| smush(editor.get_char_from_cur_layer(*pos).ch, *right).map(|c| (*pos, c)))
+                    .collect();
+                if let Some(merged) = merged {
+                    shift += 1;
+                    smushed = merged;
+                }
+            }
+        }
+    }
+
+    let attr = editor.buffer_view.lock().get_caret().get_attribute();
+    let start_x = c_pos.x - shift;
+    for (y, row) in rows.iter().enumerate() {
+        for (x, b) in row.iter().enumerate() {
+            if *b == b' ' {
+                continue;
+            }
+            let pos = Position::new(start_x + x as i32, c_pos.y + y as i32);
+            let ch = smushed.iter().find(|(p, _)| *p == pos).map_or(*b as char, |(_, c)| *c);
+            editor.set_char(pos, AttributedChar::new(ch, attr));
+        }
+    }
+    Some(Size::new(width - shift, rows.len() as i32))
+}
+
 fn watch(path: &Path, fonts: &Arc<Mutex<Vec<TheDrawFont>>>) -> notify::Result<()> {
     let (tx, rx) = std::sync::mpsc::channel();
 
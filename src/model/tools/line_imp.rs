@@ -89,6 +89,7 @@ impl Tool for LineTool {
             p2,
             self.draw_mode.clone(),
             self.color_mode,
+            editor.tile_mode,
         );
         response
     }
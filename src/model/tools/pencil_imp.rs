@@ -71,6 +71,7 @@ impl Tool for PencilTool {
                 self.draw_mode.clone(),
                 self.color_mode,
                 PointRole::Line,
+                editor.tile_mode,
             );
             editor.join_overlay(fl!(crate::LANGUAGE_LOADER, "undo-pencil"));
         }
@@ -91,6 +92,7 @@ impl Tool for PencilTool {
             self.draw_mode.clone(),
             self.color_mode,
             PointRole::Line,
+            editor.tile_mode,
         );
 
         self.last_pos = editor.half_block_click_pos;
@@ -111,6 +113,7 @@ impl Tool for PencilTool {
             self.draw_mode.clone(),
             self.color_mode,
             PointRole::Line,
+            editor.tile_mode,
         );
         editor.buffer_view.lock().get_edit_state_mut().set_is_buffer_dirty();
         Event::None
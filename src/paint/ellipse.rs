@@ -61,7 +61,7 @@ fn get_ellipse_points(from: Position, to: Position) -> Vec<Position> {
     result
 }
 
-pub fn draw_ellipse(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode) {
+pub fn draw_ellipse(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode, wrap: bool) {
     let mut from = from.into();
     let mut to = to.into();
     let mut y_mul = 1;
@@ -74,12 +74,12 @@ pub fn draw_ellipse(buffer_view: &mut BufferView, from: impl Into<Position>, to:
     for point in get_ellipse_points(from, to) {
         let pos = (point.x, point.y * y_mul);
         if visited.insert(pos) {
-            plot_point(buffer_view, pos, mode.clone(), color_mode, PointRole::Line);
+            plot_point(buffer_view, pos, mode.clone(), color_mode, PointRole::Line, wrap);
         }
     }
 }
 
-pub fn fill_ellipse(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode) {
+pub fn fill_ellipse(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode, wrap: bool) {
     let mut from = from.into();
     let mut to = to.into();
     let mut y_mul = 1;
@@ -99,7 +99,7 @@ pub fn fill_ellipse(buffer_view: &mut BufferView, from: impl Into<Position>, to:
         }
 
         while x1.x < x2.x {
-            plot_point(buffer_view, (x1.x, x1.y * y_mul), mode.clone(), color_mode, PointRole::Line);
+            plot_point(buffer_view, (x1.x, x1.y * y_mul), mode.clone(), color_mode, PointRole::Line, wrap);
 
             x1.x += 1;
         }
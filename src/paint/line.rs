@@ -33,7 +33,7 @@ fn get_line_points(from: Position, to: Position) -> Vec<Position> {
     result
 }
 
-pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode) {
+pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode, wrap: bool) {
     let mut from = from.into();
     let mut to = to.into();
     let mut y_mul = 1;
@@ -49,7 +49,7 @@ pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: im
     }
     if !matches!(mode, BrushMode::Outline) {
         for point in get_line_points(from, to) {
-            plot_point(buffer_view, (point.x, point.y * y_mul), mode.clone(), color_mode, PointRole::Line);
+            plot_point(buffer_view, (point.x, point.y * y_mul), mode.clone(), color_mode, PointRole::Line, wrap);
         }
         return;
     }
@@ -82,11 +82,11 @@ pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: im
             // Left side
             if ny2 < cy2 {
                 println!("plot ne corner above");
-                plot_point(buffer_view, point + Position::new(0, -1), mode.clone(), color_mode, PointRole::NECorner);
+                plot_point(buffer_view, point + Position::new(0, -1), mode.clone(), color_mode, PointRole::NECorner, wrap);
                 PointRole::SWCorner
             } else if ny2 > cy2 {
                 println!("plot se corner below");
-                plot_point(buffer_view, point + Position::new(0, 2), mode.clone(), color_mode, PointRole::SECorner);
+                plot_point(buffer_view, point + Position::new(0, 2), mode.clone(), color_mode, PointRole::SECorner, wrap);
                 PointRole::NWCorner
             } else {
                 PointRole::TopSide
@@ -95,11 +95,11 @@ pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: im
             // Right Side
             if ny2 < cy2 {
                 println!("plot nw corner above");
-                plot_point(buffer_view, point + Position::new(0, -1), mode.clone(), color_mode, PointRole::NWCorner);
+                plot_point(buffer_view, point + Position::new(0, -1), mode.clone(), color_mode, PointRole::NWCorner, wrap);
                 PointRole::SECorner
             } else if ny2 > cy2 {
                 println!("plot sw corner below");
-                plot_point(buffer_view, point + Position::new(0, 2), mode.clone(), color_mode, PointRole::SWCorner);
+                plot_point(buffer_view, point + Position::new(0, 2), mode.clone(), color_mode, PointRole::SWCorner, wrap);
                 PointRole::NECorner
             } else {
                 // telel
@@ -110,12 +110,12 @@ pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: im
                     } else {
                         println!("plot ne corner right");
                         i += 1;
-                        plot_point(buffer_view, point + Position::new(1, 0), mode.clone(), color_mode, PointRole::NECorner);
+                        plot_point(buffer_view, point + Position::new(1, 0), mode.clone(), color_mode, PointRole::NECorner, wrap);
                         PointRole::SWCorner
                     }
                 } else if point.x > next.x {
                     println!("plot nw corner left");
-                    plot_point(buffer_view, point + Position::new(-1, 0), mode.clone(), color_mode, PointRole::NWCorner);
+                    plot_point(buffer_view, point + Position::new(-1, 0), mode.clone(), color_mode, PointRole::NWCorner, wrap);
                     PointRole::SECorner
                 } else {
                     // case 4
@@ -124,7 +124,7 @@ pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: im
                         PointRole::TopSide
                     } else {
                         println!("plot ne corner right");
-                        plot_point(buffer_view, point + Position::new(1, 0), mode.clone(), color_mode, PointRole::NECorner);
+                        plot_point(buffer_view, point + Position::new(1, 0), mode.clone(), color_mode, PointRole::NECorner, wrap);
                         PointRole::SWCorner
                     }
                 }
@@ -132,7 +132,7 @@ pub fn draw_line(buffer_view: &mut BufferView, from: impl Into<Position>, to: im
         } else {
             PointRole::LeftSide
         };
-        plot_point(buffer_view, point, mode.clone(), color_mode, role);
+        plot_point(buffer_view, point, mode.clone(), color_mode, role, wrap);
         i += 1;
     }
 }
@@ -268,8 +268,16 @@ impl ColorMode {
     }
 }
 
-pub fn plot_point(buffer_view: &mut BufferView, pos: impl Into<Position>, mut mode: BrushMode, color_mode: ColorMode, point_role: PointRole) {
-    let pos = pos.into();
+/// With `wrap` points outside of the current layer continue on the opposite edge (tile mode).
+pub fn plot_point(buffer_view: &mut BufferView, pos: impl Into<Position>, mut mode: BrushMode, color_mode: ColorMode, point_role: PointRole, wrap: bool) {
+    let mut pos = pos.into();
+    if wrap {
+        if let Some(layer) = buffer_view.get_edit_state().get_cur_layer() {
+            // positions are in half block resolution
+            let size = layer.get_size();
+            pos = Position::new(pos.x.rem_euclid(size.width.max(1)), pos.y.rem_euclid(2 * size.height.max(1)));
+        }
+    }
     let text_pos = Position::new(pos.x, pos.y / 2);
     let mut ch = if let Some(layer) = buffer_view.get_edit_state().get_cur_layer() {
         layer.get_char(text_pos)
@@ -3,7 +3,7 @@ use icy_engine_gui::BufferView;
 
 use super::{plot_point, BrushMode, ColorMode, PointRole};
 
-pub fn draw_rectangle(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode) {
+pub fn draw_rectangle(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode, wrap: bool) {
     let mut from = from.into();
     let mut to = to.into();
     let mut y_mul = 1;
@@ -14,25 +14,25 @@ pub fn draw_rectangle(buffer_view: &mut BufferView, from: impl Into<Position>, t
     }
 
     for x in from.x + 1..to.x {
-        plot_point(buffer_view, (x, from.y * y_mul), mode.clone(), color_mode, PointRole::TopSide);
-        plot_point(buffer_view, (x, to.y * y_mul), mode.clone(), color_mode, PointRole::BottomSide);
+        plot_point(buffer_view, (x, from.y * y_mul), mode.clone(), color_mode, PointRole::TopSide, wrap);
+        plot_point(buffer_view, (x, to.y * y_mul), mode.clone(), color_mode, PointRole::BottomSide, wrap);
     }
 
     for y in from.y + 1..to.y {
-        plot_point(buffer_view, (from.x, y * y_mul), mode.clone(), color_mode, PointRole::LeftSide);
-        plot_point(buffer_view, (to.x, y * y_mul), mode.clone(), color_mode, PointRole::RightSide);
+        plot_point(buffer_view, (from.x, y * y_mul), mode.clone(), color_mode, PointRole::LeftSide, wrap);
+        plot_point(buffer_view, (to.x, y * y_mul), mode.clone(), color_mode, PointRole::RightSide, wrap);
     }
 
     if from.x != to.x && from.y != to.y {
-        plot_point(buffer_view, (from.x, from.y * y_mul), mode.clone(), color_mode, PointRole::NWCorner);
-        plot_point(buffer_view, (to.x, from.y * y_mul), mode.clone(), color_mode, PointRole::NECorner);
+        plot_point(buffer_view, (from.x, from.y * y_mul), mode.clone(), color_mode, PointRole::NWCorner, wrap);
+        plot_point(buffer_view, (to.x, from.y * y_mul), mode.clone(), color_mode, PointRole::NECorner, wrap);
 
-        plot_point(buffer_view, (from.x, to.y * y_mul), mode.clone(), color_mode, PointRole::SWCorner);
-        plot_point(buffer_view, (to.x, to.y * y_mul), mode.clone(), color_mode, PointRole::SECorner);
+        plot_point(buffer_view, (from.x, to.y * y_mul), mode.clone(), color_mode, PointRole::SWCorner, wrap);
+        plot_point(buffer_view, (to.x, to.y * y_mul), mode.clone(), color_mode, PointRole::SECorner, wrap);
     }
 }
 
-pub fn fill_rectangle(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode) {
+pub fn fill_rectangle(buffer_view: &mut BufferView, from: impl Into<Position>, to: impl Into<Position>, mode: BrushMode, color_mode: ColorMode, wrap: bool) {
     let mut from = from.into();
     let mut to = to.into();
     let mut y_mul = 1;
@@ -44,10 +44,10 @@ pub fn fill_rectangle(buffer_view: &mut BufferView, from: impl Into<Position>, t
 
     for y in from.y + 1..to.y {
         for x in from.x + 1..to.x {
-            plot_point(buffer_view, (x, y * y_mul), mode.clone(), color_mode, PointRole::Fill);
+            plot_point(buffer_view, (x, y * y_mul), mode.clone(), color_mode, PointRole::Fill, wrap);
         }
     }
     if matches!(mode, BrushMode::HalfBlock) {
-        draw_rectangle(buffer_view, from, to, mode, color_mode);
+        draw_rectangle(buffer_view, from, to, mode, color_mode, wrap);
     }
 }
@@ -72,6 +72,27 @@ impl CommandState for LineNumberState {
     }
 }
 
+#[derive(Default)]
+pub struct TileModeState {}
+
+impl CommandState for TileModeState {
+    fn is_enabled(&self, open_tab_opt: Option<&DocumentTab>) -> bool {
+        if let Some(pane) = open_tab_opt {
+            return pane.doc.lock().get_ansi_editor().is_some();
+        }
+        false
+    }
+
+    fn is_checked(&self, open_tab_opt: Option<&DocumentTab>) -> Option<bool> {
+        if let Some(pane) = open_tab_opt {
+            if let Some(editor) = pane.doc.lock().get_ansi_editor() {
+                return Some(editor.tile_mode);
+            }
+        }
+        Some(false)
+    }
+}
+
 #[derive(Default)]
 pub struct FileOpenState {}
 
@@ -81,6 +102,15 @@ impl CommandState for FileOpenState {
     }
 }
 
+#[derive(Default)]
+pub struct FileHasPathState {}
+
+impl CommandState for FileHasPathState {
+    fn is_enabled(&self, open_tab_opt: Option<&DocumentTab>) -> bool {
+        open_tab_opt.map_or(false, |pane| !pane.is_untitled())
+    }
+}
+
 #[derive(Default)]
 pub struct FileIsDirtyState {}
 
@@ -475,6 +505,9 @@ keys![
     (erase_selection, "menu-erase", DeleteSelection, BufferOpenState, Delete, NONE),
     (flip_x, "menu-flipx", FlipX, BufferOpenState),
     (flip_y, "menu-flipy", FlipY, BufferOpenState),
+    (rotate_90, "menu-rotate90", Rotate90, BufferOpenState),
+    (rotate_180, "menu-rotate180", Rotate180, BufferOpenState),
+    (rotate_270, "menu-rotate270", Rotate270, BufferOpenState),
     (justifycenter, "menu-justifycenter", Center, BufferOpenState),
     (justifyleft, "menu-justifyleft", JustifyLeft, BufferOpenState),
     (justifyright, "menu-justifyright", JustifyRight, BufferOpenState),
@@ -553,6 +586,13 @@ keys![
     (select_palette, "menu-select_palette", SelectPalette, CanSwitchPaletteState),
     (show_layer_borders, "menu-show_layer_borders", ToggleLayerBorders, LayerBordersState),
     (show_line_numbers, "menu-show_line_numbers", ToggleLineNumbers, LineNumberState),
+    (tile_mode, "menu-tile_mode", ToggleTileMode, TileModeState),
+    (offset_by_half, "menu-offset_by_half", OffsetByHalf, BufferOpenState),
+    (find_replace, "menu-find_replace", ShowFindReplaceDialog, BufferOpenState, F, CTRL),
+    (remap_colors, "menu-remap_colors", ShowRemapColorsDialog, BufferOpenState),
+    (convert_palette, "menu-convert_palette", ShowConvertPaletteDialog, BufferOpenState),
+    (compare, "menu-compare", ShowCompareDialog, BufferOpenState),
+    (document_history, "menu-document_history", ShowDocumentHistoryDialog, FileHasPathState),
     (open_plugin_directory, "menu-open_plugin_directory", OpenPluginDirectory, AlwaysEnabledState),
     (next_fg_color, "menu-next_fg_color", NextFgColor, BufferOpenState, ArrowDown, CTRL),
     (prev_fg_color, "menu-prev_fg_color", PreviousFgColor, BufferOpenState, ArrowUp, CTRL),
@@ -3,7 +3,7 @@ use egui_modal::Modal;
 use egui_tiles::TileId;
 use i18n_embed_fl::fl;
 
-use crate::{util::autosave::remove_autosave, MainWindow, Message, SaveFileDialog, TerminalResult};
+use crate::{util::autosave::discard_recovery, MainWindow, Message, SaveFileDialog, TerminalResult};
 
 pub struct AskCloseFileDialog {
     do_commit: bool,
@@ -85,7 +85,7 @@ impl crate::ModalDialog for AskCloseFileDialog {
         }
         window.document_tree.tiles.remove(self.id);
         if let Some(path) = &self.path {
-            remove_autosave(path);
+            discard_recovery(path);
         }
         Ok(msg)
     }
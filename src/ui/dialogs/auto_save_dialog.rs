@@ -2,18 +2,20 @@ use eframe::egui;
 use egui_modal::Modal;
 use i18n_embed_fl::fl;
 
-use crate::{util::autosave::remove_autosave, MainWindow, Message, TerminalResult};
+use crate::{util::autosave::discard_recovery, DocumentHistoryDialog, MainWindow, Message, TerminalResult};
 
 #[derive(Default)]
 pub struct AutoSaveDialog {
     finish: bool,
     load_autosave: bool,
+    show_history: bool,
     path: std::path::PathBuf,
 }
 impl AutoSaveDialog {
     pub(crate) fn new(path: std::path::PathBuf) -> Self {
         Self {
             load_autosave: false,
+            show_history: false,
             finish: false,
             path,
         }
@@ -41,11 +43,17 @@ impl crate::ModalDialog for AutoSaveDialog {
                 }
 
                 if ui.button(fl!(crate::LANGUAGE_LOADER, "autosave-dialog-discard_autosave_button")).clicked() {
-                    remove_autosave(&self.path);
+                    discard_recovery(&self.path);
                     self.load_autosave = false;
                     self.finish = true;
                     result = true;
                 }
+
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "autosave-dialog-history_button")).clicked() {
+                    self.show_history = true;
+                    self.finish = true;
+                    result = true;
+                }
             });
         });
         modal.open();
@@ -56,7 +64,11 @@ impl crate::ModalDialog for AutoSaveDialog {
         self.finish
     }
 
-    fn commit_self(&self, _window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+    fn commit_self(&self, window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+        if self.show_history {
+            window.open_dialog(DocumentHistoryDialog::new(self.path.clone()));
+            return Ok(None);
+        }
         Ok(Some(Message::LoadFile(self.path.clone(), self.load_autosave)))
     }
 }
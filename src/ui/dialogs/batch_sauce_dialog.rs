@@ -0,0 +1,233 @@
+use std::{path::PathBuf, time::Duration};
+
+use eframe::egui::{self, Layout, RichText};
+use egui_file::FileDialog;
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+
+use crate::{
+    util::sauce_batch::{preview_batch, write_batch, BatchEntry, SauceTemplate},
+    MainWindow, Message, ModalDialog, TerminalResult,
+};
+
+pub struct BatchSauceDialog {
+    should_commit: bool,
+    path: String,
+    file_dialog: Option<FileDialog>,
+
+    set_title: bool,
+    title: String,
+    set_author: bool,
+    author: String,
+    set_group: bool,
+    group: String,
+    set_date: bool,
+    date: String,
+
+    preview: Vec<BatchEntry>,
+    /// Reading a whole pack is slow, the preview is only updated on request and writing needs a current one.
+    preview_is_current: bool,
+    error: Option<String>,
+}
+
+impl Default for BatchSauceDialog {
+    fn default() -> Self {
+        Self {
+            should_commit: false,
+            path: String::new(),
+            file_dialog: None,
+            set_title: false,
+            title: "{filename}".to_string(),
+            set_author: false,
+            author: String::new(),
+            set_group: false,
+            group: String::new(),
+            set_date: false,
+            date: "{date}".to_string(),
+            preview: Vec::new(),
+            preview_is_current: false,
+            error: None,
+        }
+    }
+}
+
+impl BatchSauceDialog {
+    fn template(&self) -> SauceTemplate {
+        SauceTemplate {
+            title: self.set_title.then(|| self.title.clone()),
+            author: self.set_author.then(|| self.author.clone()),
+            group: self.set_group.then(|| self.group.clone()),
+            date: self.set_date.then(|| self.date.clone()),
+        }
+    }
+
+    fn update_preview(&mut self) {
+        self.preview.clear();
+        self.preview_is_current = true;
+        self.error = None;
+        if self.path.is_empty() {
+            return;
+        }
+        match preview_batch(&PathBuf::from(&self.path), &self.template()) {
+            Ok(preview) => self.preview = preview,
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+
+    fn show_field(ui: &mut egui::Ui, label: String, set: &mut bool, value: &mut String) -> bool {
+        let mut changed = false;
+        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+            changed |= ui.checkbox(set, label).changed();
+        });
+        changed |= ui.add_enabled(*set, egui::TextEdit::singleline(value)).changed();
+        ui.end_row();
+        changed
+    }
+}
+
+fn diff_text(old: &str, new: &str) -> String {
+    if old == new {
+        new.to_string()
+    } else {
+        format!("{old} → {new}")
+    }
+}
+
+impl ModalDialog for BatchSauceDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        if let Some(dialog) = &mut self.file_dialog {
+            if dialog.show(ctx).selected() {
+                if let Some(path) = dialog.path() {
+                    self.path = path.to_string_lossy().to_string();
+                    self.update_preview();
+                }
+            }
+            if !dialog.visible() {
+                self.file_dialog = None;
+            }
+            return false;
+        }
+
+        let mut result = false;
+        let modal = Modal::new(ctx, "batch_sauce_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(600.);
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "batch-sauce-title"));
+
+            modal.frame(ui, |ui| {
+                let mut changed = false;
+                let mut update = false;
+                egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "batch-sauce-path-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        let response = ui.add(egui::TextEdit::singleline(&mut self.path).desired_width(300.0));
+                        changed |= response.changed();
+                        update |= response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "batch-sauce-choose-directory")).clicked() {
+                            let mut dialog = FileDialog::select_folder(None);
+                            dialog.open();
+                            self.file_dialog = Some(dialog);
+                        }
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "batch-sauce-choose-zip")).clicked() {
+                            let mut dialog = FileDialog::open_file(None)
+                                .show_files_filter(Box::new(|path| path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case("zip"))));
+                            dialog.open();
+                            self.file_dialog = Some(dialog);
+                        }
+                    });
+                    ui.end_row();
+
+                    changed |= Self::show_field(ui, fl!(crate::LANGUAGE_LOADER, "edit-sauce-title-label"), &mut self.set_title, &mut self.title);
+                    changed |= Self::show_field(
+                        ui,
+                        fl!(crate::LANGUAGE_LOADER, "edit-sauce-author-label"),
+                        &mut self.set_author,
+                        &mut self.author,
+                    );
+                    changed |= Self::show_field(ui, fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label"), &mut self.set_group, &mut self.group);
+                    changed |= Self::show_field(ui, fl!(crate::LANGUAGE_LOADER, "batch-sauce-date-label"), &mut self.set_date, &mut self.date);
+                });
+                ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "batch-sauce-template-help")).small());
+                update |= ui.button(fl!(crate::LANGUAGE_LOADER, "batch-sauce-update-preview")).clicked();
+                if update {
+                    self.update_preview();
+                } else if changed {
+                    self.preview_is_current = false;
+                }
+
+                ui.add_space(8.0);
+                if let Some(err) = &self.error {
+                    ui.label(RichText::new(err).color(ui.style().visuals.error_fg_color));
+                }
+                let changed_files = self.preview.iter().filter(|e| e.is_changed()).count();
+                ui.label(fl!(
+                    crate::LANGUAGE_LOADER,
+                    "batch-sauce-preview-label",
+                    files = self.preview.len(),
+                    changed = changed_files
+                ));
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("batch_sauce_preview").num_columns(5).striped(true).show(ui, |ui| {
+                        ui.strong(fl!(crate::LANGUAGE_LOADER, "batch-sauce-file-column"));
+                        ui.strong(fl!(crate::LANGUAGE_LOADER, "edit-sauce-title-label"));
+                        ui.strong(fl!(crate::LANGUAGE_LOADER, "edit-sauce-author-label"));
+                        ui.strong(fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label"));
+                        ui.strong(fl!(crate::LANGUAGE_LOADER, "batch-sauce-date-label"));
+                        ui.end_row();
+
+                        for entry in &self.preview {
+                            let name = if entry.old.is_none() {
+                                fl!(crate::LANGUAGE_LOADER, "batch-sauce-new-record", name = entry.name.clone())
+                            } else {
+                                entry.name.clone()
+                            };
+                            if entry.is_changed() {
+                                ui.strong(name);
+                            } else {
+                                ui.label(name);
+                            }
+                            let old = entry.old.clone().unwrap_or_default();
+                            ui.label(diff_text(&old.title, &entry.new.title));
+                            ui.label(diff_text(&old.author, &entry.new.author));
+                            ui.label(diff_text(&old.group, &entry.new.group));
+                            ui.label(diff_text(&old.date, &entry.new.date));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+            modal.buttons(ui, |ui| {
+                let enabled = self.preview_is_current && self.error.is_none() && self.preview.iter().any(|e| e.is_changed());
+                if ui
+                    .add_enabled(enabled, egui::Button::new(fl!(crate::LANGUAGE_LOADER, "batch-sauce-write-button")))
+                    .clicked()
+                {
+                    self.should_commit = true;
+                    result = true;
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit_self(&self, window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+        let changed = write_batch(&PathBuf::from(&self.path), &self.template())?;
+        window
+            .toasts
+            .info(fl!(crate::LANGUAGE_LOADER, "batch-sauce-written", files = changed))
+            .set_duration(Some(Duration::from_secs(5)));
+        Ok(None)
+    }
+}
@@ -0,0 +1,481 @@
+use std::{fs, path::PathBuf};
+
+use eframe::{
+    egui::{self, RichText, TextureHandle},
+    epaint::{Color32, Pos2, Rect, Rounding, Stroke, Vec2},
+};
+use egui_file::FileDialog;
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::{Buffer, Position, TextPane};
+
+use crate::{
+    add_child, create_image,
+    util::{
+        autosave::get_latest_snapshot,
+        buffer_diff::{compare_buffers, get_merged_char, get_visible_layer, normalize, CellDifference, LineDifference},
+    },
+    AnsiEditor, MainWindow, Message, ModalDialog, TerminalResult,
+};
+
+const LEFT: usize = 0;
+const RIGHT: usize = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+enum CompareSource {
+    /// Current state of the active document
+    Document,
+    LastSaved,
+    AutoSave,
+    File(PathBuf),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CompareView {
+    SideBySide,
+    Overlay,
+}
+
+struct CompareSide {
+    source: Option<CompareSource>,
+    buffer: Option<Buffer>,
+    /// Layers & buffer positions of the cells copied from the other side
+    changed: Vec<(usize, Position)>,
+    texture: Option<TextureHandle>,
+}
+
+impl CompareSide {
+    fn empty() -> Self {
+        Self {
+            source: None,
+            buffer: None,
+            changed: Vec::new(),
+            texture: None,
+        }
+    }
+}
+
+pub struct CompareDialog {
+    should_commit: bool,
+    file_dialog: Option<(usize, FileDialog)>,
+
+    document: Buffer,
+    path: Option<PathBuf>,
+    sides: [CompareSide; 2],
+    differences: Vec<LineDifference>,
+
+    view: CompareView,
+    overlay_opacity: f32,
+    selected: Option<Position>,
+    error: Option<String>,
+}
+
+impl CompareDialog {
+    pub fn new(editor: &AnsiEditor, path: Option<PathBuf>) -> Self {
+        let mut result = Self {
+            should_commit: false,
+            file_dialog: None,
+            document: editor.buffer_view.lock().get_buffer().clone(),
+            path,
+            sides: [CompareSide::empty(), CompareSide::empty()],
+            differences: Vec::new(),
+            view: CompareView::SideBySide,
+            overlay_opacity: 0.5,
+            selected: None,
+            error: None,
+        };
+        result.load(LEFT, CompareSource::Document);
+        if result.is_available(&CompareSource::LastSaved) {
+            result.load(RIGHT, CompareSource::LastSaved);
+        } else if result.is_available(&CompareSource::AutoSave) {
+            result.load(RIGHT, CompareSource::AutoSave);
+        }
+        result
+    }
+
+    fn is_available(&self, source: &CompareSource) -> bool {
+        match source {
+            CompareSource::Document | CompareSource::File(_) => true,
+            CompareSource::LastSaved => self.path.as_ref().map_or(false, |path| path.exists()),
+            CompareSource::AutoSave => self.path.as_ref().map_or(false, |path| get_latest_snapshot(path).is_some()),
+        }
+    }
+
+    fn source_label(source: &Option<CompareSource>) -> String {
+        match source {
+            None => fl!(crate::LANGUAGE_LOADER, "compare-source-none"),
+            Some(CompareSource::Document) => fl!(crate::LANGUAGE_LOADER, "compare-source-document"),
+            Some(CompareSource::LastSaved) => fl!(crate::LANGUAGE_LOADER, "compare-source-last-saved"),
+            Some(CompareSource::AutoSave) => fl!(crate::LANGUAGE_LOADER, "compare-source-autosave"),
+            Some(CompareSource::File(path)) => path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        }
+    }
+
+    fn load_buffer(&self, source: &CompareSource) -> anyhow::Result<Buffer> {
+        let (load_path, format_path) = match source {
+            CompareSource::Document => return Ok(self.document.clone()),
+            CompareSource::File(path) => (path.clone(), path.clone()),
+            CompareSource::LastSaved | CompareSource::AutoSave => {
+                let Some(path) = &self.path else {
+                    return Err(anyhow::anyhow!("document has no file"));
+                };
+                if *source == CompareSource::AutoSave {
+                    let Some(snapshot) = get_latest_snapshot(path) else {
+                        return Err(anyhow::anyhow!("no autosave for {}", path.display()));
+                    };
+                    (snapshot.file, path.clone())
+                } else {
+                    (path.clone(), path.clone())
+                }
+            }
+        };
+        let data = fs::read(load_path)?;
+        let mut buffer = Buffer::from_bytes(&format_path, true, &data)?;
+        buffer.is_terminal_buffer = false;
+        Ok(buffer)
+    }
+
+    fn load(&mut self, side: usize, source: CompareSource) {
+        self.error = None;
+        match self.load_buffer(&source) {
+            Ok(buffer) => {
+                self.sides[side] = CompareSide {
+                    source: Some(source),
+                    buffer: Some(buffer),
+                    changed: Vec::new(),
+                    texture: None,
+                };
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self.update_differences();
+    }
+
+    fn update_differences(&mut self) {
+        self.differences = match (&self.sides[LEFT].buffer, &self.sides[RIGHT].buffer) {
+            (Some(left), Some(right)) => compare_buffers(left, right),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Copies the visible cells of one side to the other side, each into the layer that shows the cell there.
+    fn copy_cells(&mut self, from: usize, positions: Vec<Position>) {
+        let to = 1 - from;
+        let (Some(source), Some(target)) = (self.sides[from].buffer.clone(), self.sides[to].buffer.as_mut()) else {
+            return;
+        };
+        for pos in positions {
+            let Some(layer) = get_visible_layer(target, pos) else {
+                continue;
+            };
+            let offset = target.layers[layer].get_offset();
+            target.layers[layer].set_char(pos - offset, normalize(get_merged_char(&source, pos)));
+            if !self.sides[to].changed.contains(&(layer, pos)) {
+                self.sides[to].changed.push((layer, pos));
+            }
+        }
+        self.sides[to].texture = None;
+        self.update_differences();
+    }
+
+    fn all_differences(&self) -> Vec<Position> {
+        self.differences
+            .iter()
+            .flat_map(|line| line.cells.iter().map(move |(x, _)| Position::new(*x, line.y)))
+            .collect()
+    }
+
+    fn difference_text(diff: CellDifference) -> String {
+        let mut parts = Vec::new();
+        if diff.ch {
+            parts.push(fl!(crate::LANGUAGE_LOADER, "compare-diff-char"));
+        }
+        if diff.colors {
+            parts.push(fl!(crate::LANGUAGE_LOADER, "compare-diff-colors"));
+        }
+        if diff.font_page {
+            parts.push(fl!(crate::LANGUAGE_LOADER, "compare-diff-font-page"));
+        }
+        parts.join(", ")
+    }
+
+    fn show_file_dialog(&mut self, ctx: &egui::Context) {
+        let Some((side, file_dialog)) = &mut self.file_dialog else {
+            return;
+        };
+        let side = *side;
+        let selected = if file_dialog.show(ctx).selected() {
+            file_dialog.path().map(|p| p.to_path_buf())
+        } else {
+            None
+        };
+        if !file_dialog.visible() {
+            self.file_dialog = None;
+        }
+        if let Some(path) = selected {
+            self.load(side, CompareSource::File(path));
+        }
+    }
+
+    fn show_source_selector(&mut self, ui: &mut egui::Ui, side: usize) {
+        let mut load = None;
+        let mut open_file = false;
+        let other_is_document = self.sides[1 - side].source == Some(CompareSource::Document);
+        egui::ComboBox::from_id_source(("compare_source", side))
+            .width(220.0)
+            .selected_text(Self::source_label(&self.sides[side].source))
+            .show_ui(ui, |ui| {
+                for source in [CompareSource::Document, CompareSource::LastSaved, CompareSource::AutoSave] {
+                    let enabled = self.is_available(&source) && !(source == CompareSource::Document && other_is_document);
+                    let selected = self.sides[side].source.as_ref() == Some(&source);
+                    let label = Self::source_label(&Some(source.clone()));
+                    if ui.add_enabled(enabled, egui::SelectableLabel::new(selected, label)).clicked() {
+                        load = Some(source);
+                    }
+                }
+                if ui.selectable_label(false, fl!(crate::LANGUAGE_LOADER, "compare-source-file")).clicked() {
+                    open_file = true;
+                }
+            });
+        if let Some(source) = load {
+            self.load(side, source);
+        }
+        if open_file {
+            let mut dialog = FileDialog::open_file(self.path.clone());
+            dialog.open();
+            self.file_dialog = Some((side, dialog));
+        }
+    }
+
+    /// Paints a buffer image with the differences and returns the clicked cell.
+    fn paint_side(&self, ui: &mut egui::Ui, side: usize, max_width: f32) -> Option<Position> {
+        let (Some(texture), Some(buffer)) = (&self.sides[side].texture, &self.sides[side].buffer) else {
+            return None;
+        };
+        let size = texture.size_vec2();
+        let scale = (max_width / size.x).min(1.0);
+        let (rect, response) = ui.allocate_exact_size(size * scale, egui::Sense::click());
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        let painter = ui.painter_at(rect);
+        painter.image(texture.id(), rect, uv, Color32::WHITE);
+        if self.view == CompareView::Overlay {
+            if let Some(texture) = &self.sides[RIGHT].texture {
+                let right_size = texture.size_vec2() * scale;
+                painter.image(
+                    texture.id(),
+                    Rect::from_min_size(rect.min, right_size),
+                    uv,
+                    Color32::WHITE.gamma_multiply(self.overlay_opacity),
+                );
+            }
+        }
+        let cell = Vec2::new(
+            rect.width() / buffer.get_width().max(1) as f32,
+            rect.height() / buffer.get_height().max(1) as f32,
+        );
+        let cell_rect = |pos: Position| Rect::from_min_size(rect.min + Vec2::new(pos.x as f32 * cell.x, pos.y as f32 * cell.y), cell);
+        let stroke = Stroke::new(1.0, Color32::from_rgb(255, 64, 64));
+        for line in &self.differences {
+            for (x, _) in &line.cells {
+                painter.rect_stroke(cell_rect(Position::new(*x, line.y)), Rounding::ZERO, stroke);
+            }
+        }
+        if let Some(selected) = self.selected {
+            painter.rect_stroke(cell_rect(selected), Rounding::ZERO, Stroke::new(2.0, Color32::YELLOW));
+        }
+
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let pos = (pos - rect.min) / cell;
+                return Some(Position::new(pos.x as i32, pos.y as i32));
+            }
+        }
+        None
+    }
+
+    fn show_difference_list(&mut self, ui: &mut egui::Ui) {
+        let mut copy = None;
+        egui::ScrollArea::vertical().id_source("compare_differences").max_height(140.0).show(ui, |ui| {
+            for line in &self.differences {
+                ui.horizontal(|ui| {
+                    if ui.button("→").on_hover_text(fl!(crate::LANGUAGE_LOADER, "compare-copy-to-right")).clicked() {
+                        copy = Some((LEFT, line));
+                    }
+                    if ui.button("←").on_hover_text(fl!(crate::LANGUAGE_LOADER, "compare-copy-to-left")).clicked() {
+                        copy = Some((RIGHT, line));
+                    }
+                    let text = fl!(
+                        crate::LANGUAGE_LOADER,
+                        "compare-line",
+                        line = line.y + 1,
+                        count = line.cells.len(),
+                        kinds = Self::difference_text(line.summary())
+                    );
+                    let is_selected = self.selected.map_or(false, |pos| pos.y == line.y);
+                    if ui.selectable_label(is_selected, text).clicked() {
+                        self.selected = line.cells.first().map(|(x, _)| Position::new(*x, line.y));
+                    }
+                });
+            }
+        });
+        if let Some((from, line)) = copy {
+            let positions = line.cells.iter().map(|(x, _)| Position::new(*x, line.y)).collect();
+            self.copy_cells(from, positions);
+        }
+    }
+}
+
+impl ModalDialog for CompareDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        if self.file_dialog.is_some() {
+            self.show_file_dialog(ctx);
+            return false;
+        }
+        for side in &mut self.sides {
+            if side.texture.is_none() {
+                if let Some(buffer) = &side.buffer {
+                    side.texture = Some(create_image(ctx, buffer));
+                }
+            }
+        }
+
+        let mut result = false;
+        let modal = Modal::new(ctx, "compare_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(820.);
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "compare-title"));
+
+            modal.frame(ui, |ui| {
+                egui::Grid::new("compare_grid").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "compare-left-label"));
+                    });
+                    self.show_source_selector(ui, LEFT);
+                    ui.end_row();
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "compare-right-label"));
+                    });
+                    self.show_source_selector(ui, RIGHT);
+                    ui.end_row();
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "compare-view-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.radio_value(
+                            &mut self.view,
+                            CompareView::SideBySide,
+                            fl!(crate::LANGUAGE_LOADER, "compare-view-side-by-side"),
+                        );
+                        ui.radio_value(&mut self.view, CompareView::Overlay, fl!(crate::LANGUAGE_LOADER, "compare-view-overlay"));
+                        if self.view == CompareView::Overlay {
+                            ui.add(egui::Slider::new(&mut self.overlay_opacity, 0.0..=1.0).text(fl!(crate::LANGUAGE_LOADER, "compare-overlay-opacity")));
+                        }
+                    });
+                    ui.end_row();
+                });
+
+                if let Some(err) = &self.error {
+                    ui.label(RichText::new(err).color(ui.style().visuals.error_fg_color));
+                }
+                ui.separator();
+
+                let mut clicked = None;
+                egui::ScrollArea::both().id_source("compare_images").max_height(360.0).show(ui, |ui| {
+                    ui.horizontal_top(|ui| match self.view {
+                        CompareView::SideBySide => {
+                            clicked = self.paint_side(ui, LEFT, 400.0).or(clicked);
+                            clicked = self.paint_side(ui, RIGHT, 400.0).or(clicked);
+                        }
+                        CompareView::Overlay => {
+                            clicked = self.paint_side(ui, LEFT, 800.0);
+                        }
+                    });
+                });
+                if clicked.is_some() {
+                    self.selected = clicked;
+                }
+                ui.separator();
+
+                let cells: usize = self.differences.iter().map(|line| line.cells.len()).sum();
+                ui.horizontal(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "compare-summary", count = cells, lines = self.differences.len()));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "compare-copy-all-to-left")).clicked() {
+                            self.copy_cells(RIGHT, self.all_differences());
+                        }
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "compare-copy-all-to-right")).clicked() {
+                            self.copy_cells(LEFT, self.all_differences());
+                        }
+                        if let Some(selected) = self.selected {
+                            if ui.button(fl!(crate::LANGUAGE_LOADER, "compare-copy-cell-to-left")).clicked() {
+                                self.copy_cells(RIGHT, vec![selected]);
+                            }
+                            if ui.button(fl!(crate::LANGUAGE_LOADER, "compare-copy-cell-to-right")).clicked() {
+                                self.copy_cells(LEFT, vec![selected]);
+                            }
+                        }
+                    });
+                });
+                self.show_difference_list(ui);
+            });
+
+            modal.buttons(ui, |ui| {
+                let has_changes = self.sides.iter().any(|side| !side.changed.is_empty());
+                if ui
+                    .add_enabled(has_changes, egui::Button::new(fl!(crate::LANGUAGE_LOADER, "compare-apply")))
+                    .on_hover_text(fl!(crate::LANGUAGE_LOADER, "compare-apply-tooltip"))
+                    .clicked()
+                {
+                    self.should_commit = true;
+                    result = true;
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    /// Cells copied into the current document are written with undo.
+    fn commit(&self, editor: &mut AnsiEditor) -> TerminalResult<Option<Message>> {
+        let Some(side) = self.sides.iter().find(|side| side.source == Some(CompareSource::Document)) else {
+            return Ok(None);
+        };
+        let Some(buffer) = &side.buffer else {
+            return Ok(None);
+        };
+        if side.changed.is_empty() {
+            return Ok(None);
+        }
+        let cells = side.changed.iter().map(|(layer, pos)| {
+            let layer_pos = *pos - buffer.layers[*layer].get_offset();
+            (*layer, layer_pos, buffer.layers[*layer].get_char(layer_pos))
+        });
+        editor.set_layer_chars(fl!(crate::LANGUAGE_LOADER, "compare-title"), cells)?;
+        Ok(None)
+    }
+
+    /// Changed files and versions are opened as new documents, nothing is written to disk.
+    fn commit_self(&self, window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+        for side in &self.sides {
+            if side.source == Some(CompareSource::Document) || side.changed.is_empty() {
+                continue;
+            }
+            if let Some(buffer) = &side.buffer {
+                let id = window.create_id();
+                let editor = AnsiEditor::new(&window.gl, id, buffer.clone());
+                add_child(&mut window.document_tree, None, Box::new(editor));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,235 @@
+use eframe::{
+    egui::{self, TextureHandle},
+    epaint::{Color32, Pos2, Rect, Vec2},
+};
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::{AttributedChar, Buffer, Color, IceMode, Palette, PaletteMode, Position, TextPane, DOS_DEFAULT_PALETTE};
+
+use crate::{create_image, util::palette_convert::PaletteConverter, AnsiEditor, Message, ModalDialog, TerminalResult};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ConvertTarget {
+    /// Fixed DOS palette
+    DosDefault,
+    /// First 16 colors of the current palette
+    CurrentPalette,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ConvertOptions {
+    target: ConvertTarget,
+    ice_colors: bool,
+    dither: bool,
+}
+
+impl ConvertOptions {
+    fn palette_mode(&self) -> PaletteMode {
+        match self.target {
+            ConvertTarget::DosDefault => PaletteMode::Fixed16,
+            ConvertTarget::CurrentPalette => PaletteMode::Free16,
+        }
+    }
+
+    fn ice_mode(&self) -> IceMode {
+        if self.ice_colors {
+            IceMode::Ice
+        } else {
+            IceMode::Blink
+        }
+    }
+
+    fn target_palette(&self, palette: &Palette) -> Palette {
+        match self.target {
+            ConvertTarget::DosDefault => Palette::from_slice(&DOS_DEFAULT_PALETTE),
+            ConvertTarget::CurrentPalette => {
+                let colors: Vec<Color> = (0..DOS_DEFAULT_PALETTE.len())
+                    .map(|i| {
+                        if i < palette.len() {
+                            palette.get_color(i as u32)
+                        } else {
+                            DOS_DEFAULT_PALETTE[i].clone()
+                        }
+                    })
+                    .collect();
+                Palette::from_slice(&colors)
+            }
+        }
+    }
+
+    /// Converted cells of every layer, layers and positions in buffer order.
+    fn convert(&self, buffer: &Buffer) -> Vec<Vec<(Position, AttributedChar)>> {
+        let target = self.target_palette(&buffer.palette);
+        let bg_colors = if self.ice_colors { 16 } else { 8 };
+        let mut converter = PaletteConverter::new(&buffer.palette, &target, bg_colors, buffer.buffer_type, self.dither);
+        buffer
+            .layers
+            .iter()
+            .map(|layer| {
+                let mut cells = Vec::new();
+                for y in 0..layer.get_height() {
+                    for x in 0..layer.get_width() {
+                        let pos = Position::new(x, y);
+                        cells.push((pos, converter.convert(layer.get_char(pos))));
+                    }
+                }
+                cells
+            })
+            .collect()
+    }
+}
+
+pub struct ConvertPaletteDialog {
+    should_commit: bool,
+    buffer: Buffer,
+    options: ConvertOptions,
+
+    before: Option<TextureHandle>,
+    after: Option<(ConvertOptions, TextureHandle)>,
+}
+
+impl ConvertPaletteDialog {
+    pub fn new(editor: &AnsiEditor) -> Self {
+        let buffer = editor.buffer_view.lock().get_buffer().clone();
+        let options = ConvertOptions {
+            target: ConvertTarget::DosDefault,
+            ice_colors: !matches!(buffer.ice_mode, IceMode::Blink),
+            dither: false,
+        };
+        Self {
+            should_commit: false,
+            buffer,
+            options,
+            before: None,
+            after: None,
+        }
+    }
+
+    fn update_previews(&mut self, ctx: &egui::Context) {
+        if self.before.is_none() {
+            self.before = Some(create_image(ctx, &self.buffer));
+        }
+        if matches!(&self.after, Some((options, _)) if *options == self.options) {
+            return;
+        }
+        let mut converted = self.buffer.clone();
+        for (layer, cells) in converted.layers.iter_mut().zip(self.options.convert(&self.buffer)) {
+            for (pos, ch) in cells {
+                layer.set_char(pos, ch);
+            }
+        }
+        converted.palette = self.options.target_palette(&self.buffer.palette);
+        converted.palette_mode = self.options.palette_mode();
+        converted.ice_mode = self.options.ice_mode();
+        self.after = Some((self.options, create_image(ctx, &converted)));
+    }
+
+    fn show_preview(ui: &mut egui::Ui, label: String, texture: Option<&TextureHandle>) {
+        ui.vertical(|ui| {
+            ui.label(label);
+            let Some(texture) = texture else {
+                return;
+            };
+            let size = texture.size_vec2();
+            let scale = (260.0 / size.x).min(200.0 / size.y).min(1.0);
+            let (rect, _) = ui.allocate_exact_size(Vec2::new(260.0, 200.0), egui::Sense::hover());
+            let image_rect = Rect::from_min_size(rect.min, size * scale);
+            let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+            ui.painter().rect_filled(rect, 0.0, Color32::BLACK);
+            ui.painter().image(texture.id(), image_rect, uv, Color32::WHITE);
+        });
+    }
+}
+
+impl ModalDialog for ConvertPaletteDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        let mut result = false;
+        let modal = Modal::new(ctx, "convert_palette_dialog");
+        self.update_previews(ctx);
+
+        modal.show(|ui| {
+            ui.set_width(540.);
+
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "convert-palette-title"));
+
+            modal.frame(ui, |ui| {
+                egui::Grid::new("convert_palette_grid").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "convert-palette-target-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.radio_value(
+                            &mut self.options.target,
+                            ConvertTarget::DosDefault,
+                            fl!(crate::LANGUAGE_LOADER, "convert-palette-target-dos"),
+                        );
+                        ui.radio_value(
+                            &mut self.options.target,
+                            ConvertTarget::CurrentPalette,
+                            fl!(crate::LANGUAGE_LOADER, "convert-palette-target-current"),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "convert-palette-background-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.options.ice_colors, true, fl!(crate::LANGUAGE_LOADER, "convert-palette-ice"));
+                        ui.radio_value(&mut self.options.ice_colors, false, fl!(crate::LANGUAGE_LOADER, "convert-palette-blink"));
+                    });
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut self.options.dither, fl!(crate::LANGUAGE_LOADER, "convert-palette-dither"));
+                    ui.end_row();
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    Self::show_preview(ui, fl!(crate::LANGUAGE_LOADER, "convert-palette-before"), self.before.as_ref());
+                    Self::show_preview(ui, fl!(crate::LANGUAGE_LOADER, "convert-palette-after"), self.after.as_ref().map(|(_, t)| t));
+                });
+            });
+
+            modal.buttons(ui, |ui| {
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "convert-palette-convert")).clicked() {
+                    self.should_commit = true;
+                    result = true;
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit(&self, editor: &mut AnsiEditor) -> TerminalResult<Option<Message>> {
+        let _undo = editor.begin_atomic_undo(fl!(crate::LANGUAGE_LOADER, "convert-palette-title"));
+        let mut lock = editor.buffer_view.lock();
+        let converted = self.options.convert(lock.get_buffer());
+        let target = self.options.target_palette(&lock.get_buffer().palette);
+
+        let edit_state = lock.get_edit_state_mut();
+        if edit_state.get_buffer().palette_mode != self.options.palette_mode() {
+            edit_state.set_palette_mode(self.options.palette_mode())?;
+        }
+        if edit_state.get_buffer().ice_mode != self.options.ice_mode() {
+            edit_state.set_ice_mode(self.options.ice_mode())?;
+        }
+        edit_state.switch_to_palette(target)?;
+
+        // The mode switches may already have touched the cells, write everything that differs from the conversion
+        let cells = converted
+            .into_iter()
+            .enumerate()
+            .flat_map(|(layer, cells)| cells.into_iter().map(move |(pos, ch)| (layer, pos, ch)));
+        AnsiEditor::set_layer_chars_in(edit_state, cells)?;
+        Ok(None)
+    }
+}
@@ -0,0 +1,165 @@
+use std::{fs, path::PathBuf};
+
+use eframe::{
+    egui::{self, RichText, TextureHandle},
+    epaint::{Color32, Pos2, Rect, Vec2},
+};
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::Buffer;
+
+use crate::{
+    add_child, create_image,
+    util::autosave::{cleanup_orphaned_autosaves, get_snapshots, Snapshot},
+    AnsiEditor, MainWindow, Message, ModalDialog, TerminalResult,
+};
+
+const THUMBNAIL_SIZE: Vec2 = Vec2::new(160.0, 100.0);
+
+enum HistoryAction {
+    Restore(PathBuf),
+    OpenCopy(Buffer),
+}
+
+struct HistoryEntry {
+    snapshot: Snapshot,
+    /// `None` until the snapshot got loaded, documents that aren't buffers have no preview
+    preview: Option<Option<(Buffer, TextureHandle)>>,
+}
+
+pub struct DocumentHistoryDialog {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+    action: Option<HistoryAction>,
+    cleanup_result: Option<usize>,
+}
+
+impl DocumentHistoryDialog {
+    pub fn new(path: PathBuf) -> Self {
+        let entries = get_snapshots(&path)
+            .into_iter()
+            .map(|snapshot| HistoryEntry { snapshot, preview: None })
+            .collect();
+        Self {
+            path,
+            entries,
+            action: None,
+            cleanup_result: None,
+        }
+    }
+
+    fn load_preview(&self, ctx: &egui::Context, snapshot: &Snapshot) -> Option<(Buffer, TextureHandle)> {
+        let data = fs::read(&snapshot.file).ok()?;
+        let mut buffer = Buffer::from_bytes(&self.path, true, &data).ok()?;
+        buffer.is_terminal_buffer = false;
+        let texture = create_image(ctx, &buffer);
+        Some((buffer, texture))
+    }
+
+    fn show_thumbnail(ui: &mut egui::Ui, texture: Option<&TextureHandle>) {
+        let (rect, _) = ui.allocate_exact_size(THUMBNAIL_SIZE, egui::Sense::hover());
+        ui.painter().rect_filled(rect, 0.0, Color32::BLACK);
+        let Some(texture) = texture else {
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                fl!(crate::LANGUAGE_LOADER, "document-history-no-preview"),
+                egui::FontId::proportional(12.0),
+                Color32::GRAY,
+            );
+            return;
+        };
+        let size = texture.size_vec2();
+        let scale = (THUMBNAIL_SIZE.x / size.x).min(1.0);
+        // tall pictures show their top part
+        let shown = Vec2::new(size.x * scale, (size.y * scale).min(THUMBNAIL_SIZE.y));
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, shown.y / (size.y * scale)));
+        ui.painter().image(texture.id(), Rect::from_min_size(rect.min, shown), uv, Color32::WHITE);
+    }
+}
+
+impl ModalDialog for DocumentHistoryDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        for i in 0..self.entries.len() {
+            if self.entries[i].preview.is_none() {
+                let preview = self.load_preview(ctx, &self.entries[i].snapshot);
+                self.entries[i].preview = Some(preview);
+            }
+        }
+
+        let mut result = false;
+        let modal = Modal::new(ctx, "document_history_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(480.);
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "document-history-title"));
+
+            modal.frame(ui, |ui| {
+                ui.label(self.path.to_string_lossy());
+                ui.separator();
+                if self.entries.is_empty() {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "document-history-empty"));
+                }
+                egui::ScrollArea::vertical().max_height(420.0).show(ui, |ui| {
+                    for entry in &self.entries {
+                        let preview = entry.preview.as_ref().and_then(|p| p.as_ref());
+                        ui.horizontal(|ui| {
+                            Self::show_thumbnail(ui, preview.map(|(_, texture)| texture));
+                            ui.vertical(|ui| {
+                                ui.strong(entry.snapshot.modified_text());
+                                if ui.button(fl!(crate::LANGUAGE_LOADER, "document-history-restore")).clicked() {
+                                    self.action = Some(HistoryAction::Restore(entry.snapshot.file.clone()));
+                                    result = true;
+                                }
+                                if ui
+                                    .add_enabled(preview.is_some(), egui::Button::new(fl!(crate::LANGUAGE_LOADER, "document-history-open-copy")))
+                                    .clicked()
+                                {
+                                    if let Some((buffer, _)) = preview {
+                                        self.action = Some(HistoryAction::OpenCopy(buffer.clone()));
+                                        result = true;
+                                    }
+                                }
+                            });
+                        });
+                        ui.add_space(4.0);
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(fl!(crate::LANGUAGE_LOADER, "document-history-cleanup")).clicked() {
+                        self.cleanup_result = Some(cleanup_orphaned_autosaves());
+                    }
+                    if let Some(removed) = self.cleanup_result {
+                        ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "document-history-cleanup-result", count = removed)).small());
+                    }
+                });
+            });
+
+            modal.buttons(ui, |ui| {
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.action.is_some()
+    }
+
+    fn commit_self(&self, window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+        match &self.action {
+            Some(HistoryAction::Restore(snapshot)) => Ok(Some(Message::RestoreSnapshot(self.path.clone(), snapshot.clone()))),
+            Some(HistoryAction::OpenCopy(buffer)) => {
+                let id = window.create_id();
+                let editor = AnsiEditor::new(&window.gl, id, buffer.clone());
+                add_child(&mut window.document_tree, None, Box::new(editor));
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -1,15 +1,34 @@
-use bstr::{BStr, BString};
-use eframe::egui::{self, Layout};
+use bstr::BString;
+use eframe::egui::{self, Layout, RichText};
 use egui_modal::Modal;
 use i18n_embed_fl::fl;
+use icy_engine::{editor::EditState, EngineResult, IceMode, TextPane, SAUCE_FONT_NAMES};
 use icy_sauce::SauceMetaInformation;
 
 use crate::{to_message, AnsiEditor, Message, ModalDialog, TerminalResult};
 
+const TITLE_LENGTH: usize = 35;
+const AUTHOR_LENGTH: usize = 20;
+const GROUP_LENGTH: usize = 20;
+const COMMENT_LENGTH: usize = 64;
+const MAX_COMMENTS: usize = 255;
+
 pub struct EditSauceDialog {
     pub should_commit: bool,
     pub sauce_data: SauceMetaInformation,
     pub comments: String,
+
+    width: i32,
+    height: i32,
+    font_width: i32,
+
+    ice_mode: IceMode,
+    use_ice_colors: bool,
+    use_letter_spacing: bool,
+    use_aspect_ratio: bool,
+    /// TInfoS, None if the document uses a font that has no SAUCE name
+    sauce_font: Option<String>,
+    old_sauce_font: Option<String>,
 }
 
 impl EditSauceDialog {
@@ -21,12 +40,48 @@ impl EditSauceDialog {
             comments.push('\n');
         }
 
+        let font = buf.get_font(0);
+        let sauce_font = font.map(|f| f.name.clone()).filter(|name| SAUCE_FONT_NAMES.contains(&name.as_str()));
+
         EditSauceDialog {
             should_commit: false,
             sauce_data: buf.get_sauce_meta().clone(),
             comments,
+            width: buf.get_width(),
+            height: buf.get_height(),
+            font_width: font.map_or(8, |f| f.size.width),
+            ice_mode: buf.ice_mode,
+            use_ice_colors: buf.ice_mode == IceMode::Ice,
+            use_letter_spacing: buf.use_letter_spacing(),
+            use_aspect_ratio: buf.use_aspect_ratio(),
+            old_sauce_font: sauce_font.clone(),
+            sauce_font,
         }
     }
+
+    fn comment_lines(&self) -> impl Iterator<Item = &str> {
+        self.comments.lines()
+    }
+}
+
+fn show_text_field(ui: &mut egui::Ui, value: &mut BString, max_length: usize, length_label: String) {
+    ui.horizontal(|ui| {
+        let mut tmp_str = value.to_string();
+        ui.add(egui::TextEdit::singleline(&mut tmp_str));
+        let length = tmp_str.chars().count();
+        *value = BString::from(tmp_str);
+        if length > max_length {
+            ui.label(
+                RichText::new(fl!(crate::LANGUAGE_LOADER, "edit-sauce-too-long", length = length, max = max_length)).color(ui.style().visuals.warn_fg_color),
+            );
+        } else {
+            ui.label(length_label);
+        }
+    });
+}
+
+fn truncate(value: &BString, max_length: usize) -> BString {
+    BString::from(value.to_string().chars().take(max_length).collect::<String>())
 }
 
 impl ModalDialog for EditSauceDialog {
@@ -42,42 +97,70 @@ impl ModalDialog for EditSauceDialog {
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-title-label"));
                     });
-                    ui.horizontal(|ui| {
-                        let mut tmp_str = self.sauce_data.title.to_string();
-                        ui.add(egui::TextEdit::singleline(&mut tmp_str).char_limit(35));
-                        self.sauce_data.title = BString::from(tmp_str);
-                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-title-label-length"));
-                    });
+                    show_text_field(
+                        ui,
+                        &mut self.sauce_data.title,
+                        TITLE_LENGTH,
+                        fl!(crate::LANGUAGE_LOADER, "edit-sauce-title-label-length"),
+                    );
                     ui.end_row();
 
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-author-label"));
                     });
+                    show_text_field(
+                        ui,
+                        &mut self.sauce_data.author,
+                        AUTHOR_LENGTH,
+                        fl!(crate::LANGUAGE_LOADER, "edit-sauce-author-label-length"),
+                    );
+                    ui.end_row();
 
-                    ui.horizontal(|ui| {
-                        let mut tmp_str = self.sauce_data.author.to_string();
-                        ui.add(egui::TextEdit::singleline(&mut tmp_str).char_limit(20));
-                        self.sauce_data.author = BString::from(tmp_str);
-                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-author-label-length"));
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label"));
                     });
+                    show_text_field(
+                        ui,
+                        &mut self.sauce_data.group,
+                        GROUP_LENGTH,
+                        fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label-length"),
+                    );
                     ui.end_row();
 
+                    // TInfo1/2 are taken from the document size when the file is saved
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label"));
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-tinfo-label"));
                     });
-                    ui.horizontal(|ui| {
-                        let mut tmp_str = self.sauce_data.group.to_string();
-                        ui.add(egui::TextEdit::singleline(&mut tmp_str).char_limit(20));
-                        self.sauce_data.group = BString::from(tmp_str);
-                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label-length"));
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-tinfo", width = self.width, height = self.height));
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-font-label"));
+                    });
+                    let font_text = self.sauce_font.clone().unwrap_or_else(|| fl!(crate::LANGUAGE_LOADER, "edit-sauce-font-custom"));
+                    egui::ComboBox::from_id_source("sauce_font_combo").selected_text(font_text).show_ui(ui, |ui| {
+                        for name in SAUCE_FONT_NAMES {
+                            ui.selectable_value(&mut self.sauce_font, Some(name.to_string()), name);
+                        }
                     });
                     ui.end_row();
-                    /*
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-ice-colors"));
+                    });
+                    ui.add_enabled_ui(self.ice_mode != IceMode::Unlimited, |ui| {
+                        ui.checkbox(&mut self.use_ice_colors, "");
+                    });
+                    ui.end_row();
+
                     ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-letter-spacing"));
                     });
                     ui.horizontal(|ui| {
-                        ui.checkbox(&mut self.sauce_data.use_letter_spacing, "");
+                        ui.checkbox(&mut self.use_letter_spacing, "");
+                        if self.use_letter_spacing && self.font_width != 8 {
+                            ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "edit-sauce-letter-spacing-warning")).color(ui.style().visuals.warn_fg_color));
+                        }
                     });
                     ui.end_row();
 
@@ -85,27 +168,40 @@ impl ModalDialog for EditSauceDialog {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-aspect-ratio"));
                     });
                     ui.horizontal(|ui| {
-                        ui.checkbox(&mut self.sauce_data.use_aspect_ratio, "");
+                        ui.checkbox(&mut self.use_aspect_ratio, "");
                     });
-                    ui.end_row();*/
+                    ui.end_row();
                 });
                 ui.add_space(16.0);
                 ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-comments-label"));
                 ui.add_space(4.0);
-                self.sauce_data.comments.clear();
                 egui::ScrollArea::vertical().max_height(180.0).show(ui, |ui| {
                     ui.add(egui::TextEdit::multiline(&mut self.comments).desired_rows(6).desired_width(f32::INFINITY));
-                    let mut new_comments = String::new();
-                    for line in self.comments.lines() {
-                        if line.len() > 64 {
-                            new_comments.push_str(&line[..64]);
-                        } else {
-                            new_comments.push_str(line);
-                        }
-                        new_comments.push('\n');
-                    }
-                    self.comments = new_comments;
                 });
+                let line_count = self.comment_lines().count();
+                let long_lines = self.comment_lines().filter(|line| line.chars().count() > COMMENT_LENGTH).count();
+                if line_count > MAX_COMMENTS {
+                    ui.label(
+                        RichText::new(fl!(
+                            crate::LANGUAGE_LOADER,
+                            "edit-sauce-too-many-comments",
+                            lines = line_count,
+                            max = MAX_COMMENTS
+                        ))
+                        .color(ui.style().visuals.warn_fg_color),
+                    );
+                }
+                if long_lines > 0 {
+                    ui.label(
+                        RichText::new(fl!(
+                            crate::LANGUAGE_LOADER,
+                            "edit-sauce-comments-too-long",
+                            lines = long_lines,
+                            max = COMMENT_LENGTH
+                        ))
+                        .color(ui.style().visuals.warn_fg_color),
+                    );
+                }
             });
 
             modal.buttons(ui, |ui| {
@@ -128,17 +224,42 @@ impl ModalDialog for EditSauceDialog {
 
     fn commit(&self, editor: &mut AnsiEditor) -> TerminalResult<Option<Message>> {
         let mut data = self.sauce_data.clone();
-        let mut number = 0;
-        for line in self.comments.lines() {
-            data.comments.push(BString::from(line));
-            number += 1;
-            // limit to 255 chars which is the maximum for sauce comment lines.
-            if number > 255 {
-                break;
-            }
-        }
+        // fields exceeding the SAUCE limits would be cut by the writer anyway, do it here so the document shows what gets saved.
+        data.title = truncate(&data.title, TITLE_LENGTH);
+        data.author = truncate(&data.author, AUTHOR_LENGTH);
+        data.group = truncate(&data.group, GROUP_LENGTH);
+        data.comments = self
+            .comment_lines()
+            .take(MAX_COMMENTS)
+            .map(|line| BString::from(line.chars().take(COMMENT_LENGTH).collect::<String>()))
+            .collect();
 
+        let _undo = editor.begin_atomic_undo(fl!(crate::LANGUAGE_LOADER, "undo-edit-sauce"));
         let bv = &mut editor.buffer_view.lock();
-        Ok(to_message(bv.get_edit_state_mut().update_sauce_data(data)))
+        Ok(to_message(self.update_document(bv.get_edit_state_mut(), data)))
+    }
+}
+
+impl EditSauceDialog {
+    fn update_document(&self, edit_state: &mut EditState, data: SauceMetaInformation) -> EngineResult<()> {
+        edit_state.update_sauce_data(data)?;
+        if self.ice_mode != IceMode::Unlimited {
+            let ice_mode = if self.use_ice_colors { IceMode::Ice } else { IceMode::Blink };
+            if ice_mode != self.ice_mode {
+                edit_state.set_ice_mode(ice_mode)?;
+            }
+        }
+        if self.use_letter_spacing != edit_state.get_buffer().use_letter_spacing() {
+            edit_state.set_use_letter_spacing(self.use_letter_spacing)?;
+        }
+        if self.use_aspect_ratio != edit_state.get_buffer().use_aspect_ratio() {
+            edit_state.set_use_aspect_ratio(self.use_aspect_ratio)?;
+        }
+        if self.sauce_font != self.old_sauce_font {
+            if let Some(name) = &self.sauce_font {
+                edit_state.set_sauce_font(name)?;
+            }
+        }
+        Ok(())
     }
 }
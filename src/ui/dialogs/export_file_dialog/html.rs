@@ -0,0 +1,188 @@
+use std::fmt::Write;
+
+use eframe::egui::{self, Ui};
+use i18n_embed_fl::fl;
+use icy_engine::{Buffer, IceMode, SaveOptions, TextAttribute, TextPane};
+
+use crate::{
+    util::{ttf::bitfont_to_ttf, unicode::to_unicode},
+    WebExportOptions, SETTINGS,
+};
+
+pub fn create_settings_page(ui: &mut Ui, _options: &mut SaveOptions) {
+    let options = unsafe { &mut SETTINGS.web_export_options };
+    ui.vertical(|ui| {
+        ui.add(egui::Checkbox::new(
+            &mut options.inline_styles,
+            fl!(crate::LANGUAGE_LOADER, "export-html-inline-styles-label"),
+        ));
+        ui.add(egui::Checkbox::new(
+            &mut options.embed_font,
+            fl!(crate::LANGUAGE_LOADER, "export-html-embed-font-label"),
+        ));
+        ui.add(egui::Checkbox::new(&mut options.blink, fl!(crate::LANGUAGE_LOADER, "export-html-blink-label")));
+    });
+}
+
+/// Foreground, background & blink of a char as they're displayed.
+pub fn display_colors(buf: &Buffer, attr: &TextAttribute) -> (u32, u32, bool) {
+    let mut fg = attr.get_foreground();
+    if attr.is_bold() && fg < 8 {
+        fg += 8;
+    }
+    let blink = buf.ice_mode != IceMode::Ice && attr.is_blinking();
+    (fg, attr.get_background(), blink)
+}
+
+pub fn color_hex(buf: &Buffer, color: u32) -> String {
+    let (r, g, b) = buf.palette.get_color(color).get_rgb();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(ch: char, out: &mut String) {
+    match ch {
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '&' => out.push_str("&amp;"),
+        '\0' => out.push(' '),
+        _ => out.push(ch),
+    }
+}
+
+fn font_name(page: usize) -> String {
+    if page == 0 {
+        "icy_draw".to_string()
+    } else {
+        format!("icy_draw_{page}")
+    }
+}
+
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let n = (u32::from(chunk[0]) << 16) | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8) | u32::from(*chunk.get(2).unwrap_or(&0));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Renders the buffer as a <pre> block. Runs of equal attributes share one span.
+pub fn export_html(buf: &Buffer, options: &WebExportOptions) -> String {
+    let font = buf.get_font(0);
+    let font_height = font.map_or(16, |f| f.size.height);
+    let font_width = font.map_or(8, |f| f.size.width);
+    let title = buf.get_sauce_meta().title.to_string();
+
+    let mut used_colors = Vec::new();
+    let mut used_pages = vec![0];
+    let mut lines = Vec::new();
+    for y in 0..buf.get_height() {
+        let mut runs: Vec<((u32, u32, bool, usize), String)> = Vec::new();
+        for x in 0..buf.get_width() {
+            let ch = buf.get_char((x, y));
+            let (fg, bg, blink) = display_colors(buf, &ch.attribute);
+            if !used_colors.contains(&fg) {
+                used_colors.push(fg);
+            }
+            if !used_colors.contains(&bg) {
+                used_colors.push(bg);
+            }
+            // cells of missing fonts are shown with the default font
+            let page = if options.embed_font && buf.get_font(ch.get_font_page()).is_some() {
+                ch.get_font_page()
+            } else {
+                0
+            };
+            if !used_pages.contains(&page) {
+                used_pages.push(page);
+            }
+            let key = (fg, bg, blink, page);
+            if runs.last().map_or(true, |(k, _)| *k != key) {
+                runs.push((key, String::new()));
+            }
+            if let Some((_, text)) = runs.last_mut() {
+                escape(to_unicode(buf.buffer_type, ch.ch), text);
+            }
+        }
+        lines.push(runs);
+    }
+    used_colors.sort_unstable();
+    used_pages.sort_unstable();
+
+    let mut result = String::new();
+    result.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(result, "<title>{}</title>", title.replace('<', "&lt;"));
+    result.push_str("<style>\n");
+    let mut font_family = "monospace".to_string();
+    if options.embed_font {
+        // every used font page is embedded as its own font, spans of other pages switch the family
+        for page in &used_pages {
+            let Some(font) = buf.get_font(*page) else {
+                continue;
+            };
+            let name = font_name(*page);
+            let ttf = bitfont_to_ttf(font, &name, |ch| to_unicode(buf.buffer_type, ch));
+            let _ = writeln!(
+                result,
+                "@font-face {{ font-family: \"{name}\"; src: url(data:font/ttf;base64,{}) format(\"truetype\"); }}",
+                base64(&ttf)
+            );
+            if *page != 0 {
+                let _ = writeln!(result, ".p{page} {{ font-family: \"{name}\", monospace }}");
+            }
+        }
+        if font.is_some() {
+            font_family = format!("\"{}\", monospace", font_name(0));
+        }
+    }
+    let _ = writeln!(
+        result,
+        "pre.ansi {{ font-family: {font_family}; font-size: {font_height}px; line-height: {font_height}px; letter-spacing: 0; margin: 0; width: {}px; background-color: {}; }}",
+        buf.get_width() * font_width,
+        color_hex(buf, 0)
+    );
+    if options.blink {
+        result.push_str(".blink { animation: blink 1s steps(1) infinite; }\n");
+        result.push_str("@keyframes blink { 50% { color: transparent; } }\n");
+    }
+    if !options.inline_styles {
+        for color in &used_colors {
+            let _ = writeln!(result, ".f{color} {{ color: {} }}", color_hex(buf, *color));
+            let _ = writeln!(result, ".b{color} {{ background-color: {} }}", color_hex(buf, *color));
+        }
+    }
+    result.push_str("</style>\n</head>\n<body>\n<pre class=\"ansi\">");
+
+    for runs in lines {
+        for ((fg, bg, blink, page), text) in runs {
+            let blink = if blink && options.blink { " blink" } else { "" };
+            let page = if page != 0 { format!(" p{page}") } else { String::new() };
+            if options.inline_styles {
+                let classes = format!("{blink}{page}");
+                let class = if classes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" class=\"{}\"", classes.trim_start())
+                };
+                let _ = write!(
+                    result,
+                    "<span{class} style=\"color:{};background-color:{}\">{text}</span>",
+                    color_hex(buf, fg),
+                    color_hex(buf, bg)
+                );
+            } else {
+                let _ = write!(result, "<span class=\"f{fg} b{bg}{blink}{page}\">{text}</span>");
+            }
+        }
+        result.push('\n');
+    }
+    result.push_str("</pre>\n</body>\n</html>\n");
+    result
+}
@@ -16,9 +16,13 @@ mod ascii;
 mod atascii;
 mod avatar;
 mod bin;
+mod html;
 mod ice_draw;
 mod pcboard;
+mod petscii;
 mod png;
+mod svg;
+mod terminal;
 mod tundra_draw;
 mod xbin;
 
@@ -35,11 +39,7 @@ impl ExportFileDialog {
         let file_name = match &buf.file_name {
             Some(path) => {
                 let mut p = path.clone();
-                let desc: &[(&str, CreateSettingsFunction, &str)] = if matches!(buf.buffer_type, BufferType::Atascii) {
-                    &ATASCII_TYPE_DESCRIPTIONS
-                } else {
-                    &TYPE_DESCRIPTIONS
-                };
+                let desc = get_type_descriptions(buf.buffer_type);
                 let format_type = get_format_type(buf.buffer_type, path) as usize;
                 let ext = desc[format_type].2;
                 p.set_extension(ext);
@@ -59,15 +59,19 @@ impl ExportFileDialog {
     }
 }
 
+fn get_type_descriptions(buffer_type: BufferType) -> &'static [(&'static str, CreateSettingsFunction, &'static str)] {
+    match buffer_type {
+        BufferType::Atascii => &ATASCII_TYPE_DESCRIPTIONS,
+        BufferType::Petscii => &PETSCII_TYPE_DESCRIPTIONS,
+        _ => &TYPE_DESCRIPTIONS,
+    }
+}
+
 fn get_format_type(buf: BufferType, path: &std::path::Path) -> i32 {
     if let Some(ext) = path.extension() {
         if let Some(ext) = ext.to_str() {
             let ext = ext.to_lowercase();
-            let desc: &[(&str, CreateSettingsFunction, &str)] = if matches!(buf, BufferType::Atascii) {
-                &ATASCII_TYPE_DESCRIPTIONS
-            } else {
-                &TYPE_DESCRIPTIONS
-            };
+            let desc = get_type_descriptions(buf);
             for i in 0..desc.len() {
                 let td = desc[i];
                 if ext == td.2 {
@@ -102,11 +106,7 @@ impl ModalDialog for ExportFileDialog {
             modal.title(ui, fl!(crate::LANGUAGE_LOADER, "export-title"));
 
             modal.frame(ui, |ui| {
-                let desc: &[(&str, CreateSettingsFunction, &str)] = if matches!(self.buffer_type, BufferType::Atascii) {
-                    &ATASCII_TYPE_DESCRIPTIONS
-                } else {
-                    &TYPE_DESCRIPTIONS
-                };
+                let desc = get_type_descriptions(self.buffer_type);
 
                 egui::Grid::new("paste_mode_grid")
                     .num_columns(2)
@@ -204,6 +204,21 @@ impl ModalDialog for ExportFileDialog {
 
                     return Ok(None);
                 }
+                if crate::util::petscii::is_c64_extension(&ext) {
+                    let bytes = crate::util::petscii::save_c64_file(&ext, editor.buffer_view.lock().get_buffer())?;
+                    std::fs::write(&self.file_name, bytes)?;
+                    return Ok(None);
+                }
+                if ext == "html" || ext == "svg" || ext == "utf8" {
+                    let lock = &editor.buffer_view.lock();
+                    let content = match ext.as_str() {
+                        "html" => html::export_html(lock.get_buffer(), unsafe { &SETTINGS.web_export_options }),
+                        "svg" => svg::export_svg(lock.get_buffer()),
+                        _ => terminal::export_terminal(lock.get_buffer(), unsafe { &SETTINGS.terminal_export_options }),
+                    };
+                    std::fs::write(&self.file_name, content)?;
+                    return Ok(None);
+                }
             }
         }
         unsafe {
@@ -215,7 +230,7 @@ impl ModalDialog for ExportFileDialog {
 
 type CreateSettingsFunction = fn(&mut Ui, &mut SaveOptions);
 
-const TYPE_DESCRIPTIONS: [(&str, CreateSettingsFunction, &str); 12] = [
+const TYPE_DESCRIPTIONS: [(&str, CreateSettingsFunction, &str); 15] = [
     ("Ansi (.ans)", ansi::create_settings_page, "ans"),
     ("Avatar (.avt)", avatar::create_settings_page, "avt"),
     ("PCBoard (.pcb)", pcboard::create_settings_page, "pcb"),
@@ -228,10 +243,25 @@ const TYPE_DESCRIPTIONS: [(&str, CreateSettingsFunction, &str); 12] = [
     ("CtrlA (.msg)", pcboard::create_settings_page, "msg"),
     ("Renegade (.an1)", pcboard::create_settings_page, "an1"),
     ("PNG (.png)", png::create_settings_page, "png"),
+    ("HTML (.html)", html::create_settings_page, "html"),
+    ("SVG (.svg)", svg::create_settings_page, "svg"),
+    ("UTF-8 Terminal (.utf8)", terminal::create_settings_page, "utf8"),
 ];
 
-const ATASCII_TYPE_DESCRIPTIONS: [(&str, CreateSettingsFunction, &str); 3] = [
+const ATASCII_TYPE_DESCRIPTIONS: [(&str, CreateSettingsFunction, &str); 6] = [
     ("Atascii (.ata)", atascii::create_settings_page, "ata"),
     ("XBin (.xb)", xbin::create_settings_page, "xb"),
     ("PNG (.png)", png::create_settings_page, "png"),
+    ("HTML (.html)", html::create_settings_page, "html"),
+    ("SVG (.svg)", svg::create_settings_page, "svg"),
+    ("UTF-8 Terminal (.utf8)", terminal::create_settings_page, "utf8"),
+];
+
+const PETSCII_TYPE_DESCRIPTIONS: [(&str, CreateSettingsFunction, &str); 6] = [
+    ("C64 screen (.scr)", petscii::create_settings_page, "scr"),
+    ("PETSCII stream (.seq)", petscii::create_settings_page, "seq"),
+    ("C64 program (.prg)", petscii::create_settings_page, "prg"),
+    ("XBin (.xb)", xbin::create_settings_page, "xb"),
+    ("PNG (.png)", png::create_settings_page, "png"),
+    ("HTML (.html)", html::create_settings_page, "html"),
 ];
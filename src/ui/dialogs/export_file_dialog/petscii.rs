@@ -0,0 +1,5 @@
+use eframe::egui::Ui;
+use icy_engine::SaveOptions;
+
+/// Screen dumps, streams & programs always contain one 40x25 screen, there is nothing to set up.
+pub fn create_settings_page(_ui: &mut Ui, _options: &mut SaveOptions) {}
@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use eframe::egui::Ui;
+use icy_engine::{Buffer, SaveOptions, TextPane};
+
+use super::html::{color_hex, display_colors};
+use crate::util::ttf::glyph_runs;
+
+pub fn create_settings_page(_ui: &mut Ui, _options: &mut SaveOptions) {}
+
+/// Path of a glyph in font pixels, each run of set pixels is a rectangle.
+fn glyph_path(data: &[u8], width: i32) -> String {
+    let mut path = String::new();
+    for (x, y, len) in glyph_runs(data, width) {
+        let _ = write!(path, "M{x} {y}h{len}v1h-{len}z");
+    }
+    path
+}
+
+/// Draws backgrounds as rectangles and glyphs as vector paths traced from the bit fonts, so the art scales without blur.
+pub fn export_svg(buf: &Buffer) -> String {
+    let default_size = buf.get_font(0).map_or((8, 16), |f| (f.size.width, f.size.height));
+    let (char_width, char_height) = default_size;
+    let width = buf.get_width() * char_width;
+    let height = buf.get_height() * char_height;
+
+    let mut defs = String::new();
+    let mut used_glyphs = Vec::new();
+    let mut backgrounds = String::new();
+    let mut foregrounds = String::new();
+
+    for y in 0..buf.get_height() {
+        // merge equal backgrounds of a line into one rect
+        let mut run_start = 0;
+        let mut run_color = None;
+        for x in 0..=buf.get_width() {
+            let bg = if x < buf.get_width() {
+                Some(display_colors(buf, &buf.get_char((x, y)).attribute).1)
+            } else {
+                None
+            };
+            if bg != run_color {
+                if let Some(color) = run_color {
+                    if color != 0 {
+                        let _ = writeln!(
+                            backgrounds,
+                            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{char_height}\" fill=\"{}\"/>",
+                            run_start * char_width,
+                            y * char_height,
+                            (x - run_start) * char_width,
+                            color_hex(buf, color)
+                        );
+                    }
+                }
+                run_start = x;
+                run_color = bg;
+            }
+        }
+
+        for x in 0..buf.get_width() {
+            let ch = buf.get_char((x, y));
+            let page = ch.get_font_page();
+            let Some(font) = buf.get_font(page).or_else(|| buf.get_font(0)) else {
+                continue;
+            };
+            let Some(glyph) = font.get_glyph(ch.ch) else {
+                continue;
+            };
+            if glyph.data.iter().all(|row| *row == 0) {
+                continue;
+            }
+            if !used_glyphs.contains(&(page, ch.ch)) {
+                used_glyphs.push((page, ch.ch));
+                let _ = writeln!(
+                    defs,
+                    "<path id=\"g{page}_{}\" d=\"{}\"/>",
+                    ch.ch as u32,
+                    glyph_path(&glyph.data, font.size.width)
+                );
+            }
+            let position = if font.size.width == char_width && font.size.height == char_height {
+                format!("x=\"{}\" y=\"{}\"", x * char_width, y * char_height)
+            } else {
+                // glyphs of other font sizes are scaled to the cells of font 0
+                format!(
+                    "transform=\"translate({} {}) scale({} {})\"",
+                    x * char_width,
+                    y * char_height,
+                    char_width as f32 / font.size.width as f32,
+                    char_height as f32 / font.size.height as f32
+                )
+            };
+            let (fg, _, _) = display_colors(buf, &ch.attribute);
+            let _ = writeln!(
+                foregrounds,
+                "<use xlink:href=\"#g{page}_{}\" {position} fill=\"{}\"/>",
+                ch.ch as u32,
+                color_hex(buf, fg)
+            );
+        }
+    }
+
+    let mut result = String::new();
+    let _ = writeln!(
+        result,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" viewBox=\"0 0 {width} {height}\" width=\"{width}\" height=\"{height}\" shape-rendering=\"crispEdges\">"
+    );
+    let _ = writeln!(result, "<defs>\n{defs}</defs>");
+    let _ = writeln!(result, "<rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>", color_hex(buf, 0));
+    result.push_str(&backgrounds);
+    result.push_str(&foregrounds);
+    result.push_str("</svg>\n");
+    result
+}
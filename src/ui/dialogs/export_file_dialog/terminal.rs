@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use eframe::egui::{self, Ui};
+use i18n_embed_fl::fl;
+use icy_engine::{Buffer, ColorOptimizer, ControlCharHandling, SaveOptions, Size, StringGenerator, TextPane};
+
+use super::html::display_colors;
+use crate::{util::unicode::to_unicode, TerminalColorMode, TerminalExportOptions, SETTINGS};
+
+pub fn create_settings_page(ui: &mut Ui, _options: &mut SaveOptions) {
+    let options = unsafe { &mut SETTINGS.terminal_export_options };
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "export-terminal-color-mode-label"));
+            ui.radio_value(
+                &mut options.color_mode,
+                TerminalColorMode::TrueColor,
+                fl!(crate::LANGUAGE_LOADER, "export-terminal-truecolor"),
+            );
+            ui.radio_value(
+                &mut options.color_mode,
+                TerminalColorMode::Xterm256,
+                fl!(crate::LANGUAGE_LOADER, "export-terminal-xterm256"),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            let mut clip = options.clip_width.is_some();
+            ui.checkbox(&mut clip, fl!(crate::LANGUAGE_LOADER, "export-terminal-clip-label"));
+            if clip != options.clip_width.is_some() {
+                options.clip_width = if clip { Some(80) } else { None };
+            }
+            if let Some(width) = &mut options.clip_width {
+                ui.add(egui::DragValue::new(width).clamp_range(1..=1000));
+            }
+        });
+    });
+}
+
+/// The DOS palette, if the document uses it the colors map to the 16 terminal colors.
+const DOS_PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0x00, 0x00, 0xAA),
+    (0x00, 0xAA, 0x00),
+    (0x00, 0xAA, 0xAA),
+    (0xAA, 0x00, 0x00),
+    (0xAA, 0x00, 0xAA),
+    (0xAA, 0x55, 0x00),
+    (0xAA, 0xAA, 0xAA),
+    (0x55, 0x55, 0x55),
+    (0x55, 0x55, 0xFF),
+    (0x55, 0xFF, 0x55),
+    (0x55, 0xFF, 0xFF),
+    (0xFF, 0x55, 0x55),
+    (0xFF, 0x55, 0xFF),
+    (0xFF, 0xFF, 0x55),
+    (0xFF, 0xFF, 0xFF),
+];
+
+/// DOS color order to ANSI color order (red and blue are swapped).
+const DOS_TO_ANSI: [u8; 8] = [0, 4, 2, 6, 1, 5, 3, 7];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn xterm_color(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let v = 8 + 10 * (index - 232);
+        return (v, v, v);
+    }
+    let i = (index - 16) as usize;
+    (CUBE_LEVELS[i / 36], CUBE_LEVELS[i / 6 % 6], CUBE_LEVELS[i % 6])
+}
+
+/// Nearest color of the fixed xterm-256 colors 16-255, the first 16 depend on the terminal theme.
+pub fn nearest_xterm256(rgb: (u8, u8, u8)) -> u8 {
+    let dist = |c: (u8, u8, u8)| {
+        let dr = i32::from(c.0) - i32::from(rgb.0);
+        let dg = i32::from(c.1) - i32::from(rgb.1);
+        let db = i32::from(c.2) - i32::from(rgb.2);
+        // weighted for perceived brightness
+        2 * dr * dr + 4 * dg * dg + 3 * db * db
+    };
+    (16..=255).min_by_key(|i| dist(xterm_color(*i))).unwrap_or(16)
+}
+
+fn color_sgr(buf: &Buffer, color: u32, is_foreground: bool) -> String {
+    let rgb = buf.palette.get_color(color).get_rgb();
+    if color < 16 && DOS_PALETTE[color as usize] == rgb {
+        let ansi = DOS_TO_ANSI[color as usize % 8];
+        let offset = if is_foreground { 30 } else { 40 };
+        if color < 8 {
+            format!("{}", offset + ansi)
+        } else {
+            // aixterm bright colors, also used for bright backgrounds instead of blink
+            format!("{}", offset + 60 + ansi)
+        }
+    } else {
+        let base = if is_foreground { 38 } else { 48 };
+        format!("{base};5;{}", nearest_xterm256(rgb))
+    }
+}
+
+/// UTF-8 output for modern terminals.
+/// True color goes through the ANSI writer of the engine in modern terminal mode, like the asciicast export.
+/// The engine can't limit colors to the xterm-256 palette, that mode is written here.
+pub fn export_terminal(buf: &Buffer, options: &TerminalExportOptions) -> String {
+    let buf = clipped_buffer(buf, options.clip_width);
+    match options.color_mode {
+        TerminalColorMode::TrueColor => {
+            let mut opt = SaveOptions::new();
+            opt.control_char_handling = ControlCharHandling::FilterOut;
+            opt.modern_terminal_output = true;
+            opt.use_cursor_forward = false;
+            opt.save_sauce = false;
+
+            let mut gen = StringGenerator::new(opt.clone());
+            let optimizer = ColorOptimizer::new(&buf, &opt);
+            let buf = optimizer.optimize(&buf);
+            gen.generate(&buf, &buf);
+            String::from_utf8_lossy(gen.get_data()).to_string()
+        }
+        TerminalColorMode::Xterm256 => export_xterm256(&buf),
+    }
+}
+
+/// Copy of the visible layers, cut to the clip width.
+fn clipped_buffer(buf: &Buffer, clip_width: Option<i32>) -> Buffer {
+    let width = clip_width.map_or(buf.get_width(), |w| w.min(buf.get_width()));
+    let mut result = Buffer::new(Size::new(width, buf.get_height()));
+    result.buffer_type = buf.buffer_type;
+    result.ice_mode = buf.ice_mode;
+    result.palette = buf.palette.clone();
+    for y in 0..buf.get_height() {
+        for x in 0..width {
+            result.layers[0].set_char((x, y), buf.get_char((x, y)));
+        }
+    }
+    result
+}
+
+/// In iCE mode the high background colors are written as bright backgrounds, otherwise blink is SGR 5.
+fn export_xterm256(buf: &Buffer) -> String {
+    let mut result = String::new();
+    for y in 0..buf.get_height() {
+        // trailing blanks on the default background are dropped
+        let mut line_end = buf.get_width();
+        while line_end > 0 {
+            let ch = buf.get_char((line_end - 1, y));
+            let (_, bg, _) = display_colors(buf, &ch.attribute);
+            if bg != 0 || !(ch.ch == ' ' || ch.ch == '\0') {
+                break;
+            }
+            line_end -= 1;
+        }
+
+        let mut last = None;
+        for x in 0..line_end {
+            let ch = buf.get_char((x, y));
+            let colors = display_colors(buf, &ch.attribute);
+            if last != Some(colors) {
+                let (fg, bg, blink) = colors;
+                let _ = write!(
+                    result,
+                    "\x1b[0;{};{}{}m",
+                    color_sgr(buf, fg, true),
+                    color_sgr(buf, bg, false),
+                    if blink { ";5" } else { "" }
+                );
+                last = Some(colors);
+            }
+            let ch = to_unicode(buf.buffer_type, ch.ch);
+            result.push(if ch == '\0' || ch.is_control() { ' ' } else { ch });
+        }
+        if last.is_some() {
+            result.push_str("\x1b[0m");
+        }
+        result.push('\n');
+    }
+    result
+}
@@ -0,0 +1,332 @@
+use std::sync::Arc;
+
+use eframe::{
+    egui::{self, Layout, RichText},
+    epaint::{Color32, Rounding, Vec2},
+};
+use egui::mutex::Mutex;
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::{BufferType, Palette};
+use icy_engine_gui::BufferView;
+
+use crate::{
+    util::find_replace::{find_matches, CharClass, FindMatch, FindPattern, FindScope, Replacement},
+    AnsiEditor, Message, ModalDialog, TerminalResult,
+};
+
+pub struct FindReplaceDialog {
+    should_commit: bool,
+    buffer_view: Arc<Mutex<BufferView>>,
+    cur_layer: usize,
+    buffer_type: BufferType,
+    palette: Palette,
+    has_selection: bool,
+
+    pattern: FindPattern,
+    find_char: String,
+    replacement: Replacement,
+    replace_char: String,
+    scope: FindScope,
+
+    matches: Vec<FindMatch>,
+    /// Search the matches were computed for
+    last_search: Option<(FindPattern, FindScope)>,
+}
+
+impl FindReplaceDialog {
+    pub fn new(editor: &AnsiEditor) -> Self {
+        let cur_layer = editor.get_cur_layer_index().unwrap_or(0);
+        let lock = editor.buffer_view.lock();
+        let has_selection = lock.get_edit_state().is_something_selected();
+        Self {
+            should_commit: false,
+            buffer_view: editor.buffer_view.clone(),
+            cur_layer,
+            buffer_type: lock.get_buffer().buffer_type,
+            palette: lock.get_buffer().palette.clone(),
+            has_selection,
+            pattern: FindPattern::default(),
+            find_char: String::new(),
+            replacement: Replacement::default(),
+            replace_char: String::new(),
+            scope: if has_selection { FindScope::Selection } else { FindScope::Layer },
+            matches: Vec::new(),
+            last_search: None,
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let search = (self.pattern.clone(), self.scope);
+        if self.last_search.as_ref() == Some(&search) {
+            return;
+        }
+        let mut lock = self.buffer_view.lock();
+        self.matches = find_matches(lock.get_edit_state(), self.cur_layer, &self.pattern, self.scope);
+        self.last_search = Some(search);
+
+        // highlight the matches on the canvas
+        let offsets: Vec<_> = lock.get_buffer().layers.iter().map(|layer| layer.get_offset()).collect();
+        let edit_state = lock.get_edit_state_mut();
+        let mask = edit_state.get_tool_overlay_mask_mut();
+        mask.clear();
+        for m in &self.matches {
+            mask.set_is_selected(m.pos + offsets[m.layer], true);
+        }
+        edit_state.set_is_buffer_dirty();
+    }
+
+    fn clear_highlight(&self) {
+        let mut lock = self.buffer_view.lock();
+        lock.get_edit_state_mut().get_tool_overlay_mask_mut().clear();
+        lock.get_edit_state_mut().set_is_buffer_dirty();
+    }
+
+    fn show_find_grid(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("find_grid").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-char-label"));
+            });
+            ui.horizontal(|ui| {
+                let cur = self.pattern.char_class;
+                egui::ComboBox::from_id_source("find_char_class_combobox")
+                    .selected_text(char_class_label(cur))
+                    .show_ui(ui, |ui| {
+                        let classes = [
+                            CharClass::Any,
+                            CharClass::Char(' '),
+                            CharClass::Shade,
+                            CharClass::Block,
+                            CharClass::BoxDrawing,
+                            CharClass::Whitespace,
+                            CharClass::Letter,
+                            CharClass::Digit,
+                        ];
+                        for class in classes {
+                            let selected = std::mem::discriminant(&cur) == std::mem::discriminant(&class);
+                            if ui.selectable_label(selected, char_class_label(class)).clicked() {
+                                self.pattern.char_class = class;
+                            }
+                        }
+                    });
+                if let CharClass::Char(_) = self.pattern.char_class {
+                    ui.add(egui::TextEdit::singleline(&mut self.find_char).char_limit(1).desired_width(24.0));
+                    self.pattern.char_class = CharClass::Char(self.find_char.chars().next().unwrap_or(' '));
+                }
+            });
+            ui.end_row();
+
+            color_row(
+                ui,
+                fl!(crate::LANGUAGE_LOADER, "find-replace-fg-label"),
+                &mut self.pattern.foreground,
+                &self.palette,
+            );
+            color_row(
+                ui,
+                fl!(crate::LANGUAGE_LOADER, "find-replace-bg-label"),
+                &mut self.pattern.background,
+                &self.palette,
+            );
+            font_page_row(ui, &mut self.pattern.font_page);
+            blink_row(
+                ui,
+                "find_blink_combobox",
+                &mut self.pattern.blink,
+                fl!(crate::LANGUAGE_LOADER, "find-replace-any"),
+            );
+        });
+    }
+
+    fn show_replace_grid(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("replace_grid").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-char-label"));
+            });
+            ui.horizontal(|ui| {
+                let mut replace = self.replacement.ch.is_some();
+                ui.checkbox(&mut replace, "");
+                if replace {
+                    ui.add(egui::TextEdit::singleline(&mut self.replace_char).char_limit(1).desired_width(24.0));
+                    self.replacement.ch = Some(self.replace_char.chars().next().unwrap_or(' '));
+                } else {
+                    self.replacement.ch = None;
+                }
+            });
+            ui.end_row();
+
+            color_row(
+                ui,
+                fl!(crate::LANGUAGE_LOADER, "find-replace-fg-label"),
+                &mut self.replacement.foreground,
+                &self.palette,
+            );
+            color_row(
+                ui,
+                fl!(crate::LANGUAGE_LOADER, "find-replace-bg-label"),
+                &mut self.replacement.background,
+                &self.palette,
+            );
+            font_page_row(ui, &mut self.replacement.font_page);
+            blink_row(
+                ui,
+                "replace_blink_combobox",
+                &mut self.replacement.blink,
+                fl!(crate::LANGUAGE_LOADER, "find-replace-keep"),
+            );
+        });
+    }
+}
+
+fn char_class_label(class: CharClass) -> String {
+    match class {
+        CharClass::Any => fl!(crate::LANGUAGE_LOADER, "find-replace-class-any"),
+        CharClass::Char(_) => fl!(crate::LANGUAGE_LOADER, "find-replace-class-char"),
+        CharClass::Shade => fl!(crate::LANGUAGE_LOADER, "find-replace-class-shade"),
+        CharClass::Block => fl!(crate::LANGUAGE_LOADER, "find-replace-class-block"),
+        CharClass::BoxDrawing => fl!(crate::LANGUAGE_LOADER, "find-replace-class-box"),
+        CharClass::Whitespace => fl!(crate::LANGUAGE_LOADER, "find-replace-class-whitespace"),
+        CharClass::Letter => fl!(crate::LANGUAGE_LOADER, "find-replace-class-letter"),
+        CharClass::Digit => fl!(crate::LANGUAGE_LOADER, "find-replace-class-digit"),
+    }
+}
+
+fn color_row(ui: &mut egui::Ui, label: String, value: &mut Option<u32>, palette: &Palette) {
+    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.label(label);
+    });
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+        ui.checkbox(&mut enabled, "");
+        if enabled != value.is_some() {
+            *value = if enabled { Some(0) } else { None };
+        }
+        if let Some(color) = value {
+            let max = palette.len().saturating_sub(1) as u32;
+            ui.add(egui::DragValue::new(color).clamp_range(0..=max));
+            let (r, g, b) = palette.get_color(*color).get_rgb();
+            let (rect, _) = ui.allocate_exact_size(Vec2::new(16.0, 16.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, Rounding::same(2.0), Color32::from_rgb(r, g, b));
+        }
+    });
+    ui.end_row();
+}
+
+fn font_page_row(ui: &mut egui::Ui, value: &mut Option<usize>) {
+    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-font-page-label"));
+    });
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+        ui.checkbox(&mut enabled, "");
+        if enabled != value.is_some() {
+            *value = if enabled { Some(0) } else { None };
+        }
+        if let Some(page) = value {
+            ui.add(egui::DragValue::new(page).clamp_range(0..=255));
+        }
+    });
+    ui.end_row();
+}
+
+fn blink_row(ui: &mut egui::Ui, id: &str, value: &mut Option<bool>, none_label: String) {
+    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-blink-label"));
+    });
+    let label = |v: Option<bool>| match v {
+        None => none_label.clone(),
+        Some(true) => fl!(crate::LANGUAGE_LOADER, "find-replace-on"),
+        Some(false) => fl!(crate::LANGUAGE_LOADER, "find-replace-off"),
+    };
+    egui::ComboBox::from_id_source(id).selected_text(label(*value)).show_ui(ui, |ui| {
+        for v in [None, Some(true), Some(false)] {
+            ui.selectable_value(value, v, label(v));
+        }
+    });
+    ui.end_row();
+}
+
+/// The dialog may be closed without a button (escape), the highlight is removed however it goes away.
+impl Drop for FindReplaceDialog {
+    fn drop(&mut self) {
+        self.clear_highlight();
+    }
+}
+
+impl ModalDialog for FindReplaceDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        let mut result = false;
+        let modal = Modal::new(ctx, "find_replace_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(420.);
+
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "find-replace-title"));
+
+            modal.frame(ui, |ui| {
+                ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "find-replace-find-heading")).strong());
+                self.show_find_grid(ui);
+                ui.separator();
+                ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "find-replace-replace-heading")).strong());
+                self.show_replace_grid(ui);
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-scope-label"));
+                    ui.add_enabled_ui(self.has_selection, |ui| {
+                        ui.radio_value(
+                            &mut self.scope,
+                            FindScope::Selection,
+                            fl!(crate::LANGUAGE_LOADER, "find-replace-scope-selection"),
+                        );
+                    });
+                    ui.radio_value(&mut self.scope, FindScope::Layer, fl!(crate::LANGUAGE_LOADER, "find-replace-scope-layer"));
+                    ui.radio_value(
+                        &mut self.scope,
+                        FindScope::AllLayers,
+                        fl!(crate::LANGUAGE_LOADER, "find-replace-scope-all-layers"),
+                    );
+                });
+
+                self.update_matches();
+                ui.add_space(4.0);
+                ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-matches", count = self.matches.len()));
+            });
+
+            modal.buttons(ui, |ui| {
+                if ui
+                    .add_enabled(
+                        !self.matches.is_empty() && !self.replacement.is_empty(),
+                        egui::Button::new(fl!(crate::LANGUAGE_LOADER, "find-replace-replace-all")),
+                    )
+                    .clicked()
+                {
+                    self.should_commit = true;
+                    result = true;
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit(&self, editor: &mut AnsiEditor) -> TerminalResult<Option<Message>> {
+        let cells: Vec<_> = {
+            let lock = editor.buffer_view.lock();
+            let buffer = lock.get_buffer();
+            self.matches
+                .iter()
+                .map(|m| (m.layer, m.pos, self.replacement.apply(self.buffer_type, buffer.layers[m.layer].get_char(m.pos))))
+                .collect()
+        };
+        editor.set_layer_chars(fl!(crate::LANGUAGE_LOADER, "undo-find-replace"), cells)?;
+        Ok(None)
+    }
+}
@@ -1,12 +1,13 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use crate::{AnsiEditor, Message, TerminalResult};
+use crate::{util::font::is_same_font, AnsiEditor, Message, TerminalResult};
 use eframe::{
     egui::{self, Button, Sense, TextStyle, TopBottomPanel, WidgetText},
     epaint::{FontFamily, FontId, Rounding},
 };
 use egui_modal::Modal;
 use i18n_embed_fl::fl;
+use icy_engine::{BitFont, Buffer, TextPane};
 
 pub struct FontManager {
     selected: usize,
@@ -14,24 +15,122 @@ pub struct FontManager {
     do_select: bool,
     buffer_view: Arc<eframe::epaint::mutex::Mutex<icy_engine_gui::BufferView>>,
     used_fonts: Vec<usize>,
+    /// font slot -> (layer title, cell count) for all layers using the font
+    usage_report: HashMap<usize, Vec<(String, usize)>>,
 }
 
 impl FontManager {
     pub fn new(editor: &AnsiEditor) -> Self {
         let used_fonts = icy_engine::analyze_font_usage(editor.buffer_view.lock().get_buffer());
+        let usage_report = create_usage_report(editor.buffer_view.lock().get_buffer());
         Self {
             selected: 0,
             do_select: false,
             replace_with: 0,
             buffer_view: editor.buffer_view.clone(),
             used_fonts,
+            usage_report,
         }
     }
 
     fn update_used_fonts(&mut self) {
         let lock = &self.buffer_view.lock();
         self.used_fonts = icy_engine::analyze_font_usage(lock.get_buffer());
+        self.usage_report = create_usage_report(lock.get_buffer());
     }
+
+    /// Removes all font slots no cell refers to, slot 0 is the default font and always kept.
+    fn purge_unused_fonts(&mut self) {
+        let unused: Vec<usize> = self
+            .buffer_view
+            .lock()
+            .get_buffer()
+            .font_iter()
+            .map(|(i, _)| *i)
+            .filter(|i| *i > 0 && !self.used_fonts.contains(i))
+            .collect();
+        if unused.is_empty() {
+            return;
+        }
+        let _undo = self
+            .buffer_view
+            .lock()
+            .get_edit_state_mut()
+            .begin_atomic_undo(fl!(crate::LANGUAGE_LOADER, "undo-purge-fonts"));
+        for slot in unused {
+            if let Err(err) = self.buffer_view.lock().get_edit_state_mut().remove_font(slot) {
+                log::error!("Error removing font {slot}: {err}");
+            }
+        }
+    }
+
+    /// Maps cells of fonts which are identical to a font in a lower slot to that font and removes the duplicate.
+    fn merge_identical_fonts(&mut self) {
+        let duplicates = {
+            let lock = &self.buffer_view.lock();
+            let fonts: Vec<(usize, &BitFont)> = lock.get_buffer().font_iter().map(|(i, font)| (*i, font)).collect();
+            find_identical_fonts(&fonts)
+        };
+        if duplicates.is_empty() {
+            return;
+        }
+        let _undo = self
+            .buffer_view
+            .lock()
+            .get_edit_state_mut()
+            .begin_atomic_undo(fl!(crate::LANGUAGE_LOADER, "undo-merge-fonts"));
+        for (slot, original) in duplicates {
+            let lock = &mut self.buffer_view.lock();
+            let edit_state = lock.get_edit_state_mut();
+            if let Err(err) = edit_state.replace_font_usage(slot, original) {
+                log::error!("Error replacing font {slot}->{original}: {err}");
+                continue;
+            }
+            if edit_state.get_buffer().get_font(slot).is_some() {
+                if let Err(err) = edit_state.remove_font(slot) {
+                    log::error!("Error removing font {slot}: {err}");
+                }
+            }
+        }
+    }
+}
+
+/// Maps every font slot to the lowest slot holding an identical font, sorted by slot.
+/// The slots may come in any order, the buffer stores its fonts in a hash map.
+fn find_identical_fonts(fonts: &[(usize, &BitFont)]) -> Vec<(usize, usize)> {
+    let mut result: Vec<(usize, usize)> = fonts
+        .iter()
+        .filter_map(|(slot, font)| {
+            fonts
+                .iter()
+                .filter(|(i, _)| i < slot)
+                .filter(|(_, other)| is_same_font(font, other))
+                .map(|(i, _)| *i)
+                .min()
+                .map(|original| (*slot, original))
+        })
+        .collect();
+    result.sort_unstable();
+    result
+}
+
+fn create_usage_report(buf: &Buffer) -> HashMap<usize, Vec<(String, usize)>> {
+    let mut result: HashMap<usize, Vec<(String, usize)>> = HashMap::new();
+    for layer in &buf.layers {
+        let mut cells: HashMap<usize, usize> = HashMap::new();
+        for y in 0..layer.get_height() {
+            for x in 0..layer.get_width() {
+                let ch = layer.get_char((x, y));
+                if ch.is_visible() {
+                    *cells.entry(ch.get_font_page()).or_default() += 1;
+                }
+            }
+        }
+        for (font, count) in cells {
+            result.entry(font).or_default().push((layer.get_title().to_string(), count));
+        }
+    }
+    result
 }
 
 impl crate::ModalDialog for FontManager {
@@ -39,7 +138,7 @@ impl crate::ModalDialog for FontManager {
         let mut result = false;
         let modal = Modal::new(ctx, "manage_font_dialog");
         modal.show(|ui| {
-            ui.set_height(320.);
+            ui.set_height(420.);
             ui.set_width(600.);
 
             modal.title(ui, fl!(crate::LANGUAGE_LOADER, "manage-font-dialog-title"));
@@ -47,7 +146,7 @@ impl crate::ModalDialog for FontManager {
                 egui::CentralPanel::default().show_inside(ui, |ui| {
                     let row_height = 24.0;
                     ui.label(fl!(crate::LANGUAGE_LOADER, "manage-font-used_font_label"));
-                    egui::ScrollArea::vertical().id_source("bitfont_scroll_area").show(ui, |ui| {
+                    egui::ScrollArea::vertical().id_source("bitfont_scroll_area").max_height(180.0).show(ui, |ui| {
                         for (i, font) in self.buffer_view.lock().get_buffer().font_iter() {
                             let is_selected = *i == self.selected;
 
@@ -122,6 +221,25 @@ impl crate::ModalDialog for FontManager {
                             }
                         }
                     });
+                    ui.separator();
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "manage-font-usage_report_label", font = self.selected));
+                    egui::ScrollArea::vertical()
+                        .id_source("font_usage_scroll_area")
+                        .show(ui, |ui| match self.usage_report.get(&self.selected) {
+                            Some(layers) => {
+                                for (title, cells) in layers {
+                                    ui.label(fl!(
+                                        crate::LANGUAGE_LOADER,
+                                        "manage-font-usage_report_layer",
+                                        layer = title.clone(),
+                                        cells = *cells
+                                    ));
+                                }
+                            }
+                            None => {
+                                ui.label(fl!(crate::LANGUAGE_LOADER, "manage-font-usage_report_unused"));
+                            }
+                        });
                 });
                 TopBottomPanel::bottom("font_manager_bottom_panel")
                     .exact_height(24.0)
@@ -189,6 +307,28 @@ impl crate::ModalDialog for FontManager {
                         ui.output_mut(|o| o.copied_text = font.encode_as_ansi(self.selected));
                     }
                 }
+                let purge_button = ui.button(fl!(crate::LANGUAGE_LOADER, "manage-font-purge_button"));
+                let purge_button = purge_button.on_hover_ui(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "manage-font-purge_button-tooltip"));
+                });
+                if purge_button.clicked() {
+                    self.purge_unused_fonts();
+                    self.update_used_fonts();
+                    self.selected = 0;
+                    self.do_select = true;
+                }
+
+                let merge_button = ui.button(fl!(crate::LANGUAGE_LOADER, "manage-font-merge_button"));
+                let merge_button = merge_button.on_hover_ui(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "manage-font-merge_button-tooltip"));
+                });
+                if merge_button.clicked() {
+                    self.merge_identical_fonts();
+                    self.update_used_fonts();
+                    self.selected = 0;
+                    self.do_select = true;
+                }
+
                 let remove_font_button = &ui.add_enabled(self.selected > 0, Button::new(fl!(crate::LANGUAGE_LOADER, "manage-font-remove_font_button")));
                 if remove_font_button.clicked() {
                     if let Err(err) = self.buffer_view.lock().get_edit_state_mut().remove_font(self.selected) {
@@ -212,3 +352,33 @@ impl crate::ModalDialog for FontManager {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::BitFont;
+
+    use super::find_identical_fonts;
+
+    fn changed_font() -> BitFont {
+        let mut font = BitFont::default();
+        let glyph = font.get_glyph_mut('A').unwrap();
+        glyph.data[0] ^= 0xFF;
+        font
+    }
+
+    #[test]
+    fn test_no_identical_fonts() {
+        let a = BitFont::default();
+        let b = changed_font();
+        assert!(find_identical_fonts(&[(0, &a), (1, &b)]).is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_map_to_lowest_slot() {
+        let a = BitFont::default();
+        let b = changed_font();
+        // unordered like the font map of a buffer
+        let fonts = [(7, &a), (3, &b), (5, &a), (2, &a), (9, &b)];
+        assert_eq!(find_identical_fonts(&fonts), vec![(5, 2), (7, 2), (9, 3)]);
+    }
+}
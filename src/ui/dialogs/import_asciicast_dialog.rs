@@ -0,0 +1,244 @@
+use std::path::PathBuf;
+
+use eframe::egui::{self, Layout, RichText};
+use egui_file::FileDialog;
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::SaveOptions;
+
+use crate::{add_child, create_animation_script, frames_to_buffer, AsciiCastRecording, MainWindow, Message, ModalDialog, SnapshotMode, TerminalResult};
+
+pub struct ImportAsciiCastDialog {
+    should_commit: bool,
+    file_dialog: Option<FileDialog>,
+    path: PathBuf,
+    recording: AsciiCastRecording,
+    /// The animation script, the frames are written next to it.
+    output: String,
+    /// Set after the import button was clicked for existing files, the next click overwrites them.
+    confirm_overwrite: bool,
+
+    use_event_bursts: bool,
+    time_step: u32,
+    burst_gap: u32,
+    limit_idle_time: bool,
+    idle_time_limit: f64,
+}
+
+impl ImportAsciiCastDialog {
+    pub fn new(path: PathBuf, recording: AsciiCastRecording) -> Self {
+        let limit_idle_time = recording.idle_time_limit.is_some();
+        let idle_time_limit = recording.idle_time_limit.unwrap_or(2.0);
+        let output = path.with_extension("icyanim").to_string_lossy().to_string();
+        Self {
+            should_commit: false,
+            file_dialog: None,
+            path,
+            recording,
+            output,
+            confirm_overwrite: false,
+            use_event_bursts: true,
+            time_step: 100,
+            burst_gap: 50,
+            limit_idle_time,
+            idle_time_limit,
+        }
+    }
+
+    fn snapshot_mode(&self) -> SnapshotMode {
+        if self.use_event_bursts {
+            SnapshotMode::EventBurst(self.burst_gap)
+        } else {
+            SnapshotMode::TimeStep(self.time_step)
+        }
+    }
+
+    fn output_path(&self) -> PathBuf {
+        PathBuf::from(&self.output)
+    }
+
+    fn frame_file(&self) -> String {
+        let file_stem = self.output_path().file_stem().unwrap_or_default().to_string_lossy().to_string();
+        format!("{file_stem}_frames.icy")
+    }
+
+    /// Files of the import that already exist.
+    fn existing_files(&self) -> Vec<PathBuf> {
+        let output = self.output_path();
+        [output.with_file_name(self.frame_file()), output].into_iter().filter(|p| p.exists()).collect()
+    }
+
+    fn show_file_dialog(&mut self, ctx: &egui::Context) {
+        let Some(file_dialog) = &mut self.file_dialog else {
+            return;
+        };
+        let selected = if file_dialog.show(ctx).selected() {
+            file_dialog.path().map(|p| p.to_path_buf())
+        } else {
+            None
+        };
+        if !file_dialog.visible() {
+            self.file_dialog = None;
+        }
+        if let Some(path) = selected {
+            self.output = path.with_extension("icyanim").to_string_lossy().to_string();
+            self.confirm_overwrite = false;
+        }
+    }
+}
+
+impl ModalDialog for ImportAsciiCastDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        if self.file_dialog.is_some() {
+            self.show_file_dialog(ctx);
+            return false;
+        }
+        let mut result = false;
+        let modal = Modal::new(ctx, "import_asciicast_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(350.);
+
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "import-asciicast-title"));
+
+            modal.frame(ui, |ui| {
+                egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-size-label"));
+                    });
+                    ui.label(format!("{}x{}", self.recording.width, self.recording.height));
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-duration-label"));
+                    });
+                    ui.label(fl!(
+                        crate::LANGUAGE_LOADER,
+                        "import-asciicast-duration",
+                        seconds = format!("{:.1}", self.recording.duration()),
+                        events = self.recording.events.len()
+                    ));
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.radio_value(&mut self.use_event_bursts, true, fl!(crate::LANGUAGE_LOADER, "import-asciicast-event-bursts"));
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-burst-gap-label"));
+                    });
+                    ui.add_enabled(
+                        self.use_event_bursts,
+                        egui::DragValue::new(&mut self.burst_gap).clamp_range(1..=10000).suffix(" ms"),
+                    );
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.radio_value(&mut self.use_event_bursts, false, fl!(crate::LANGUAGE_LOADER, "import-asciicast-time-step"));
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-time-step-label"));
+                    });
+                    ui.add_enabled(
+                        !self.use_event_bursts,
+                        egui::DragValue::new(&mut self.time_step).clamp_range(10..=10000).suffix(" ms"),
+                    );
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut self.limit_idle_time, fl!(crate::LANGUAGE_LOADER, "import-asciicast-limit-idle-time"));
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-idle-time-label"));
+                    });
+                    ui.add_enabled(
+                        self.limit_idle_time,
+                        egui::DragValue::new(&mut self.idle_time_limit).clamp_range(0.1..=60.0).speed(0.1).suffix(" s"),
+                    );
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-output-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.text_edit_singleline(&mut self.output).changed() {
+                            self.confirm_overwrite = false;
+                        }
+                        if ui.button("…").clicked() {
+                            let mut dialog = FileDialog::save_file(self.path.parent().map(|p| p.to_path_buf()));
+                            dialog.open();
+                            self.file_dialog = Some(dialog);
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "import-asciicast-frames-label"));
+                    });
+                    ui.label(self.frame_file());
+                    ui.end_row();
+                });
+                ui.add_space(4.0);
+                if self.confirm_overwrite {
+                    for file in self.existing_files() {
+                        ui.label(
+                            RichText::new(fl!(
+                                crate::LANGUAGE_LOADER,
+                                "import-asciicast-overwrite-warning",
+                                file = file.to_string_lossy().to_string()
+                            ))
+                            .color(ui.style().visuals.warn_fg_color),
+                        );
+                    }
+                }
+            });
+
+            modal.buttons(ui, |ui| {
+                let label = if self.confirm_overwrite {
+                    fl!(crate::LANGUAGE_LOADER, "import-asciicast-overwrite-button")
+                } else {
+                    fl!(crate::LANGUAGE_LOADER, "import-asciicast-import-button")
+                };
+                if ui.add_enabled(!self.output.is_empty(), egui::Button::new(label)).clicked() {
+                    if !self.existing_files().is_empty() && !self.confirm_overwrite {
+                        self.confirm_overwrite = true;
+                    } else {
+                        self.should_commit = true;
+                        result = true;
+                    }
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit_self(&self, window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+        let idle_time_limit = if self.limit_idle_time { Some(self.idle_time_limit) } else { None };
+        let frames = self.recording.replay(self.snapshot_mode(), idle_time_limit)?;
+
+        let output = self.output_path();
+        let frame_file = self.frame_file();
+        let mut options = SaveOptions::new();
+        options.compress = false;
+        options.lossles_output = true;
+        let bytes = frames_to_buffer(&frames).to_bytes("icy", &options)?;
+        std::fs::write(output.with_file_name(&frame_file), bytes)?;
+
+        let script = create_animation_script(&frame_file, &frames);
+        std::fs::write(&output, &script)?;
+        let id = window.create_id();
+        let editor = crate::AnimationEditor::new(window.gl.clone(), id, &output, script);
+        add_child(&mut window.document_tree, None, Box::new(editor));
+        Ok(None)
+    }
+}
@@ -51,3 +51,27 @@ pub use select_palette_dialog::*;
 
 mod settings_dialog;
 pub use settings_dialog::*;
+
+mod import_asciicast_dialog;
+pub use import_asciicast_dialog::*;
+
+mod batch_sauce_dialog;
+pub use batch_sauce_dialog::*;
+
+mod release_pack_dialog;
+pub use release_pack_dialog::*;
+
+mod save_brush_dialog;
+pub use save_brush_dialog::*;
+
+mod find_replace_dialog;
+pub use find_replace_dialog::*;
+
+mod remap_colors_dialog;
+pub use remap_colors_dialog::*;
+mod convert_palette_dialog;
+pub use convert_palette_dialog::*;
+mod compare_dialog;
+pub use compare_dialog::*;
+mod document_history_dialog;
+pub use document_history_dialog::*;
@@ -283,6 +283,37 @@ impl Template for AtasciiTemplate {
     }
 }
 
+struct PetsciiTemplate {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Template for PetsciiTemplate {
+    fn image(&self) -> &Image<'static> {
+        &crate::ANSI_TEMPLATE_IMG
+    }
+
+    fn title(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "new-file-template-petscii-title")
+    }
+
+    fn description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "new-file-template-petscii-description")
+    }
+
+    fn show_ui(&mut self, ui: &mut Ui) {
+        show_file_ui(ui, &mut self.width, &mut self.height);
+    }
+
+    fn create_file(&self, window: &mut MainWindow<'_>) -> crate::TerminalResult<Option<Message>> {
+        let buf = crate::util::petscii::create_petscii_buffer(self.width, self.height);
+        let id = window.create_id();
+        let editor = AnsiEditor::new(&window.gl, id, buf);
+        add_child(&mut window.document_tree, None, Box::new(editor));
+        Ok(None)
+    }
+}
+
 fn show_file_ui(ui: &mut Ui, width: &mut i32, height: &mut i32) {
     egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
         ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
@@ -455,6 +486,7 @@ impl Default for NewFileDialog {
             Box::new(FileIdTemplate { width: 44, height: 25 }),
             Box::new(AnsiMationTemplate {}),
             Box::new(AtasciiTemplate { width: 40, height: 24 }),
+            Box::new(PetsciiTemplate { width: 40, height: 25 }),
             Box::new(BitFontTemplate { width: 8, height: 16 }),
             Box::new(TdfFontTemplate { font_type: FontType::Color }),
             Box::new(TdfFontTemplate { font_type: FontType::Block }),
@@ -0,0 +1,311 @@
+use std::{path::PathBuf, time::Duration};
+
+use eframe::egui::{self, Layout, RichText};
+use egui_file::FileDialog;
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+
+use crate::{
+    util::{
+        date::today,
+        release_pack::{file_id_size, generate_file_id, read_folder, rename_files, write_pack, PackFile, PackIssue, FILE_ID_HEIGHT, FILE_ID_WIDTH},
+    },
+    MainWindow, Message, ModalDialog, TerminalResult,
+};
+
+enum PackDialog {
+    Folder(FileDialog),
+    Output(FileDialog),
+}
+
+pub struct ReleasePackDialog {
+    should_commit: bool,
+    file_dialog: Option<PackDialog>,
+
+    /// Documents that were open when the dialog was created
+    open_documents: Vec<(String, Vec<u8>)>,
+    folder: Option<PathBuf>,
+    files: Vec<PackFile>,
+    error: Option<String>,
+
+    pack_name: String,
+    group: String,
+    prefix: String,
+    file_id: String,
+    output: String,
+    /// Set after the build button was clicked for an existing pack, the next click overwrites it.
+    confirm_overwrite: bool,
+}
+
+impl ReleasePackDialog {
+    pub fn new(open_documents: Vec<(String, Vec<u8>)>) -> Self {
+        let mut result = Self {
+            should_commit: false,
+            file_dialog: None,
+            open_documents,
+            folder: None,
+            files: Vec::new(),
+            error: None,
+            pack_name: String::new(),
+            group: String::new(),
+            prefix: String::new(),
+            file_id: String::new(),
+            output: String::new(),
+            confirm_overwrite: false,
+        };
+        result.use_open_documents();
+        result
+    }
+
+    fn use_open_documents(&mut self) {
+        self.folder = None;
+        self.error = None;
+        self.files = self
+            .open_documents
+            .iter()
+            .map(|(name, data)| PackFile::new(name.clone(), data.clone()))
+            .collect();
+        self.update_names();
+        self.generate_file_id();
+    }
+
+    fn use_folder(&mut self, folder: PathBuf) {
+        self.error = None;
+        match read_folder(&folder) {
+            Ok((files, file_id)) => {
+                self.files = files;
+                if self.pack_name.is_empty() {
+                    self.pack_name = folder.file_name().unwrap_or_default().to_string_lossy().to_string();
+                }
+                self.update_names();
+                match file_id {
+                    Some(file_id) => self.file_id = file_id,
+                    None => self.generate_file_id(),
+                }
+                if self.output.is_empty() {
+                    self.output = folder.with_extension("zip").to_string_lossy().to_string();
+                }
+            }
+            Err(err) => {
+                self.files.clear();
+                self.error = Some(err.to_string());
+            }
+        }
+        self.folder = Some(folder);
+    }
+
+    fn update_names(&mut self) {
+        rename_files(&mut self.files, &self.prefix);
+    }
+
+    fn generate_file_id(&mut self) {
+        let names: Vec<&str> = self.files.iter().filter(|f| f.include).map(|f| f.name.as_str()).collect();
+        self.file_id = generate_file_id(&self.pack_name, &self.group, &today(), &names);
+    }
+
+    fn issue_text(issue: PackIssue) -> String {
+        match issue {
+            PackIssue::MissingSauce => fl!(crate::LANGUAGE_LOADER, "release-pack-issue-no-sauce"),
+            PackIssue::TooWide(width) => fl!(crate::LANGUAGE_LOADER, "release-pack-issue-too-wide", width = width),
+            PackIssue::UnknownFormat => fl!(crate::LANGUAGE_LOADER, "release-pack-issue-format"),
+        }
+    }
+
+    fn show_file_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &mut self.file_dialog else {
+            return;
+        };
+        let (file_dialog, is_folder) = match dialog {
+            PackDialog::Folder(d) => (d, true),
+            PackDialog::Output(d) => (d, false),
+        };
+        let selected = if file_dialog.show(ctx).selected() {
+            file_dialog.path().map(|p| p.to_path_buf())
+        } else {
+            None
+        };
+        if !file_dialog.visible() {
+            self.file_dialog = None;
+        }
+        if let Some(path) = selected {
+            if is_folder {
+                self.use_folder(path);
+            } else {
+                self.output = path.with_extension("zip").to_string_lossy().to_string();
+                self.confirm_overwrite = false;
+            }
+        }
+    }
+}
+
+impl ModalDialog for ReleasePackDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        if self.file_dialog.is_some() {
+            self.show_file_dialog(ctx);
+            return false;
+        }
+        let mut result = false;
+        let modal = Modal::new(ctx, "release_pack_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(640.);
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "release-pack-title"));
+
+            modal.frame(ui, |ui| {
+                egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "release-pack-source-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        if ui
+                            .radio(self.folder.is_none(), fl!(crate::LANGUAGE_LOADER, "release-pack-open-documents"))
+                            .clicked()
+                        {
+                            self.use_open_documents();
+                        }
+                        let folder_label = match &self.folder {
+                            Some(folder) => folder.to_string_lossy().to_string(),
+                            None => fl!(crate::LANGUAGE_LOADER, "release-pack-folder"),
+                        };
+                        if ui.radio(self.folder.is_some(), folder_label).clicked() {
+                            let mut dialog = FileDialog::select_folder(self.folder.clone());
+                            dialog.open();
+                            self.file_dialog = Some(PackDialog::Folder(dialog));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "release-pack-name-label"));
+                    });
+                    ui.text_edit_singleline(&mut self.pack_name);
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "edit-sauce-group-label"));
+                    });
+                    ui.text_edit_singleline(&mut self.group);
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "release-pack-prefix-label"));
+                    });
+                    if ui.text_edit_singleline(&mut self.prefix).changed() {
+                        self.update_names();
+                    }
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "release-pack-output-label"));
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::TextEdit::singleline(&mut self.output).desired_width(400.0)).changed() {
+                            self.confirm_overwrite = false;
+                        }
+                        if ui.button("…").clicked() {
+                            let mut dialog = FileDialog::save_file(self.folder.clone());
+                            dialog.open();
+                            self.file_dialog = Some(PackDialog::Output(dialog));
+                        }
+                    });
+                    ui.end_row();
+                });
+
+                ui.add_space(8.0);
+                if let Some(err) = &self.error {
+                    ui.label(RichText::new(err).color(ui.style().visuals.error_fg_color));
+                }
+                egui::ScrollArea::vertical().id_source("release_pack_files").max_height(200.0).show(ui, |ui| {
+                    egui::Grid::new("release_pack_files_grid").num_columns(3).striped(true).show(ui, |ui| {
+                        for file in &mut self.files {
+                            ui.checkbox(&mut file.include, &file.source);
+                            ui.label(&file.name);
+                            if file.issues.is_empty() {
+                                ui.label("");
+                            } else {
+                                let text = file.issues.iter().map(|i| Self::issue_text(*i)).collect::<Vec<_>>().join(", ");
+                                ui.label(RichText::new(text).color(ui.style().visuals.warn_fg_color));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "release-pack-file-id-label"));
+                    if ui.button(fl!(crate::LANGUAGE_LOADER, "release-pack-generate-file-id")).clicked() {
+                        self.generate_file_id();
+                    }
+                });
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.file_id)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(FILE_ID_HEIGHT)
+                        .desired_width(f32::INFINITY),
+                );
+                let (lines, width) = file_id_size(&self.file_id);
+                if lines > FILE_ID_HEIGHT || width > FILE_ID_WIDTH {
+                    ui.label(
+                        RichText::new(fl!(
+                            crate::LANGUAGE_LOADER,
+                            "release-pack-file-id-too-big",
+                            width = width,
+                            lines = lines,
+                            max_width = FILE_ID_WIDTH,
+                            max_lines = FILE_ID_HEIGHT
+                        ))
+                        .color(ui.style().visuals.warn_fg_color),
+                    );
+                }
+                if self.confirm_overwrite {
+                    ui.label(
+                        RichText::new(fl!(crate::LANGUAGE_LOADER, "release-pack-overwrite-warning", file = self.output.clone()))
+                            .color(ui.style().visuals.warn_fg_color),
+                    );
+                }
+            });
+
+            modal.buttons(ui, |ui| {
+                let (lines, width) = file_id_size(&self.file_id);
+                let enabled = !self.output.is_empty() && self.files.iter().any(|f| f.include) && lines <= FILE_ID_HEIGHT && width <= FILE_ID_WIDTH;
+                let label = if self.confirm_overwrite {
+                    fl!(crate::LANGUAGE_LOADER, "release-pack-overwrite-button")
+                } else {
+                    fl!(crate::LANGUAGE_LOADER, "release-pack-build-button")
+                };
+                if ui.add_enabled(enabled, egui::Button::new(label)).clicked() {
+                    if PathBuf::from(&self.output).exists() && !self.confirm_overwrite {
+                        self.confirm_overwrite = true;
+                    } else {
+                        self.should_commit = true;
+                        result = true;
+                    }
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit_self(&self, window: &mut MainWindow<'_>) -> TerminalResult<Option<Message>> {
+        let path = PathBuf::from(&self.output);
+        write_pack(&path, &self.file_id, &self.files)?;
+        window
+            .toasts
+            .info(fl!(
+                crate::LANGUAGE_LOADER,
+                "release-pack-written",
+                file = path.file_name().unwrap_or_default().to_string_lossy().to_string()
+            ))
+            .set_duration(Some(Duration::from_secs(5)));
+        Ok(None)
+    }
+}
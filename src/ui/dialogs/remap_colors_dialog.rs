@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use eframe::{
+    egui::{self, RichText},
+    epaint::{Color32, Rounding, Vec2},
+};
+use egui::mutex::Mutex;
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::{AttributedChar, Palette};
+use icy_engine_gui::BufferView;
+
+use crate::{
+    util::{
+        color_remap::{count_color_usage, ColorMapping, ColorUsage},
+        find_replace::{find_matches, FindMatch, FindPattern, FindScope},
+    },
+    AnsiEditor, Message, ModalDialog, TerminalResult,
+};
+
+pub struct RemapColorsDialog {
+    should_commit: bool,
+    buffer_view: Arc<Mutex<BufferView>>,
+    cur_layer: usize,
+    palette: Palette,
+    has_selection: bool,
+
+    scope: FindScope,
+    /// Original cells of the scope
+    cells: Vec<(FindMatch, AttributedChar)>,
+    cells_scope: Option<FindScope>,
+    usage: Vec<ColorUsage>,
+    mapping: ColorMapping,
+
+    live_preview: bool,
+    /// Mapping currently written to the buffer for the preview
+    preview_mapping: Option<ColorMapping>,
+}
+
+impl RemapColorsDialog {
+    pub fn new(editor: &AnsiEditor) -> Self {
+        let cur_layer = editor.get_cur_layer_index().unwrap_or(0);
+        let lock = editor.buffer_view.lock();
+        let has_selection = lock.get_edit_state().is_something_selected();
+        Self {
+            should_commit: false,
+            buffer_view: editor.buffer_view.clone(),
+            cur_layer,
+            palette: lock.get_buffer().palette.clone(),
+            has_selection,
+            scope: if has_selection { FindScope::Selection } else { FindScope::AllLayers },
+            cells: Vec::new(),
+            cells_scope: None,
+            usage: Vec::new(),
+            mapping: ColorMapping::default(),
+            live_preview: true,
+            preview_mapping: None,
+        }
+    }
+
+    fn load_cells(&mut self) {
+        if self.cells_scope == Some(self.scope) {
+            return;
+        }
+        self.restore_cells();
+        let lock = self.buffer_view.lock();
+        let edit_state = lock.get_edit_state();
+        self.cells = find_matches(edit_state, self.cur_layer, &FindPattern::default(), self.scope)
+            .into_iter()
+            .map(|m| (m, edit_state.get_buffer().layers[m.layer].get_char(m.pos)))
+            .collect();
+        self.usage = count_color_usage(self.cells.iter().map(|(_, ch)| ch));
+        self.mapping = ColorMapping::identity(&self.usage);
+        self.cells_scope = Some(self.scope);
+    }
+
+    fn write_cells(&self, mapping: Option<&ColorMapping>) {
+        let mut lock = self.buffer_view.lock();
+        for (m, ch) in &self.cells {
+            let ch = mapping.map_or(*ch, |mapping| mapping.apply(*ch));
+            lock.get_buffer_mut().layers[m.layer].set_char(m.pos, ch);
+        }
+        lock.get_edit_state_mut().set_is_buffer_dirty();
+    }
+
+    fn restore_cells(&mut self) {
+        if self.preview_mapping.take().is_some() {
+            self.write_cells(None);
+        }
+    }
+
+    /// The preview writes directly to the layers, the originals are restored before the undoable commit.
+    fn update_preview(&mut self) {
+        let wanted = if self.live_preview && !self.mapping.is_identity() {
+            Some(self.mapping.clone())
+        } else {
+            None
+        };
+        if wanted == self.preview_mapping {
+            return;
+        }
+        self.write_cells(wanted.as_ref());
+        self.preview_mapping = wanted;
+    }
+
+    fn show_mapping_table(&mut self, ui: &mut egui::Ui) {
+        let max = self.palette.len().saturating_sub(1) as u32;
+        egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+            egui::Grid::new("remap_colors_grid")
+                .num_columns(5)
+                .spacing([12.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "remap-colors-color")).strong());
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "remap-colors-fg-cells")).strong());
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "remap-colors-fg-target")).strong());
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "remap-colors-bg-cells")).strong());
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "remap-colors-bg-target")).strong());
+                    ui.end_row();
+
+                    for usage in &self.usage {
+                        ui.horizontal(|ui| {
+                            color_swatch(ui, &self.palette, usage.index);
+                            ui.label(usage.index.to_string());
+                        });
+                        for (count, map) in [(usage.fg_count, &mut self.mapping.foreground), (usage.bg_count, &mut self.mapping.background)] {
+                            ui.label(count.to_string());
+                            if let Some((_, target)) = map.iter_mut().find(|(from, _)| *from == usage.index) {
+                                ui.horizontal(|ui| {
+                                    ui.add(egui::DragValue::new(target).clamp_range(0..=max));
+                                    color_swatch(ui, &self.palette, *target);
+                                });
+                            } else {
+                                ui.label("-");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
+
+fn color_swatch(ui: &mut egui::Ui, palette: &Palette, color: u32) {
+    let (r, g, b) = palette.get_color(color).get_rgb();
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(16.0, 16.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, Rounding::same(2.0), Color32::from_rgb(r, g, b));
+}
+
+/// Closing the dialog with escape skips the buttons, the preview must not stay in the buffer without an undo operation.
+impl Drop for RemapColorsDialog {
+    fn drop(&mut self) {
+        self.restore_cells();
+    }
+}
+
+impl ModalDialog for RemapColorsDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        let mut result = false;
+        let modal = Modal::new(ctx, "remap_colors_dialog");
+        self.load_cells();
+
+        modal.show(|ui| {
+            ui.set_width(460.);
+
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "remap-colors-title"));
+
+            modal.frame(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "find-replace-scope-label"));
+                    ui.add_enabled_ui(self.has_selection, |ui| {
+                        ui.radio_value(
+                            &mut self.scope,
+                            FindScope::Selection,
+                            fl!(crate::LANGUAGE_LOADER, "find-replace-scope-selection"),
+                        );
+                    });
+                    ui.radio_value(&mut self.scope, FindScope::Layer, fl!(crate::LANGUAGE_LOADER, "find-replace-scope-layer"));
+                    ui.radio_value(
+                        &mut self.scope,
+                        FindScope::AllLayers,
+                        fl!(crate::LANGUAGE_LOADER, "remap-colors-scope-document"),
+                    );
+                });
+                ui.separator();
+                self.show_mapping_table(ui);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.live_preview, fl!(crate::LANGUAGE_LOADER, "remap-colors-live-preview"));
+                    if ui.button(fl!(crate::LANGUAGE_LOADER, "remap-colors-reset")).clicked() {
+                        self.mapping = ColorMapping::identity(&self.usage);
+                    }
+                });
+            });
+
+            modal.buttons(ui, |ui| {
+                if ui
+                    .add_enabled(
+                        !self.mapping.is_identity(),
+                        egui::Button::new(fl!(crate::LANGUAGE_LOADER, "remap-colors-apply")),
+                    )
+                    .clicked()
+                {
+                    self.should_commit = true;
+                    result = true;
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+
+        if result {
+            self.restore_cells();
+        } else {
+            self.load_cells();
+            self.update_preview();
+        }
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit(&self, editor: &mut AnsiEditor) -> TerminalResult<Option<Message>> {
+        let cells = self.cells.iter().map(|(m, ch)| (m.layer, m.pos, self.mapping.apply(*ch)));
+        editor.set_layer_chars(fl!(crate::LANGUAGE_LOADER, "remap-colors-title"), cells)?;
+        Ok(None)
+    }
+}
@@ -0,0 +1,97 @@
+use eframe::egui::{self, Layout, RichText};
+use egui_modal::Modal;
+use i18n_embed_fl::fl;
+use icy_engine::Layer;
+
+use crate::{
+    util::brush_library::{brush_path, save_brush},
+    AnsiEditor, Message, ModalDialog, TerminalResult,
+};
+
+pub struct SaveBrushDialog {
+    should_commit: bool,
+    name: String,
+    /// Set after the save button was clicked for an existing brush, the next click overwrites it.
+    confirm_overwrite: bool,
+}
+
+impl Default for SaveBrushDialog {
+    fn default() -> Self {
+        Self {
+            should_commit: false,
+            name: "brush".to_string(),
+            confirm_overwrite: false,
+        }
+    }
+}
+
+impl ModalDialog for SaveBrushDialog {
+    fn show(&mut self, ctx: &egui::Context) -> bool {
+        let mut result = false;
+        let modal = Modal::new(ctx, "save_brush_dialog");
+
+        modal.show(|ui| {
+            ui.set_width(300.);
+
+            modal.title(ui, fl!(crate::LANGUAGE_LOADER, "save-brush-title"));
+
+            modal.frame(ui, |ui| {
+                egui::Grid::new("some_unique_id").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "save-brush-name-label"));
+                    });
+                    if ui.add(egui::TextEdit::singleline(&mut self.name)).changed() {
+                        self.confirm_overwrite = false;
+                    }
+                    ui.end_row();
+                });
+                if self.confirm_overwrite {
+                    ui.label(
+                        RichText::new(fl!(crate::LANGUAGE_LOADER, "save-brush-overwrite-warning", name = self.name.trim().to_string()))
+                            .color(ui.style().visuals.warn_fg_color),
+                    );
+                }
+                ui.add_space(4.0);
+            });
+
+            modal.buttons(ui, |ui| {
+                let label = if self.confirm_overwrite {
+                    fl!(crate::LANGUAGE_LOADER, "save-brush-overwrite")
+                } else {
+                    fl!(crate::LANGUAGE_LOADER, "save-brush-save")
+                };
+                if ui.add_enabled(!self.name.trim().is_empty(), egui::Button::new(label)).clicked() {
+                    if brush_path(&self.name).map_or(false, |path| path.exists()) && !self.confirm_overwrite {
+                        self.confirm_overwrite = true;
+                    } else {
+                        self.should_commit = true;
+                        result = true;
+                    }
+                }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "new-file-cancel")).clicked() {
+                    result = true;
+                }
+            });
+        });
+        modal.open();
+        result
+    }
+
+    fn should_commit(&self) -> bool {
+        self.should_commit
+    }
+
+    fn commit(&self, editor: &mut AnsiEditor) -> TerminalResult<Option<Message>> {
+        let Some(data) = editor.buffer_view.lock().get_edit_state_mut().get_clipboard_data() else {
+            return Ok(None);
+        };
+        let Some(layer) = Layer::from_clipboard_data(&data) else {
+            return Ok(None);
+        };
+        save_brush(&self.name, &layer, self.confirm_overwrite)?;
+        unsafe {
+            crate::model::brush_imp::CUSTOM_BRUSH = Some(layer);
+        }
+        Ok(Some(Message::SelectTool(crate::BRUSH_TOOL)))
+    }
+}
@@ -9,13 +9,22 @@ use i18n_embed_fl::fl;
 use icy_engine::{AttributedChar, BitFont, Buffer, Color, Position, Size, TextAttribute};
 use icy_engine_gui::{show_monitor_settings, show_terminal_area, BufferView, MarkerSettings, MonitorSettings};
 
-use crate::{CharSetMapping, CharTableToolWindow, Commands, FontSelector, ModalDialog, SelectOutlineDialog, Settings, CHARACTER_SETS, KEYBINDINGS, SETTINGS};
+use crate::{
+    util::{
+        autosave::cleanup_orphaned_autosaves,
+        glyph_map::{GlyphCodePage, GlyphMapping},
+    },
+    AutoSaveOptions, CharSetMapping, CharTableToolWindow, Commands, FontSelector, ModalDialog, SelectOutlineDialog, Settings, CHARACTER_SETS, GLYPH_MAPPINGS,
+    KEYBINDINGS, SETTINGS,
+};
 pub struct SettingsDialog {
     settings_category: usize,
     select_outline_dialog: SelectOutlineDialog,
     is_dark_mode: Option<bool>,
     monitor_settings: MonitorSettings,
     marker_settings: MarkerSettings,
+    auto_save_options: AutoSaveOptions,
+    cleanup_result: Option<usize>,
     key_filter: String,
     key_bindings: Vec<(String, eframe::egui::Key, Modifiers)>,
 
@@ -26,12 +35,19 @@ pub struct SettingsDialog {
     views: Vec<Arc<Mutex<BufferView>>>,
     selected_view: usize,
     char_view: CharTableToolWindow,
+
+    glyph_mappings: Vec<GlyphMapping>,
+    cur_glyph_mapping: usize,
+    /// Mirror x, mirror y & rotation tables as editable text
+    glyph_texts: Option<[String; 3]>,
 }
 const MONITOR_CAT: usize = 0;
 const MARKER_CAT: usize = 1;
 const OUTLINE_CAT: usize = 2;
 const CHAR_SET_CAT: usize = 3;
-const KEYBIND_CAT: usize = 4;
+const GLYPH_MAP_CAT: usize = 4;
+const AUTOSAVE_CAT: usize = 5;
+const KEYBIND_CAT: usize = 6;
 
 impl SettingsDialog {
     pub fn new(ctx: &Context, gl: &Arc<glow::Context>) -> Self {
@@ -69,6 +85,8 @@ impl SettingsDialog {
             select_outline_dialog: SelectOutlineDialog::default(),
             monitor_settings: Default::default(),
             marker_settings: Default::default(),
+            auto_save_options: Default::default(),
+            cleanup_result: None,
             key_filter: String::new(),
             key_bindings: Commands::default_keybindings(),
             char_sets: Default::default(),
@@ -78,6 +96,9 @@ impl SettingsDialog {
             views,
             char_view,
             font_selector: None,
+            glyph_mappings: Vec::new(),
+            cur_glyph_mapping: 0,
+            glyph_texts: None,
             is_dark_mode: unsafe { SETTINGS.is_dark_mode },
         }
     }
@@ -85,8 +106,13 @@ impl SettingsDialog {
     pub(crate) fn init(&mut self) {
         self.monitor_settings = unsafe { SETTINGS.monitor_settings.clone() };
         self.marker_settings = unsafe { SETTINGS.marker_settings.clone() };
+        self.auto_save_options = unsafe { SETTINGS.auto_save_options.clone() };
+        self.cleanup_result = None;
         self.key_bindings = unsafe { KEYBINDINGS.key_bindings.clone() };
         self.char_sets = unsafe { CHARACTER_SETS.character_sets.clone() };
+        self.glyph_mappings = unsafe { GLYPH_MAPPINGS.glyph_mappings.clone() };
+        self.cur_glyph_mapping = 0;
+        self.glyph_texts = None;
     }
 
     pub fn show(&mut self, ctx: &egui::Context) -> bool {
@@ -101,9 +127,17 @@ impl SettingsDialog {
             if selector.show(ctx) {
                 if selector.should_commit() {
                     let font = selector.selected_font().get_checksum();
-                    let mut new_set = self.char_sets[0].clone();
-                    new_set.font_checksum = font;
-                    self.char_sets.push(new_set);
+                    if self.settings_category == GLYPH_MAP_CAT {
+                        let mut new_mapping = GlyphMapping::cp437();
+                        new_mapping.font_checksum = font;
+                        self.glyph_mappings.push(new_mapping);
+                        self.cur_glyph_mapping = self.glyph_mappings.len() - 1;
+                        self.glyph_texts = None;
+                    } else {
+                        let mut new_set = self.char_sets[0].clone();
+                        new_set.font_checksum = font;
+                        self.char_sets.push(new_set);
+                    }
                 }
                 self.font_selector = None;
             }
@@ -149,6 +183,19 @@ impl SettingsDialog {
                     {
                         self.settings_category = CHAR_SET_CAT;
                     }
+                    if ui
+                        .selectable_label(settings_category == GLYPH_MAP_CAT, fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-category"))
+                        .clicked()
+                    {
+                        self.settings_category = GLYPH_MAP_CAT;
+                    }
+
+                    if ui
+                        .selectable_label(settings_category == AUTOSAVE_CAT, fl!(crate::LANGUAGE_LOADER, "settings-autosave-category"))
+                        .clicked()
+                    {
+                        self.settings_category = AUTOSAVE_CAT;
+                    }
 
                     if ui
                         .selectable_label(settings_category == KEYBIND_CAT, fl!(crate::LANGUAGE_LOADER, "settings-keybindings-category"))
@@ -178,6 +225,16 @@ impl SettingsDialog {
                         self.show_charset_editor(ui);
                     }
 
+                    GLYPH_MAP_CAT => {
+                        ui.add_space(8.0);
+                        self.show_glyph_mapping_editor(ui);
+                    }
+
+                    AUTOSAVE_CAT => {
+                        ui.add_space(8.0);
+                        self.show_autosave_settings(ui);
+                    }
+
                     OUTLINE_CAT => {
                         ui.add_space(8.0);
                         self.select_outline_dialog.show_outline_ui(ui, 4, Vec2::new(8.0, 8.0));
@@ -214,6 +271,12 @@ impl SettingsDialog {
                                     log::error!("Error saving character sets: {}", err);
                                 }
                             }
+                            if GLYPH_MAPPINGS.glyph_mappings != self.glyph_mappings {
+                                GLYPH_MAPPINGS.glyph_mappings = self.glyph_mappings.clone();
+                                if let Err(err) = GLYPH_MAPPINGS.save() {
+                                    log::error!("Error saving glyph mappings: {err}");
+                                }
+                            }
                             SETTINGS.is_dark_mode = self.is_dark_mode;
                             if let Err(err) = Settings::save() {
                                 log::error!("Error saving settings: {err}");
@@ -226,6 +289,7 @@ impl SettingsDialog {
                         unsafe {
                             SETTINGS.monitor_settings = self.monitor_settings.clone();
                             SETTINGS.marker_settings = self.marker_settings.clone();
+                            SETTINGS.auto_save_options = self.auto_save_options.clone();
                             if let Some(dark_mode) = SETTINGS.is_dark_mode {
                                 ui.visuals_mut().dark_mode = dark_mode;
                             }
@@ -236,6 +300,8 @@ impl SettingsDialog {
                     if (self.settings_category == MONITOR_CAT
                         || self.settings_category == MARKER_CAT
                         || self.settings_category == CHAR_SET_CAT
+                        || self.settings_category == GLYPH_MAP_CAT
+                        || self.settings_category == AUTOSAVE_CAT
                         || self.settings_category == KEYBIND_CAT)
                         && ui.button(fl!(crate::LANGUAGE_LOADER, "settings-reset_button")).clicked()
                     {
@@ -243,7 +309,13 @@ impl SettingsDialog {
                             match self.settings_category {
                                 MONITOR_CAT => SETTINGS.monitor_settings = Default::default(),
                                 MARKER_CAT => SETTINGS.marker_settings = Default::default(),
+                                AUTOSAVE_CAT => SETTINGS.auto_save_options = Default::default(),
                                 CHAR_SET_CAT => self.char_sets = Default::default(),
+                                GLYPH_MAP_CAT => {
+                                    self.glyph_mappings = GlyphMapping::defaults();
+                                    self.cur_glyph_mapping = 0;
+                                    self.glyph_texts = None;
+                                }
                                 KEYBIND_CAT => {
                                     self.key_bindings = Commands::default_keybindings();
                                 }
@@ -397,6 +469,156 @@ impl SettingsDialog {
     }
 }
 
+impl SettingsDialog {
+    fn glyph_mapping_label(&self, mapping: &GlyphMapping) -> String {
+        if mapping.font_checksum == 0 {
+            return mapping.code_page.name().to_string();
+        }
+        for font in &self.font_cache {
+            if font.checksum == mapping.font_checksum {
+                return font.name.to_string();
+            }
+        }
+        "Unknown".to_string()
+    }
+
+    pub fn show_autosave_settings(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("autosave_settings_grid")
+            .num_columns(2)
+            .spacing([4.0, 8.0])
+            .show(ui, |ui| unsafe {
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "settings-autosave-count-label"));
+                });
+                ui.add(egui::DragValue::new(&mut SETTINGS.auto_save_options.snapshot_count).clamp_range(1..=100));
+                ui.end_row();
+
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "settings-autosave-interval-label"));
+                });
+                ui.add(
+                    egui::DragValue::new(&mut SETTINGS.auto_save_options.snapshot_interval)
+                        .clamp_range(1..=1440)
+                        .suffix(fl!(crate::LANGUAGE_LOADER, "settings-autosave-minutes")),
+                );
+                ui.end_row();
+            });
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            if ui.button(fl!(crate::LANGUAGE_LOADER, "document-history-cleanup")).clicked() {
+                self.cleanup_result = Some(cleanup_orphaned_autosaves());
+            }
+            if let Some(removed) = self.cleanup_result {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "document-history-cleanup-result", count = removed));
+            }
+        });
+    }
+
+    pub fn show_glyph_mapping_editor(&mut self, ui: &mut egui::Ui) {
+        if self.glyph_mappings.is_empty() {
+            self.glyph_mappings = GlyphMapping::defaults();
+        }
+        self.cur_glyph_mapping = self.cur_glyph_mapping.min(self.glyph_mappings.len() - 1);
+
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-list-label"));
+                egui::ScrollArea::vertical().id_source("glyph_map_list").max_height(120.0).show(ui, |ui| {
+                    for i in 0..self.glyph_mappings.len() {
+                        let label = self.glyph_mapping_label(&self.glyph_mappings[i]);
+                        if ui.selectable_label(self.cur_glyph_mapping == i, label).clicked() {
+                            self.cur_glyph_mapping = i;
+                            self.glyph_texts = None;
+                        }
+                    }
+                });
+            });
+            ui.separator();
+            if ui.add(egui::Button::new(fl!(crate::LANGUAGE_LOADER, "add-font-dialog-select"))).clicked() {
+                self.font_selector = Some(FontSelector::font_library());
+            }
+
+            let is_default = self.glyph_mappings[self.cur_glyph_mapping].font_checksum == 0;
+            if ui
+                .add_enabled(!is_default, egui::Button::new(fl!(crate::LANGUAGE_LOADER, "manage-font-remove_font_button")))
+                .clicked()
+            {
+                self.glyph_mappings.remove(self.cur_glyph_mapping);
+                self.cur_glyph_mapping = 0;
+                self.glyph_texts = None;
+            }
+        });
+        ui.separator();
+
+        let mapping = &mut self.glyph_mappings[self.cur_glyph_mapping];
+        let texts = self.glyph_texts.get_or_insert_with(|| {
+            [
+                mapping.pairs_to_text(&mapping.mirror_x),
+                mapping.pairs_to_text(&mapping.mirror_y),
+                mapping.cycles_to_text(),
+            ]
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-code-page-label"));
+            let mut code_page = mapping.code_page;
+            ui.radio_value(&mut code_page, GlyphCodePage::Cp437, GlyphCodePage::Cp437.name());
+            ui.radio_value(&mut code_page, GlyphCodePage::Atascii, GlyphCodePage::Atascii.name());
+            if code_page != mapping.code_page && mapping.font_checksum != 0 {
+                let font_checksum = mapping.font_checksum;
+                *mapping = GlyphMapping::default_for(code_page);
+                mapping.font_checksum = font_checksum;
+                *texts = [
+                    mapping.pairs_to_text(&mapping.mirror_x),
+                    mapping.pairs_to_text(&mapping.mirror_y),
+                    mapping.cycles_to_text(),
+                ];
+            }
+        });
+
+        ui.label(fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-mirror-x-label"));
+        if ui
+            .add(
+                egui::TextEdit::multiline(&mut texts[0])
+                    .code_editor()
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            )
+            .changed()
+        {
+            mapping.mirror_x = mapping.text_to_pairs(&texts[0]);
+        }
+
+        ui.label(fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-mirror-y-label"));
+        if ui
+            .add(
+                egui::TextEdit::multiline(&mut texts[1])
+                    .code_editor()
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            )
+            .changed()
+        {
+            mapping.mirror_y = mapping.text_to_pairs(&texts[1]);
+        }
+
+        ui.label(fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-rotate-label"));
+        if ui
+            .add(
+                egui::TextEdit::multiline(&mut texts[2])
+                    .code_editor()
+                    .desired_rows(6)
+                    .desired_width(f32::INFINITY),
+            )
+            .changed()
+        {
+            mapping.rotate = mapping.text_to_cycles(&texts[2]);
+        }
+
+        ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "settings-glyph-map-description")).small());
+    }
+}
+
 pub fn show_marker_settings(ui: &mut egui::Ui, old_settings: &MarkerSettings) -> Option<MarkerSettings> {
     let mut result = None;
 
@@ -3,7 +3,7 @@ use std::{fs, path::PathBuf, sync::Arc, time::Instant};
 use crate::{
     create_image,
     model::Tool,
-    util::autosave::{remove_autosave, store_auto_save},
+    util::autosave::{discard_recovery, store_auto_save},
     Document, DocumentOptions, Message, Settings, DEFAULT_CHAR_SET_TABLE, FIRST_TOOL, MRU_FILES,
 };
 use eframe::{
@@ -58,7 +58,7 @@ impl DocumentTab {
                 } else if let Err(err) = fs::rename(tmp_file, path) {
                     msg = Some(Message::ShowError(format!("Error moving file {err}")));
                 }
-                remove_autosave(path);
+                discard_recovery(path);
 
                 let undo_stack_len = doc.undo_stack_len();
                 self.last_save = undo_stack_len;
@@ -71,11 +71,16 @@ impl DocumentTab {
             }
         }
         if msg.is_none() {
-            remove_autosave(path);
+            discard_recovery(path);
         }
         msg
     }
 
+    /// Content was replaced by an autosave, the document needs to be saved again.
+    pub fn mark_unsaved(&mut self) {
+        self.last_save = usize::MAX;
+    }
+
     pub fn get_path(&self) -> Option<PathBuf> {
         self.full_path.clone()
     }
@@ -84,7 +89,7 @@ impl DocumentTab {
         let doc = &mut self.doc.lock();
         path.set_extension(doc.default_extension());
         if let Some(old_path) = &self.full_path {
-            remove_autosave(old_path);
+            discard_recovery(old_path);
         }
         self.full_path = Some(path);
     }
@@ -0,0 +1,203 @@
+use icy_engine::{ansi, ascii::CP437Converter, Buffer, BufferParser, Caret, Layer, Size, TextPane, UnicodeConverter};
+
+use crate::TerminalResult;
+
+/// How the replayed terminal output is cut into animation frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapshotMode {
+    /// Take a snapshot every n milliseconds of recording time.
+    TimeStep(u32),
+    /// Take a snapshot after each burst of output events. Events closer than n milliseconds belong to the same burst.
+    EventBurst(u32),
+}
+
+/// An asciinema v2 recording.
+/// Spec: <https://docs.asciinema.org/manual/asciicast/v2/>
+pub struct AsciiCastRecording {
+    pub width: i32,
+    pub height: i32,
+    pub title: String,
+    pub idle_time_limit: Option<f64>,
+    /// Output events as (time in seconds, data)
+    pub events: Vec<(f64, String)>,
+}
+
+impl AsciiCastRecording {
+    pub fn from_bytes(data: &[u8]) -> TerminalResult<Self> {
+        let txt = String::from_utf8_lossy(data);
+        let mut lines = txt.lines().filter(|l| !l.trim().is_empty());
+
+        let Some(header) = lines.next() else {
+            return Err(anyhow::anyhow!("empty asciicast file"));
+        };
+        let header: serde_json::Value = serde_json::from_str(header)?;
+        let version = header["version"].as_i64().unwrap_or_default();
+        if version != 2 {
+            return Err(anyhow::anyhow!("unsupported asciicast version {version}"));
+        }
+        let width = header["width"].as_i64().unwrap_or(80).clamp(1, 1000) as i32;
+        let height = header["height"].as_i64().unwrap_or(25).clamp(1, 1000) as i32;
+        let title = header["title"].as_str().unwrap_or_default().to_string();
+        let idle_time_limit = header["idle_time_limit"].as_f64();
+
+        let mut events = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let event: serde_json::Value = match serde_json::from_str(line) {
+                Ok(event) => event,
+                Err(err) => {
+                    log::error!("Skipping invalid asciicast event in line {}: {err}", i + 2);
+                    continue;
+                }
+            };
+            let (Some(time), Some(code), Some(data)) = (event[0].as_f64(), event[1].as_str(), event[2].as_str()) else {
+                continue;
+            };
+            // only output events change the screen, input & marker events are ignored
+            if code == "o" {
+                events.push((time, data.to_string()));
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            title,
+            idle_time_limit,
+            events,
+        })
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.events.last().map(|(t, _)| *t).unwrap_or_default()
+    }
+
+    /// Replays the output events through the ansi parser and snapshots the screen.
+    /// Returns the frames together with the frame delay in ms.
+    pub fn replay(&self, mode: SnapshotMode, idle_time_limit: Option<f64>) -> TerminalResult<Vec<(Buffer, u32)>> {
+        let mut buffer = Buffer::new(Size::new(self.width, self.height));
+        buffer.is_terminal_buffer = true;
+        let mut caret = Caret::default();
+        let mut parser = ansi::Parser::default();
+        let converter = CP437Converter::default();
+
+        // (time of snapshot, screen)
+        let mut snapshots: Vec<(f64, Buffer)> = Vec::new();
+        let mut last_time = 0.0;
+        let mut play_time = 0.0;
+        let mut next_step = 0.0;
+
+        for (i, (time, data)) in self.events.iter().enumerate() {
+            let mut delta = (time - last_time).max(0.0);
+            if let Some(limit) = idle_time_limit {
+                delta = delta.min(limit);
+            }
+            last_time = *time;
+            play_time += delta;
+
+            if let SnapshotMode::TimeStep(step) = mode {
+                let step = f64::from(step.max(1)) / 1000.0;
+                while next_step + step <= play_time {
+                    next_step += step;
+                    // most steps don't change the screen, only clone the ones that do
+                    if snapshots.last().map_or(true, |(_, last)| !is_same_screen(last, &buffer)) {
+                        snapshots.push((next_step, buffer.clone()));
+                    }
+                }
+            }
+
+            for ch in data.chars() {
+                let ch = if (ch as u32) < 0x80 { ch } else { converter.convert_from_unicode(ch, 0) };
+                if let Err(err) = parser.print_char(&mut buffer, 0, &mut caret, ch) {
+                    log::error!("Error replaying asciicast: {err}");
+                }
+            }
+
+            if let SnapshotMode::EventBurst(gap) = mode {
+                let gap = f64::from(gap) / 1000.0;
+                let burst_ends = match self.events.get(i + 1) {
+                    Some((next_time, _)) => next_time - time >= gap,
+                    None => true,
+                };
+                if burst_ends {
+                    snapshots.push((play_time, buffer.clone()));
+                }
+            }
+        }
+        if matches!(mode, SnapshotMode::TimeStep(_)) {
+            snapshots.push((play_time, buffer));
+        }
+
+        // merge unchanged screens into one frame and convert the snapshot times to delays
+        let mut frames: Vec<(Buffer, u32, f64)> = Vec::new();
+        for (time, screen) in snapshots {
+            if let Some(last) = frames.last() {
+                if is_same_screen(&last.0, &screen) {
+                    continue;
+                }
+            }
+            frames.push((screen, 0, time));
+        }
+        for i in 0..frames.len() {
+            let end = if i + 1 < frames.len() { frames[i + 1].2 } else { frames[i].2 + 1.0 };
+            frames[i].1 = ((end - frames[i].2) * 1000.0).round().max(1.0) as u32;
+        }
+        if frames.is_empty() {
+            return Err(anyhow::anyhow!("recording contains no output"));
+        }
+        Ok(frames.into_iter().map(|(buf, delay, _)| (buf, delay)).collect())
+    }
+}
+
+fn is_same_screen(a: &Buffer, b: &Buffer) -> bool {
+    for y in 0..a.get_height() {
+        for x in 0..a.get_width() {
+            if a.get_char((x, y)) != b.get_char((x, y)) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Stores every frame as a layer of a single buffer, the animation script switches the visible layer.
+pub fn frames_to_buffer(frames: &[(Buffer, u32)]) -> Buffer {
+    let Some((first, _)) = frames.first() else {
+        return Buffer::new(Size::new(80, 25));
+    };
+    let mut result = Buffer::new(first.get_size());
+    result.is_terminal_buffer = false;
+    result.layers.clear();
+    for (i, (frame, delay)) in frames.iter().enumerate() {
+        let mut layer = Layer::new(format!("Frame {} ({delay}ms)", i + 1), frame.get_size());
+        layer.properties.is_visible = i == 0;
+        for y in 0..frame.get_height() {
+            for x in 0..frame.get_width() {
+                layer.set_char((x, y), frame.get_char((x, y)));
+            }
+        }
+        result.layers.push(layer);
+    }
+    // the parser only appends colors, so the last frame contains the whole palette
+    if let Some((last, _)) = frames.last() {
+        result.palette = last.palette.clone();
+    }
+    result
+}
+
+/// Generates an animation script playing the frames stored in `frame_file` (see `frames_to_buffer`).
+pub fn create_animation_script(frame_file: &str, frames: &[(Buffer, u32)]) -> String {
+    let delays = frames.iter().map(|(_, delay)| delay.to_string()).collect::<Vec<String>>().join(", ");
+    format!(
+        r#"-- imported from an asciicast recording, each frame is a layer in "{frame_file}"
+local buf = load_buffer("{frame_file}")
+local delays = {{ {delays} }}
+
+for i=0,buf.layer_count - 1 do
+    for j=0,buf.layer_count - 1 do
+        buf:set_layer_visible(j, i == j)
+    end
+    set_delay(delays[i + 1])
+    next_frame(buf)
+end"#
+    )
+}
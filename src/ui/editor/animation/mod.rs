@@ -16,6 +16,8 @@ use icy_engine::{ascii, AttributedChar, Buffer, EngineResult, Size, TextAttribut
 use icy_engine_gui::{animations::Animator, show_terminal_area, BufferView, MonitorSettings};
 
 use self::encoding::{start_encoding_thread, ENCODERS};
+mod asciicast_decoder;
+pub use asciicast_decoder::*;
 mod asciicast_encoder;
 mod encoding;
 mod gif_encoder;
@@ -14,7 +14,7 @@ use eframe::{
 use i18n_embed_fl::fl;
 use icy_engine::{
     attribute,
-    editor::{AtomicUndoGuard, UndoState},
+    editor::{AtomicUndoGuard, EditState, UndoState},
     util::{pop_data, pop_sixel_image, push_data, BUFFER_DATA},
     AttributedChar, Buffer, EngineResult, Line, Position, Rectangle, SaveOptions, TextAttribute, TextPane,
 };
@@ -27,6 +27,12 @@ use crate::{
     ClipboardHandler, Commands, Document, DocumentOptions, Message, SavingError, TerminalResult, UndoHandler, SETTINGS,
 };
 
+mod symmetry;
+mod tile;
+mod transform;
+pub use symmetry::*;
+pub use tile::*;
+
 pub enum Event {
     None,
     CursorPositionChange(Position, Position),
@@ -54,6 +60,14 @@ pub struct AnsiEditor {
     //pub attr_changed: std::boxed::Box<dyn Fn(TextAttribute)>
     pub request_focus: bool,
     pub color_mode: ColorMode,
+    pub symmetry: Symmetry,
+    /// Tools wrap around the canvas edges & the canvas is shown repeated around itself
+    pub tile_mode: bool,
+    tile_preview: Option<TilePreview>,
+    /// Increased on every edit, undo and redo - the undo stack length alone repeats after an undo followed by an edit.
+    change_count: usize,
+    /// Length of the undo stack when the change count was last updated.
+    last_undo_len: usize,
 }
 
 impl UndoHandler for AnsiEditor {
@@ -67,6 +81,7 @@ impl UndoHandler for AnsiEditor {
 
     fn undo(&mut self) -> EngineResult<Option<Message>> {
         self.buffer_view.lock().get_edit_state_mut().undo()?;
+        self.change_count += 1;
         Ok(None)
     }
 
@@ -80,6 +95,7 @@ impl UndoHandler for AnsiEditor {
 
     fn redo(&mut self) -> EngineResult<Option<Message>> {
         self.buffer_view.lock().get_edit_state_mut().redo()?;
+        self.change_count += 1;
         Ok(None)
     }
 }
@@ -149,6 +165,9 @@ impl Document for AnsiEditor {
         } else {
             ICED_EXT.to_string()
         };
+        if crate::util::petscii::is_c64_extension(&ext) {
+            return crate::util::petscii::save_c64_file(&ext, self.buffer_view.lock().get_buffer());
+        }
         let mut options = SaveOptions::new();
         options.compress = false;
         options.lossles_output = true;
@@ -204,7 +223,11 @@ impl Document for AnsiEditor {
         if let Some(response_opt) = response_opt {
             response = response_opt.response;
         }
-        self.handle_response(ui, response, calc, cur_tool, &mut message);
+        if !self.handle_symmetry_drag(ui, &response, &calc) {
+            self.handle_response(ui, response, calc.clone(), cur_tool, &mut message);
+        }
+        self.paint_tile_preview(ui, &calc);
+        self.paint_symmetry_axis(ui, &calc);
 
         message
     }
@@ -244,7 +267,22 @@ impl AnsiEditor {
             last_selected_tool: 0,
             request_focus: false,
             color_mode: ColorMode::Both,
+            symmetry: Symmetry::default(),
+            tile_mode: false,
+            tile_preview: None,
+            change_count: 0,
+            last_undo_len: 0,
+        }
+    }
+
+    /// Changes whenever the buffer may have changed, new undo operations are picked up when this is called.
+    pub fn get_change_count(&mut self) -> usize {
+        let len = self.buffer_view.lock().get_edit_state().undo_stack_len();
+        if len != self.last_undo_len {
+            self.last_undo_len = len;
+            self.change_count += 1;
         }
+        self.change_count
     }
 
     pub fn get_cur_layer_index(&self) -> TerminalResult<usize> {
@@ -441,7 +479,16 @@ impl AnsiEditor {
     }
 
     pub fn set_char(&mut self, pos: impl Into<Position>, attributed_char: AttributedChar) {
-        let _ = self.buffer_view.lock().get_edit_state_mut().set_char(pos, attributed_char);
+        let cells = self.get_symmetric_chars(pos.into(), attributed_char);
+        let cells: Vec<_> = if self.tile_mode {
+            cells.into_iter().map(|(pos, ch)| (self.wrap_position(pos), ch)).collect()
+        } else {
+            cells
+        };
+        let mut lock = self.buffer_view.lock();
+        for (pos, ch) in cells {
+            let _ = lock.get_edit_state_mut().set_char(pos, ch);
+        }
     }
 
     #[must_use]
@@ -449,6 +496,37 @@ impl AnsiEditor {
         self.buffer_view.lock().get_edit_state_mut().begin_atomic_undo(description.into())
     }
 
+    /// Writes chars to any layer as one undo operation, unchanged cells are skipped.
+    pub fn set_layer_chars(
+        &mut self,
+        description: impl Into<String>,
+        cells: impl IntoIterator<Item = (usize, Position, AttributedChar)>,
+    ) -> TerminalResult<()> {
+        let _undo = self.begin_atomic_undo(description);
+        let mut lock = self.buffer_view.lock();
+        Self::set_layer_chars_in(lock.get_edit_state_mut(), cells)
+    }
+
+    /// [`Self::set_layer_chars`] for edits that are part of a larger undo operation.
+    /// The current layer is restored even if writing a char fails.
+    pub fn set_layer_chars_in(edit_state: &mut EditState, cells: impl IntoIterator<Item = (usize, Position, AttributedChar)>) -> TerminalResult<()> {
+        let cur_layer = edit_state.get_current_layer()?;
+        let result = cells.into_iter().try_for_each(|(layer, pos, ch)| -> TerminalResult<()> {
+            if edit_state.get_buffer().layers[layer].get_char(pos) == ch {
+                return Ok(());
+            }
+            // set_char writes to the current layer
+            if edit_state.get_current_layer()? != layer {
+                edit_state.set_current_layer(layer);
+            }
+            edit_state.set_char(pos, ch)?;
+            Ok(())
+        });
+        edit_state.set_current_layer(cur_layer);
+        edit_state.set_is_buffer_dirty();
+        result
+    }
+
     pub fn fill(&mut self, rect: Rectangle, dos_char: AttributedChar) {
         let mut pos = rect.start;
         let _undo = self.begin_atomic_undo("Fill");
@@ -802,6 +880,7 @@ pub fn terminal_context_menu(editor: &AnsiEditor, commands: &Commands, ui: &mut
         commands.erase_selection.ui(ui, &mut result);
         commands.flip_x.ui(ui, &mut result);
         commands.flip_y.ui(ui, &mut result);
+        commands.rotate_90.ui(ui, &mut result);
         commands.justifycenter.ui(ui, &mut result);
         commands.justifyleft.ui(ui, &mut result);
         commands.justifyright.ui(ui, &mut result);
@@ -0,0 +1,337 @@
+use eframe::{
+    egui::{self, CursorIcon, PointerButton, Response},
+    epaint::{Color32, Pos2, Stroke, Vec2},
+};
+use icy_engine::{AttributedChar, Position, Size, TextPane};
+use icy_engine_gui::TerminalCalc;
+
+use super::AnsiEditor;
+use crate::{util::glyph_map::GlyphMapping, SETTINGS};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SymmetryMode {
+    #[default]
+    Off,
+    /// Mirrors left/right on a vertical axis
+    Horizontal,
+    /// Mirrors top/bottom on a horizontal axis
+    Vertical,
+    Quad,
+    Radial(usize),
+}
+
+impl SymmetryMode {
+    pub fn is_active(&self) -> bool {
+        !matches!(self, SymmetryMode::Off)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Symmetry {
+    pub mode: SymmetryMode,
+    /// Mode restored by the mirror mode toggle
+    pub last_mode: SymmetryMode,
+    /// Axis position in buffer cells, `None` is the buffer center.
+    pub center: Option<Vec2>,
+    dragging: bool,
+}
+
+impl Default for Symmetry {
+    fn default() -> Self {
+        Self {
+            mode: SymmetryMode::Off,
+            last_mode: SymmetryMode::Horizontal,
+            center: None,
+            dragging: false,
+        }
+    }
+}
+
+impl Symmetry {
+    pub fn set_mode(&mut self, mode: SymmetryMode) {
+        if mode.is_active() {
+            self.last_mode = mode;
+        }
+        self.mode = mode;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.mode.is_active() {
+            self.mode = SymmetryMode::Off;
+        } else {
+            self.mode = self.last_mode;
+        }
+    }
+
+    /// The cells written for a char set at `pos`, `offset` is the offset of the layer `pos` is relative to.
+    /// The char itself is the last entry so it wins over mirrored copies landing on the same cell.
+    pub fn get_symmetric_chars(
+        &self,
+        center: Vec2,
+        offset: Position,
+        font_size: Size,
+        mapping: Option<&GlyphMapping>,
+        pos: Position,
+        ch: AttributedChar,
+    ) -> Vec<(Position, AttributedChar)> {
+        let mode = self.mode;
+        if !mode.is_active() {
+            return vec![(pos, ch)];
+        }
+        let abs = pos + offset;
+        let mirror_x = |x: i32| (2.0 * center.x - 1.0 - x as f32).round() as i32;
+        let mirror_y = |y: i32| (2.0 * center.y - 1.0 - y as f32).round() as i32;
+        let glyph = |f: &dyn Fn(&GlyphMapping, char) -> char| {
+            let mut result = ch;
+            if let Some(mapping) = mapping {
+                if ch.is_visible() {
+                    result.ch = f(mapping, ch.ch);
+                }
+            }
+            result
+        };
+
+        let mut result = Vec::new();
+        match mode {
+            SymmetryMode::Off => {}
+            SymmetryMode::Horizontal => {
+                result.push((Position::new(mirror_x(abs.x), abs.y), glyph(&|m, c| m.mirror_x_char(c))));
+            }
+            SymmetryMode::Vertical => {
+                result.push((Position::new(abs.x, mirror_y(abs.y)), glyph(&|m, c| m.mirror_y_char(c))));
+            }
+            SymmetryMode::Quad => {
+                result.push((Position::new(mirror_x(abs.x), abs.y), glyph(&|m, c| m.mirror_x_char(c))));
+                result.push((Position::new(abs.x, mirror_y(abs.y)), glyph(&|m, c| m.mirror_y_char(c))));
+                result.push((
+                    Position::new(mirror_x(abs.x), mirror_y(abs.y)),
+                    glyph(&|m, c| m.mirror_y_char(m.mirror_x_char(c))),
+                ));
+            }
+            SymmetryMode::Radial(n) => {
+                let n = n.max(2);
+                // rotate in pixel space so the copies keep their shape with non square cells
+                let fw = font_size.width as f32;
+                let fh = font_size.height as f32;
+                let px = (abs.x as f32 + 0.5 - center.x) * fw;
+                let py = (abs.y as f32 + 0.5 - center.y) * fh;
+                for k in 1..n {
+                    let angle = std::f32::consts::TAU * k as f32 / n as f32;
+                    let (sin, cos) = angle.sin_cos();
+                    let nx = px * cos - py * sin;
+                    let ny = px * sin + py * cos;
+                    let target = Position::new((nx / fw + center.x).floor() as i32, (ny / fh + center.y).floor() as i32);
+                    // glyphs can only follow rotations by whole quarter turns
+                    let ch = if (4 * k) % n == 0 {
+                        let turns = 4 * k / n;
+                        glyph(&|m, c| m.rotate_char(c, turns))
+                    } else {
+                        ch
+                    };
+                    result.push((target, ch));
+                }
+            }
+        }
+
+        let mut cells: Vec<(Position, AttributedChar)> = Vec::new();
+        for (p, ch) in result {
+            let p = p - offset;
+            if p != pos && !cells.iter().any(|(q, _)| *q == p) {
+                cells.push((p, ch));
+            }
+        }
+        cells.push((pos, ch));
+        cells
+    }
+}
+
+const AXIS_GRAB_DISTANCE: f32 = 5.0;
+
+impl AnsiEditor {
+    pub fn get_symmetry_center(&self) -> Vec2 {
+        if let Some(center) = self.symmetry.center {
+            return center;
+        }
+        let lock = self.buffer_view.lock();
+        Vec2::new(lock.get_buffer().get_width() as f32 / 2.0, lock.get_buffer().get_height() as f32 / 2.0)
+    }
+
+    /// The cells the symmetry mode writes for a char set at `pos` (current layer coordinates).
+    pub(crate) fn get_symmetric_chars(&self, pos: Position, ch: AttributedChar) -> Vec<(Position, AttributedChar)> {
+        if !self.symmetry.mode.is_active() {
+            return vec![(pos, ch)];
+        }
+        let center = self.get_symmetry_center();
+        let lock = self.buffer_view.lock();
+        let offset = lock.get_edit_state().get_cur_layer().map_or(Position::default(), |layer| layer.get_offset());
+        let mapping = GlyphMapping::for_buffer(lock.get_buffer());
+        self.symmetry
+            .get_symmetric_chars(center, offset, lock.get_buffer().get_font_dimensions(), mapping.as_ref(), pos, ch)
+    }
+
+    fn symmetry_cell_to_screen(calc: &TerminalCalc, font_size: Vec2, cell: Vec2) -> Pos2 {
+        let scale = calc.char_size / font_size;
+        calc.buffer_rect.min + (cell * font_size - calc.char_scroll_position) * scale
+    }
+
+    fn symmetry_screen_to_cell(calc: &TerminalCalc, font_size: Vec2, pos: Pos2) -> Vec2 {
+        let scale = calc.char_size / font_size;
+        ((pos - calc.buffer_rect.min) / scale + calc.char_scroll_position) / font_size
+    }
+
+    fn get_font_size_vec(&self) -> Vec2 {
+        let dim = self.buffer_view.lock().get_buffer().get_font_dimensions();
+        Vec2::new(dim.width as f32, dim.height as f32)
+    }
+
+    fn symmetry_axis_hit(&self, calc: &TerminalCalc, pos: Pos2) -> bool {
+        let center = Self::symmetry_cell_to_screen(calc, self.get_font_size_vec(), self.get_symmetry_center());
+        match self.symmetry.mode {
+            SymmetryMode::Off => false,
+            SymmetryMode::Horizontal => (pos.x - center.x).abs() < AXIS_GRAB_DISTANCE,
+            SymmetryMode::Vertical => (pos.y - center.y).abs() < AXIS_GRAB_DISTANCE,
+            SymmetryMode::Quad => (pos.x - center.x).abs() < AXIS_GRAB_DISTANCE || (pos.y - center.y).abs() < AXIS_GRAB_DISTANCE,
+            SymmetryMode::Radial(_) => pos.distance(center) < 2.0 * AXIS_GRAB_DISTANCE,
+        }
+    }
+
+    /// Drags the symmetry axis, returns true if the pointer input is consumed by the axis.
+    pub(crate) fn handle_symmetry_drag(&mut self, ui: &egui::Ui, response: &Response, calc: &TerminalCalc) -> bool {
+        if !self.symmetry.mode.is_active() {
+            self.symmetry.dragging = false;
+            return false;
+        }
+
+        if response.drag_started_by(PointerButton::Primary) {
+            if let Some(pos) = ui.input(|i| i.pointer.press_origin()) {
+                self.symmetry.dragging = self.symmetry_axis_hit(calc, pos);
+            }
+        }
+
+        if self.symmetry.dragging {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let cell = Self::symmetry_screen_to_cell(calc, self.get_font_size_vec(), pos);
+                let mut center = self.get_symmetry_center();
+                // axis snaps to cell borders & cell centers
+                let snap = |v: f32| (v * 2.0).round() / 2.0;
+                if !matches!(self.symmetry.mode, SymmetryMode::Vertical) {
+                    center.x = snap(cell.x);
+                }
+                if !matches!(self.symmetry.mode, SymmetryMode::Horizontal) {
+                    center.y = snap(cell.y);
+                }
+                self.symmetry.center = Some(center);
+            }
+            if !response.dragged_by(PointerButton::Primary) {
+                self.symmetry.dragging = false;
+            }
+            ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+            return true;
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            if self.symmetry_axis_hit(calc, pos) {
+                ui.ctx().set_cursor_icon(CursorIcon::Grab);
+            }
+        }
+        false
+    }
+
+    pub(crate) fn paint_symmetry_axis(&self, ui: &egui::Ui, calc: &TerminalCalc) {
+        let mode = self.symmetry.mode;
+        if !mode.is_active() {
+            return;
+        }
+        let (r, g, b) = unsafe { SETTINGS.marker_settings.guide_color.get_rgb() };
+        let stroke = Stroke::new(1.5, Color32::from_rgb(r, g, b));
+        let painter = ui.painter_at(calc.terminal_rect);
+        let font_size = self.get_font_size_vec();
+        let center = Self::symmetry_cell_to_screen(calc, font_size, self.get_symmetry_center());
+        let rect = calc.buffer_rect;
+
+        if matches!(mode, SymmetryMode::Horizontal | SymmetryMode::Quad) {
+            painter.line_segment([Pos2::new(center.x, rect.top()), Pos2::new(center.x, rect.bottom())], stroke);
+        }
+        if matches!(mode, SymmetryMode::Vertical | SymmetryMode::Quad) {
+            painter.line_segment([Pos2::new(rect.left(), center.y), Pos2::new(rect.right(), center.y)], stroke);
+        }
+        if let SymmetryMode::Radial(n) = mode {
+            let n = n.max(2);
+            let radius = rect.width().min(rect.height()) / 4.0;
+            for k in 0..n {
+                let angle = std::f32::consts::TAU * k as f32 / n as f32 - std::f32::consts::FRAC_PI_2;
+                let end = center + Vec2::new(angle.cos(), angle.sin()) * radius;
+                painter.line_segment([center, end], stroke);
+            }
+        }
+        painter.circle_stroke(center, AXIS_GRAB_DISTANCE, stroke);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::TextAttribute;
+
+    use super::*;
+
+    const CENTER: Vec2 = Vec2::new(40.0, 12.0);
+
+    fn symmetry(mode: SymmetryMode) -> Symmetry {
+        let mut result = Symmetry::default();
+        result.set_mode(mode);
+        result
+    }
+
+    fn chars(mode: SymmetryMode, offset: Position, pos: Position, ch: char) -> Vec<(Position, char)> {
+        let mapping = GlyphMapping::cp437();
+        symmetry(mode)
+            .get_symmetric_chars(
+                CENTER,
+                offset,
+                Size::new(8, 16),
+                Some(&mapping),
+                pos,
+                AttributedChar::new(ch, TextAttribute::default()),
+            )
+            .into_iter()
+            .map(|(p, ch)| (p, ch.ch))
+            .collect()
+    }
+
+    #[test]
+    fn test_off_returns_the_char() {
+        assert_eq!(
+            chars(SymmetryMode::Off, Position::default(), Position::new(10, 5), 'A'),
+            vec![(Position::new(10, 5), 'A')]
+        );
+    }
+
+    #[test]
+    fn test_horizontal_mirrors_glyph() {
+        assert_eq!(
+            chars(SymmetryMode::Horizontal, Position::default(), Position::new(10, 5), '\u{DD}'),
+            vec![(Position::new(69, 5), '\u{DE}'), (Position::new(10, 5), '\u{DD}')]
+        );
+    }
+
+    #[test]
+    fn test_quad_with_layer_offset() {
+        assert_eq!(
+            chars(SymmetryMode::Quad, Position::new(2, 1), Position::new(8, 4), '\u{DA}'),
+            vec![
+                (Position::new(67, 4), '\u{BF}'),
+                (Position::new(8, 17), '\u{C0}'),
+                (Position::new(67, 17), '\u{D9}'),
+                (Position::new(8, 4), '\u{DA}'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_radial_half_turn_matches_point_mirror() {
+        assert_eq!(
+            chars(SymmetryMode::Radial(2), Position::default(), Position::new(10, 5), '\u{DA}'),
+            vec![(Position::new(69, 18), '\u{D9}'), (Position::new(10, 5), '\u{DA}')]
+        );
+    }
+}
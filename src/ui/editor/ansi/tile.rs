@@ -0,0 +1,116 @@
+use eframe::{
+    egui::{self, TextureHandle},
+    epaint::{Color32, Pos2, Rect, Vec2},
+};
+use i18n_embed_fl::fl;
+use icy_engine::{Position, TextPane};
+use icy_engine_gui::TerminalCalc;
+
+use super::AnsiEditor;
+use crate::{create_image, TerminalResult};
+
+/// Rendered canvas used for the tile preview, updated when the buffer or the palette changes.
+pub struct TilePreview {
+    change_count: usize,
+    palette_hash: u32,
+    texture: TextureHandle,
+}
+
+impl AnsiEditor {
+    /// Wraps a position of the current layer around the edges of the buffer, the tile preview repeats the whole buffer.
+    pub fn wrap_position(&self, pos: Position) -> Position {
+        let lock = self.buffer_view.lock();
+        let size = lock.get_buffer().get_size();
+        let offset = lock.get_edit_state().get_cur_layer().map_or(Position::default(), |layer| layer.get_offset());
+        Position::new(
+            (pos.x + offset.x).rem_euclid(size.width.max(1)) - offset.x,
+            (pos.y + offset.y).rem_euclid(size.height.max(1)) - offset.y,
+        )
+    }
+
+    /// Shifts the selection or the current layer by half its size, moves the tile seams to the center.
+    pub fn offset_by_half(&mut self) -> TerminalResult<()> {
+        let layer = self.get_cur_layer_index()?;
+        let cells = {
+            let lock = self.buffer_view.lock();
+            let Some(cur_layer) = lock.get_edit_state().get_cur_layer() else {
+                return Ok(());
+            };
+            let layer_size = cur_layer.get_size();
+            // the selection is in buffer coordinates, only the part on the layer is shifted
+            let (start, end) = if let Some(selection) = lock.get_selection() {
+                let rect = selection.as_rectangle();
+                let offset = cur_layer.get_offset();
+                (
+                    Position::new((rect.start.x - offset.x).max(0), (rect.start.y - offset.y).max(0)),
+                    Position::new(
+                        (rect.start.x - offset.x + rect.size.width).min(layer_size.width),
+                        (rect.start.y - offset.y + rect.size.height).min(layer_size.height),
+                    ),
+                )
+            } else {
+                (Position::default(), Position::new(layer_size.width, layer_size.height))
+            };
+            let (width, height) = (end.x - start.x, end.y - start.y);
+            let mut cells = Vec::new();
+            for y in 0..height {
+                for x in 0..width {
+                    let src = Position::new(start.x + (x + width / 2) % width, start.y + (y + height / 2) % height);
+                    cells.push((layer, Position::new(start.x + x, start.y + y), cur_layer.get_char(src)));
+                }
+            }
+            cells
+        };
+        self.set_layer_chars(fl!(crate::LANGUAGE_LOADER, "menu-offset_by_half"), cells)
+    }
+
+    /// Paints the canvas repeated around itself, the neighbors are dimmed to keep the real canvas visible.
+    pub(crate) fn paint_tile_preview(&mut self, ui: &egui::Ui, calc: &TerminalCalc) {
+        if !self.tile_mode {
+            self.tile_preview = None;
+            return;
+        }
+        let change_count = self.get_change_count();
+        let palette_hash = self.buffer_view.lock().get_buffer_mut().palette.get_checksum();
+        let needs_update = self
+            .tile_preview
+            .as_ref()
+            .map_or(true, |preview| preview.change_count != change_count || preview.palette_hash != palette_hash);
+        if needs_update {
+            let texture = create_image(ui.ctx(), self.buffer_view.lock().get_buffer());
+            self.tile_preview = Some(TilePreview {
+                change_count,
+                palette_hash,
+                texture,
+            });
+        }
+        let Some(preview) = &self.tile_preview else {
+            return;
+        };
+
+        let (width, height, font_size) = {
+            let lock = self.buffer_view.lock();
+            let dim = lock.get_buffer().get_font_dimensions();
+            (
+                lock.get_buffer().get_width(),
+                lock.get_buffer().get_height(),
+                Vec2::new(dim.width as f32, dim.height as f32),
+            )
+        };
+        let scale = calc.char_size / font_size;
+        let origin = calc.buffer_rect.min - calc.char_scroll_position * scale;
+        let size = Vec2::new(width as f32 * calc.char_size.x, height as f32 * calc.char_size.y);
+        let painter = ui.painter_at(calc.terminal_rect);
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        let tint = Color32::from_gray(140);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let rect = Rect::from_min_size(origin + Vec2::new(dx as f32 * size.x, dy as f32 * size.y), size);
+                painter.image(preview.texture.id(), rect, uv, tint);
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use i18n_embed_fl::fl;
+use icy_engine::{AttributedChar, Position, Rectangle, TextPane};
+
+use super::AnsiEditor;
+use crate::{util::glyph_map::GlyphMapping, TerminalResult};
+
+/// Cells of the transformed area, `None` for cells outside of the selection.
+struct TransformArea {
+    rect: Rectangle,
+    whole_layer: bool,
+    cells: Vec<Option<AttributedChar>>,
+}
+
+impl TransformArea {
+    fn get(&self, x: i32, y: i32) -> Option<AttributedChar> {
+        self.cells[(y * self.rect.size.width + x) as usize]
+    }
+}
+
+impl AnsiEditor {
+    pub fn get_glyph_mapping(&self) -> Option<GlyphMapping> {
+        GlyphMapping::for_buffer(self.buffer_view.lock().get_buffer())
+    }
+
+    /// The selected cells of the current layer or the whole layer if nothing is selected.
+    fn get_transform_area(&self) -> TerminalResult<TransformArea> {
+        let lock = self.buffer_view.lock();
+        let edit_state = lock.get_edit_state();
+        let Some(layer) = edit_state.get_cur_layer() else {
+            return Err(anyhow::anyhow!("no current layer"));
+        };
+
+        if !edit_state.is_something_selected() {
+            let rect = Rectangle::from(0, 0, layer.get_width(), layer.get_height());
+            let mut cells = Vec::new();
+            for y in 0..layer.get_height() {
+                for x in 0..layer.get_width() {
+                    cells.push(Some(layer.get_char((x, y))));
+                }
+            }
+            return Ok(TransformArea {
+                rect,
+                whole_layer: true,
+                cells,
+            });
+        }
+
+        let offset = layer.get_offset();
+        let mut min = Position::new(i32::MAX, i32::MAX);
+        let mut max = Position::new(i32::MIN, i32::MIN);
+        for y in 0..layer.get_height() {
+            for x in 0..layer.get_width() {
+                if edit_state.get_is_selected(Position::new(x, y) + offset) {
+                    min = Position::new(min.x.min(x), min.y.min(y));
+                    max = Position::new(max.x.max(x), max.y.max(y));
+                }
+            }
+        }
+        if min.x > max.x {
+            return Ok(TransformArea {
+                rect: Rectangle::from(0, 0, 0, 0),
+                whole_layer: false,
+                cells: Vec::new(),
+            });
+        }
+        let rect = Rectangle::from(min.x, min.y, max.x - min.x + 1, max.y - min.y + 1);
+        let mut cells = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let pos = Position::new(x, y);
+                cells.push(edit_state.get_is_selected(pos + offset).then(|| layer.get_char(pos)));
+            }
+        }
+        Ok(TransformArea {
+            rect,
+            whole_layer: false,
+            cells,
+        })
+    }
+
+    /// Transformations write the cells as they are, the symmetry mode must not apply.
+    fn set_transformed_char(&mut self, pos: Position, ch: AttributedChar) {
+        let _ = self.buffer_view.lock().get_edit_state_mut().set_char(pos, ch);
+    }
+
+    fn run_transform(&mut self, description: String, f: impl FnOnce(&mut Self) -> TerminalResult<()>) -> TerminalResult<()> {
+        let _undo = self.begin_atomic_undo(description);
+        let result = f(self);
+        self.buffer_view.lock().get_edit_state_mut().set_is_buffer_dirty();
+        result
+    }
+
+    /// Mirrors the selection or the current layer, directional glyphs are swapped with the glyph mapping of the font.
+    pub fn flip_glyphs(&mut self, horizontal: bool) -> TerminalResult<()> {
+        let area = self.get_transform_area()?;
+        let mapping = self.get_glyph_mapping();
+        let description = if horizontal {
+            fl!(crate::LANGUAGE_LOADER, "menu-flipx")
+        } else {
+            fl!(crate::LANGUAGE_LOADER, "menu-flipy")
+        };
+
+        self.run_transform(description, |editor| {
+            let width = area.rect.size.width;
+            let height = area.rect.size.height;
+            for y in 0..height {
+                for x in 0..width {
+                    let Some(ch) = area.get(x, y) else {
+                        continue;
+                    };
+                    let (tx, ty) = if horizontal { (width - 1 - x, y) } else { (x, height - 1 - y) };
+                    if area.get(tx, ty).is_none() {
+                        continue;
+                    }
+                    let mut ch = ch;
+                    if let Some(mapping) = &mapping {
+                        if ch.is_visible() {
+                            ch.ch = if horizontal {
+                                mapping.mirror_x_char(ch.ch)
+                            } else {
+                                mapping.mirror_y_char(ch.ch)
+                            };
+                        }
+                    }
+                    editor.set_transformed_char(area.rect.start + Position::new(tx, ty), ch);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Rotates the selection or the current layer clockwise, the rotated selection stays centered on the old one.
+    pub fn rotate_glyphs(&mut self, quarter_turns: usize) -> TerminalResult<()> {
+        let turns = quarter_turns % 4;
+        if turns == 0 {
+            return Ok(());
+        }
+        let area = self.get_transform_area()?;
+        let mapping = self.get_glyph_mapping();
+        let description = match turns {
+            1 => fl!(crate::LANGUAGE_LOADER, "menu-rotate90"),
+            2 => fl!(crate::LANGUAGE_LOADER, "menu-rotate180"),
+            _ => fl!(crate::LANGUAGE_LOADER, "menu-rotate270"),
+        };
+
+        self.run_transform(description, |editor| {
+            let width = area.rect.size.width;
+            let height = area.rect.size.height;
+            let (new_width, new_height) = if turns == 2 { (width, height) } else { (height, width) };
+            let start = if area.whole_layer {
+                Position::default()
+            } else {
+                area.rect.start + Position::new((width - new_width) / 2, (height - new_height) / 2)
+            };
+
+            if turns != 2 {
+                if area.whole_layer {
+                    let layer = editor.get_cur_layer_index()?;
+                    editor.buffer_view.lock().get_edit_state_mut().set_layer_size(layer, (new_width, new_height))?;
+                } else {
+                    for y in 0..height {
+                        for x in 0..width {
+                            if area.get(x, y).is_some() {
+                                editor.set_transformed_char(area.rect.start + Position::new(x, y), AttributedChar::invisible());
+                            }
+                        }
+                    }
+                }
+            }
+
+            for y in 0..height {
+                for x in 0..width {
+                    let Some(mut ch) = area.get(x, y) else {
+                        continue;
+                    };
+                    let (tx, ty) = match turns {
+                        1 => (height - 1 - y, x),
+                        2 => (width - 1 - x, height - 1 - y),
+                        _ => (y, width - 1 - x),
+                    };
+                    if turns == 2 && area.get(tx, ty).is_none() {
+                        continue;
+                    }
+                    if let Some(mapping) = &mapping {
+                        if ch.is_visible() {
+                            ch.ch = mapping.rotate_char(ch.ch, turns);
+                        }
+                    }
+                    editor.set_transformed_char(start + Position::new(tx, ty), ch);
+                }
+            }
+
+            if !area.whole_layer && turns != 2 {
+                let offset = editor
+                    .buffer_view
+                    .lock()
+                    .get_edit_state()
+                    .get_cur_layer()
+                    .map_or(Position::default(), |layer| layer.get_offset());
+                editor
+                    .buffer_view
+                    .lock()
+                    .set_selection(Rectangle::from(start.x + offset.x, start.y + offset.y, new_width, new_height));
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use i18n_embed_fl::fl;
+use icy_engine::{BitFont, BufferType};
+
+use crate::util::unicode::{from_unicode, to_unicode};
+
+/// Code page used to map the glyphs of a font to unicode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodePage {
+    Cp437,
+    Petscii,
+    Atascii,
+    Viewdata,
+}
+
+impl CodePage {
+    pub const ALL: [CodePage; 4] = [CodePage::Cp437, CodePage::Petscii, CodePage::Atascii, CodePage::Viewdata];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CodePage::Cp437 => "CP437",
+            CodePage::Petscii => "PETSCII",
+            CodePage::Atascii => "ATASCII",
+            CodePage::Viewdata => "Viewdata",
+        }
+    }
+
+    pub fn buffer_type(self) -> BufferType {
+        match self {
+            CodePage::Cp437 => BufferType::CP437,
+            CodePage::Petscii => BufferType::Petscii,
+            CodePage::Atascii => BufferType::Atascii,
+            CodePage::Viewdata => BufferType::Viewdata,
+        }
+    }
+
+    pub fn glyph_to_unicode(self, ch: char) -> char {
+        to_unicode(self.buffer_type(), ch)
+    }
+
+    pub fn unicode_to_glyph(self, ch: char) -> char {
+        from_unicode(self.buffer_type(), ch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    Latin,
+    Punctuation,
+    Accented,
+    BoxDrawing,
+    BlockElements,
+    Symbols,
+}
+
+impl CharClass {
+    pub const ALL: [CharClass; 6] = [
+        CharClass::Latin,
+        CharClass::Punctuation,
+        CharClass::Accented,
+        CharClass::BoxDrawing,
+        CharClass::BlockElements,
+        CharClass::Symbols,
+    ];
+
+    /// Returns None for chars that are blank by design (spaces, control chars).
+    pub fn of(ch: char) -> Option<CharClass> {
+        if ch.is_whitespace() || ch.is_control() || ch == '\u{A0}' {
+            return None;
+        }
+        Some(match ch {
+            '\u{2500}'..='\u{257F}' => CharClass::BoxDrawing,
+            '\u{2580}'..='\u{259F}' => CharClass::BlockElements,
+            _ if ch.is_ascii_alphanumeric() => CharClass::Latin,
+            _ if ch.is_ascii_punctuation() => CharClass::Punctuation,
+            _ if ch.is_alphabetic() => CharClass::Accented,
+            _ => CharClass::Symbols,
+        })
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            CharClass::Latin => fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-latin"),
+            CharClass::Punctuation => fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-punctuation"),
+            CharClass::Accented => fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-accented"),
+            CharClass::BoxDrawing => fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-box_drawing"),
+            CharClass::BlockElements => fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-block_elements"),
+            CharClass::Symbols => fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-symbols"),
+        }
+    }
+}
+
+pub struct CoverageGroup {
+    pub class: CharClass,
+    pub covered: usize,
+    /// (glyph, unicode char) of the glyphs that are still blank
+    pub missing: Vec<(char, char)>,
+}
+
+impl CoverageGroup {
+    pub fn total(&self) -> usize {
+        self.covered + self.missing.len()
+    }
+}
+
+/// Maps all glyphs of the font to unicode and checks which ones are blank but shouldn't be.
+pub fn font_coverage(font: &BitFont, code_page: CodePage) -> Vec<CoverageGroup> {
+    let mut groups: Vec<CoverageGroup> = CharClass::ALL
+        .iter()
+        .map(|class| CoverageGroup {
+            class: *class,
+            covered: 0,
+            missing: Vec::new(),
+        })
+        .collect();
+
+    for code in 0..font.length {
+        let ch = unsafe { char::from_u32_unchecked(code as u32) };
+        let unicode = code_page.glyph_to_unicode(ch);
+        let Some(class) = CharClass::of(unicode) else {
+            continue;
+        };
+        let Some(group) = groups.iter_mut().find(|g| g.class == class) else {
+            continue;
+        };
+        let is_blank = font.get_glyph(ch).map_or(true, |g| g.data.iter().all(|row| *row == 0));
+        if is_blank {
+            group.missing.push((ch, unicode));
+        } else {
+            group.covered += 1;
+        }
+    }
+    groups.retain(|g| g.total() > 0);
+    groups
+}
@@ -0,0 +1,233 @@
+use std::io::Cursor;
+
+use icy_engine::BitFont;
+
+use crate::TerminalResult;
+
+/// Formats the bit font editor can export to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitFontFormat {
+    Psf1,
+    Psf2,
+    Bdf,
+    Png,
+}
+
+impl BitFontFormat {
+    pub const ALL: [BitFontFormat; 4] = [BitFontFormat::Psf1, BitFontFormat::Psf2, BitFontFormat::Bdf, BitFontFormat::Png];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            BitFontFormat::Psf1 | BitFontFormat::Psf2 => "psf",
+            BitFontFormat::Bdf => "bdf",
+            BitFontFormat::Png => "png",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BitFontFormat::Psf1 => "PSF1",
+            BitFontFormat::Psf2 => "PSF2",
+            BitFontFormat::Bdf => "BDF",
+            BitFontFormat::Png => "PNG",
+        }
+    }
+
+    pub fn to_bytes(self, font: &BitFont) -> TerminalResult<Vec<u8>> {
+        match self {
+            BitFontFormat::Psf1 => to_psf1_bytes(font),
+            BitFontFormat::Psf2 => font.to_psf2_bytes(),
+            BitFontFormat::Bdf => Ok(to_bdf(font).into_bytes()),
+            BitFontFormat::Png => to_png_sheet(font, PNG_SHEET_COLUMNS),
+        }
+    }
+}
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE512: u8 = 0x01;
+
+/// Glyphs per row in exported PNG sheets, matches the layout of the char table.
+pub const PNG_SHEET_COLUMNS: i32 = 16;
+
+fn glyph_rows(font: &BitFont, code: i32) -> Vec<u8> {
+    let ch = unsafe { char::from_u32_unchecked(code as u32) };
+    let mut data = font.get_glyph(ch).map(|g| g.data.clone()).unwrap_or_default();
+    data.resize(font.size.height as usize, 0);
+    data
+}
+
+/// PSF1 is the old linux console format (setfont), it only knows 8 pixel wide fonts with 256 or 512 glyphs.
+/// Spec: <https://www.win.tue.nl/~aeb/linux/kbd/font-formats-1.html>
+pub fn to_psf1_bytes(font: &BitFont) -> TerminalResult<Vec<u8>> {
+    if font.size.width != 8 {
+        return Err(anyhow::anyhow!("PSF1 fonts can't be {} pixels wide", font.size.width));
+    }
+    if !(1..=255).contains(&font.size.height) {
+        return Err(anyhow::anyhow!("PSF1 fonts can't be {} pixels high", font.size.height));
+    }
+    let length = if font.length > 256 { 512 } else { 256 };
+    let mut result = PSF1_MAGIC.to_vec();
+    result.push(if length == 512 { PSF1_MODE512 } else { 0 });
+    result.push(font.size.height as u8);
+    for code in 0..length {
+        result.extend(glyph_rows(font, code));
+    }
+    Ok(result)
+}
+
+/// Glyph bitmap distribution format (X11), the baseline is assumed at 3/4 of the font height.
+/// Spec: <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5005.BDF_Spec.pdf>
+pub fn to_bdf(font: &BitFont) -> String {
+    let width = font.size.width;
+    let height = font.size.height;
+    let descent = height / 4;
+    let ascent = height - descent;
+    let name = if font.name.is_empty() { "icy_draw" } else { font.name.as_str() };
+    let name = name.replace(['-', ' '], "_");
+
+    let mut result = String::new();
+    result.push_str("STARTFONT 2.1\n");
+    result.push_str(&format!(
+        "FONT -IcyDraw-{name}-Medium-R-Normal--{height}-{}-75-75-C-{}-IBM-437\n",
+        height * 10,
+        width * 10
+    ));
+    result.push_str(&format!("SIZE {height} 75 75\n"));
+    result.push_str(&format!("FONTBOUNDINGBOX {width} {height} 0 {}\n", -descent));
+    result.push_str("STARTPROPERTIES 4\n");
+    result.push_str(&format!("FONT_ASCENT {ascent}\n"));
+    result.push_str(&format!("FONT_DESCENT {descent}\n"));
+    result.push_str("CHARSET_REGISTRY \"IBM\"\n");
+    result.push_str("CHARSET_ENCODING \"437\"\n");
+    result.push_str("ENDPROPERTIES\n");
+    result.push_str(&format!("CHARS {}\n", font.length));
+    for code in 0..font.length {
+        result.push_str(&format!("STARTCHAR char{code}\n"));
+        result.push_str(&format!("ENCODING {code}\n"));
+        result.push_str(&format!("SWIDTH {} 0\n", width * 1000 / height.max(1)));
+        result.push_str(&format!("DWIDTH {width} 0\n"));
+        result.push_str(&format!("BBX {width} {height} 0 {}\n", -descent));
+        result.push_str("BITMAP\n");
+        for row in glyph_rows(font, code) {
+            result.push_str(&format!("{row:02X}\n"));
+        }
+        result.push_str("ENDCHAR\n");
+    }
+    result.push_str("ENDFONT\n");
+    result
+}
+
+/// Renders all glyphs white on black into a sprite sheet, each glyph occupies one cell of font size.
+pub fn to_png_sheet(font: &BitFont, columns: i32) -> TerminalResult<Vec<u8>> {
+    let width = font.size.width;
+    let height = font.size.height;
+    let rows = (font.length + columns - 1) / columns;
+    let mut img = image::GrayImage::new((columns * width) as u32, (rows * height) as u32);
+    for code in 0..font.length {
+        let cell_x = (code % columns) * width;
+        let cell_y = (code / columns) * height;
+        for (y, row) in glyph_rows(font, code).iter().enumerate() {
+            for x in 0..width {
+                if row & (128 >> x) != 0 {
+                    img.put_pixel((cell_x + x) as u32, (cell_y + y as i32) as u32, image::Luma([255]));
+                }
+            }
+        }
+    }
+    let mut result = Cursor::new(Vec::new());
+    img.write_to(&mut result, image::ImageOutputFormat::Png)?;
+    Ok(result.into_inner())
+}
+
+/// Describes where the glyphs are inside a PNG glyph sheet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PngSheetSettings {
+    /// Size of a grid cell, the glyph is read from the top left corner of the cell
+    pub cell_width: i32,
+    pub cell_height: i32,
+    /// Position of the first cell
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// Pixels with a luminance >= threshold are set
+    pub threshold: u8,
+    /// For dark glyphs on a bright background
+    pub invert: bool,
+}
+
+impl PngSheetSettings {
+    pub fn new(font: &BitFont) -> Self {
+        Self {
+            cell_width: font.size.width,
+            cell_height: font.size.height,
+            offset_x: 0,
+            offset_y: 0,
+            threshold: 128,
+            invert: false,
+        }
+    }
+}
+
+/// Reads the glyphs from a PNG glyph sheet, cells are numbered left to right, top to bottom.
+/// Returns the rows of each glyph found in the sheet, at most `font.length` glyphs are read.
+pub fn import_png_sheet(font: &BitFont, data: &[u8], settings: &PngSheetSettings) -> TerminalResult<Vec<(char, Vec<u8>)>> {
+    let img = image::load_from_memory(data)?.to_luma8();
+    let cell_width = settings.cell_width.max(1);
+    let cell_height = settings.cell_height.max(1);
+    let columns = (img.width() as i32 - settings.offset_x) / cell_width;
+    let rows = (img.height() as i32 - settings.offset_y) / cell_height;
+    if columns <= 0 || rows <= 0 {
+        return Err(anyhow::anyhow!("image is smaller than a glyph cell"));
+    }
+
+    let glyph_width = font.size.width.min(cell_width).min(8);
+    let glyph_height = font.size.height.min(cell_height);
+    let mut result = Vec::new();
+    for code in 0..font.length.min(columns * rows) {
+        let cell_x = settings.offset_x + (code % columns) * cell_width;
+        let cell_y = settings.offset_y + (code / columns) * cell_height;
+        let mut glyph = vec![0; font.size.height as usize];
+        for y in 0..glyph_height {
+            for x in 0..glyph_width {
+                let luma = img.get_pixel((cell_x + x) as u32, (cell_y + y) as u32).0[0];
+                if (luma >= settings.threshold) != settings.invert {
+                    glyph[y as usize] |= 128 >> x;
+                }
+            }
+        }
+        result.push((unsafe { char::from_u32_unchecked(code as u32) }, glyph));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::BitFont;
+
+    use super::to_psf1_bytes;
+
+    #[test]
+    fn test_psf1_header() {
+        let font = BitFont::default();
+        let data = to_psf1_bytes(&font).unwrap();
+        assert_eq!(&data[0..2], &super::PSF1_MAGIC);
+        assert_eq!(data[2], 0);
+        assert_eq!(data[3] as i32, font.size.height);
+        assert_eq!(data.len(), 4 + 256 * font.size.height as usize);
+    }
+
+    #[test]
+    fn test_psf1_glyph_data() {
+        let font = BitFont::default();
+        let data = to_psf1_bytes(&font).unwrap();
+        let height = font.size.height as usize;
+        let glyph = font.get_glyph('A').unwrap();
+        assert_eq!(&data[4 + 65 * height..4 + 66 * height], &glyph.data[..height]);
+    }
+
+    #[test]
+    fn test_psf1_rejects_wide_fonts() {
+        let mut font = BitFont::default();
+        font.size.width = 9;
+        assert!(to_psf1_bytes(&font).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use i18n_embed_fl::fl;
+use icy_engine::Size;
+
+/// Drawing tools of the glyph edit area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphTool {
+    Pencil,
+    Line,
+    Rectangle,
+    FilledRectangle,
+    Fill,
+}
+
+impl GlyphTool {
+    pub const ALL: [GlyphTool; 5] = [
+        GlyphTool::Pencil,
+        GlyphTool::Line,
+        GlyphTool::Rectangle,
+        GlyphTool::FilledRectangle,
+        GlyphTool::Fill,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            GlyphTool::Pencil => fl!(crate::LANGUAGE_LOADER, "font-editor-tool-pencil"),
+            GlyphTool::Line => fl!(crate::LANGUAGE_LOADER, "font-editor-tool-line"),
+            GlyphTool::Rectangle => fl!(crate::LANGUAGE_LOADER, "font-editor-tool-rectangle"),
+            GlyphTool::FilledRectangle => fl!(crate::LANGUAGE_LOADER, "font-editor-tool-filled_rectangle"),
+            GlyphTool::Fill => fl!(crate::LANGUAGE_LOADER, "font-editor-tool-fill"),
+        }
+    }
+}
+
+/// Operations that can be applied to a set of glyphs at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphTransform {
+    Clear,
+    Inverse,
+    Left,
+    Right,
+    Up,
+    Down,
+    FlipX,
+    FlipY,
+    Bold,
+    Italic,
+    Underline,
+}
+
+impl GlyphTransform {
+    pub fn get_description(self) -> String {
+        match self {
+            GlyphTransform::Clear => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-clear"),
+            GlyphTransform::Inverse => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-inverse"),
+            GlyphTransform::Left => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-left"),
+            GlyphTransform::Right => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-right"),
+            GlyphTransform::Up => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-up"),
+            GlyphTransform::Down => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-down"),
+            GlyphTransform::FlipX => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-flip-x"),
+            GlyphTransform::FlipY => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-flip-y"),
+            GlyphTransform::Bold => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-bold"),
+            GlyphTransform::Italic => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-italic"),
+            GlyphTransform::Underline => fl!(crate::LANGUAGE_LOADER, "undo-bitfont-underline"),
+        }
+    }
+
+    pub fn apply(self, data: &mut Vec<u8>, size: Size) {
+        let mask = row_mask(size.width);
+        match self {
+            GlyphTransform::Clear => data.fill(0),
+            GlyphTransform::Inverse => data.iter_mut().for_each(|row| *row ^= 0xFF),
+            GlyphTransform::Left => data.iter_mut().for_each(|row| *row <<= 1),
+            GlyphTransform::Right => data.iter_mut().for_each(|row| *row >>= 1),
+            GlyphTransform::Up => {
+                if !data.is_empty() {
+                    data.remove(0);
+                    data.push(0);
+                }
+            }
+            GlyphTransform::Down => {
+                if !data.is_empty() {
+                    data.insert(0, 0);
+                    data.pop();
+                }
+            }
+            GlyphTransform::FlipX => {
+                let w = 8 - size.width;
+                data.iter_mut().for_each(|row| *row = (*row & mask).reverse_bits() << w);
+            }
+            GlyphTransform::FlipY => data.reverse(),
+            GlyphTransform::Bold => data.iter_mut().for_each(|row| *row |= *row >> 1),
+            GlyphTransform::Italic => {
+                // slant the upper half one pixel to the right
+                let height = data.len();
+                for (y, row) in data.iter_mut().enumerate() {
+                    let shift = (height - 1 - y) * 2 / height.max(1);
+                    *row >>= shift;
+                }
+            }
+            GlyphTransform::Underline => {
+                if let Some(row) = data.last_mut() {
+                    *row = 0xFF;
+                }
+            }
+        }
+        data.iter_mut().for_each(|row| *row &= mask);
+    }
+}
+
+/// The bits of a glyph row that are inside the glyph width.
+fn row_mask(width: i32) -> u8 {
+    (0xFF_u16 << (8 - width.clamp(0, 8))) as u8
+}
+
+pub fn get_pixel(data: &[u8], x: i32, y: i32) -> bool {
+    if !(0..8).contains(&x) || y < 0 {
+        return false;
+    }
+    data.get(y as usize).map_or(false, |row| row & (128 >> x) != 0)
+}
+
+pub fn set_pixel(data: &mut [u8], size: Size, x: i32, y: i32, value: bool) {
+    if x < 0 || y < 0 || x >= size.width.min(8) || y >= size.height {
+        return;
+    }
+    if let Some(row) = data.get_mut(y as usize) {
+        if value {
+            *row |= 128 >> x;
+        } else {
+            *row &= !(128 >> x);
+        }
+    }
+}
+
+pub fn draw_line(data: &mut [u8], size: Size, from: (i32, i32), to: (i32, i32), value: bool) {
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let sx = if x < to.0 { 1 } else { -1 };
+    let sy = if y < to.1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    loop {
+        set_pixel(data, size, x, y, value);
+        if x == to.0 && y == to.1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+pub fn draw_rectangle(data: &mut [u8], size: Size, from: (i32, i32), to: (i32, i32), filled: bool, value: bool) {
+    let (x1, x2) = (from.0.min(to.0), from.0.max(to.0));
+    let (y1, y2) = (from.1.min(to.1), from.1.max(to.1));
+    for y in y1..=y2 {
+        for x in x1..=x2 {
+            if filled || x == x1 || x == x2 || y == y1 || y == y2 {
+                set_pixel(data, size, x, y, value);
+            }
+        }
+    }
+}
+
+/// 4-connected flood fill starting at (x, y).
+pub fn flood_fill(data: &mut [u8], size: Size, x: i32, y: i32, value: bool) {
+    let width = size.width.min(8);
+    if x < 0 || y < 0 || x >= width || y >= size.height || get_pixel(data, x, y) == value {
+        return;
+    }
+    let mut stack = vec![(x, y)];
+    while let Some((x, y)) = stack.pop() {
+        if x < 0 || y < 0 || x >= width || y >= size.height || get_pixel(data, x, y) == value {
+            continue;
+        }
+        set_pixel(data, size, x, y, value);
+        stack.extend([(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]);
+    }
+}
@@ -1,3 +1,7 @@
+mod coverage;
+mod formats;
+mod glyph_ops;
+mod scaling;
 mod undo;
 
 use std::{path::Path, sync::Arc};
@@ -7,16 +11,69 @@ use eframe::{
     emath::Align2,
     epaint::{mutex::Mutex, Color32, FontFamily, FontId, Pos2, Rect, Rounding, Vec2},
 };
+use egui_file::FileDialog;
 use i18n_embed_fl::fl;
 use icy_engine::{
     util::{pop_data, push_data, BITFONT_GLYPH},
-    BitFont, Buffer, EngineResult, Glyph, Size, TextAttribute, TextPane,
+    AttributedChar, BitFont, Buffer, EngineResult, Glyph, Size, TextAttribute, TextPane,
 };
 use icy_engine_gui::{show_terminal_area, BufferView};
 
 use crate::{model::Tool, to_message, AnsiEditor, ClipboardHandler, Document, DocumentOptions, Message, TerminalResult, UndoHandler, SETTINGS};
 
-use self::undo::UndoOperation;
+use self::{
+    coverage::{font_coverage, CodePage, CoverageGroup},
+    formats::{import_png_sheet, BitFontFormat, PngSheetSettings},
+    glyph_ops::{draw_line, draw_rectangle, flood_fill, set_pixel, GlyphTool, GlyphTransform},
+    scaling::{lga_pixel, scale_font, ScaleMode, ScaledFontCache},
+    undo::UndoOperation,
+};
+
+/// Glyph ranges can't be stored in the glyph clipboard format, so they're kept here for all bit font editors.
+/// `first_glyph` is what got pushed to the clipboard, if the clipboard content changed the range is stale.
+#[derive(Clone)]
+struct GlyphRangeClipboard {
+    first_glyph: Vec<u8>,
+    glyphs: Vec<Vec<u8>>,
+}
+
+static GLYPH_RANGE_CLIPBOARD: std::sync::Mutex<Option<GlyphRangeClipboard>> = std::sync::Mutex::new(None);
+
+/// Remembers the glyphs of a multi glyph selection and returns the clipboard data of the first glyph.
+fn copy_glyphs(font: &BitFont, chars: &[char]) -> Option<Vec<u8>> {
+    let data = font.get_clipboard_data(*chars.first()?)?;
+    let range = if chars.len() > 1 {
+        let height = font.size.height as usize;
+        let glyphs = chars
+            .iter()
+            .map(|ch| font.get_glyph(*ch).map_or_else(|| vec![0; height], |g| g.data.clone()))
+            .collect();
+        Some(GlyphRangeClipboard {
+            first_glyph: data.clone(),
+            glyphs,
+        })
+    } else {
+        None
+    };
+    if let Ok(mut clipboard) = GLYPH_RANGE_CLIPBOARD.lock() {
+        *clipboard = range;
+    }
+    Some(data)
+}
+
+/// The copied range placed consecutively from `start`, None if `data` isn't the first glyph of the last copied range.
+fn paste_glyph_range(font: &BitFont, data: &[u8], start: char) -> Option<Vec<(char, Vec<u8>)>> {
+    let range = GLYPH_RANGE_CLIPBOARD.lock().ok()?.clone().filter(|r| r.first_glyph == data)?;
+    let glyphs = range
+        .glyphs
+        .into_iter()
+        .enumerate()
+        .map(|(i, data)| (start as u32 + i as u32, data))
+        .filter(|(code, _)| (*code as i32) < font.length)
+        .map(|(code, data)| (unsafe { char::from_u32_unchecked(code) }, data))
+        .collect();
+    Some(glyphs)
+}
 
 pub struct BitFontEditor {
     id: usize,
@@ -26,21 +83,42 @@ pub struct BitFontEditor {
 
     width: i32,
     height: i32,
+    scale_mode: ScaleMode,
+    scaled_preview: ScaledFontCache,
+    /// Increased on every change of the font.
+    change_count: usize,
+    lga_preview: bool,
 
     buffer_view: Arc<Mutex<BufferView>>,
 
     selected_char_opt: Option<char>,
+    /// Additional glyphs operations are applied to, empty if only `selected_char_opt` is selected
+    selected_glyphs: Vec<char>,
+    tool: GlyphTool,
+    drag_start: Option<(i32, i32)>,
     undo_stack: Arc<Mutex<Vec<Box<dyn UndoOperation>>>>,
     redo_stack: Vec<Box<dyn UndoOperation>>,
     old_data: Option<Vec<u8>>,
 
     send_update_message: bool,
+
+    png_settings: PngSheetSettings,
+    import_dialog: Option<FileDialog>,
+    export_dialog: Option<(FileDialog, BitFontFormat)>,
+
+    preview_view: Arc<Mutex<BufferView>>,
+    preview_text: String,
+    sample_dialog: Option<FileDialog>,
+    code_page: CodePage,
+    /// Coverage of the font, None if the font or the code page changed since it was calculated.
+    coverage: Option<Vec<CoverageGroup>>,
 }
 
 pub enum DrawGlyphStyle {
     Normal,
     Selected,
     GrayOut,
+    Marked,
 }
 
 impl BitFontEditor {
@@ -50,9 +128,15 @@ impl BitFontEditor {
         let mut buffer_view = BufferView::from_buffer(gl, buffer);
         buffer_view.interactive = false;
         let buffer_view = Arc::new(Mutex::new(buffer_view));
+
+        let mut preview_view = BufferView::from_buffer(gl, Buffer::new(Size::new(1, 1)));
+        preview_view.interactive = false;
+        let preview_view = Arc::new(Mutex::new(preview_view));
+
         let size = font.size;
         let last_updated_font = font.clone();
-        Self {
+        let png_settings = PngSheetSettings::new(&font);
+        let mut result = Self {
             id,
             buffer_view,
             original_font: font.clone(),
@@ -60,12 +144,29 @@ impl BitFontEditor {
             font,
             width: size.width,
             height: size.height,
+            scale_mode: ScaleMode::Crop,
+            scaled_preview: ScaledFontCache::default(),
+            change_count: 0,
+            lga_preview: true,
             selected_char_opt: Some('A'),
+            selected_glyphs: Vec::new(),
+            tool: GlyphTool::Pencil,
+            drag_start: None,
             undo_stack: Arc::new(Mutex::new(Vec::new())),
             redo_stack: Vec::new(),
             old_data: None,
             send_update_message: false,
-        }
+            png_settings,
+            import_dialog: None,
+            export_dialog: None,
+            preview_view,
+            preview_text: fl!(crate::LANGUAGE_LOADER, "font-editor-preview_sample_text"),
+            sample_dialog: None,
+            code_page: CodePage::Cp437,
+            coverage: None,
+        };
+        result.set_preview_text();
+        result
     }
 
     pub fn draw_glyph(ui: &mut egui::Ui, font: &BitFont, style: DrawGlyphStyle, ch: char) -> egui::Response {
@@ -77,12 +178,14 @@ impl BitFontEditor {
                 DrawGlyphStyle::Normal => Color32::LIGHT_GRAY,
                 DrawGlyphStyle::Selected => Color32::WHITE,
                 DrawGlyphStyle::GrayOut => Color32::GRAY,
+                DrawGlyphStyle::Marked => Color32::WHITE,
             }
         } else {
             match style {
                 DrawGlyphStyle::Normal => Color32::GRAY,
                 DrawGlyphStyle::Selected => Color32::YELLOW,
                 DrawGlyphStyle::GrayOut => Color32::DARK_GRAY,
+                DrawGlyphStyle::Marked => Color32::LIGHT_BLUE,
             }
         };
 
@@ -108,6 +211,54 @@ impl BitFontEditor {
         response
     }
 
+    /// Draws a few connected line drawing glyphs. With `lga` the glyphs are 9 pixels wide,
+    /// like VGA in 9 dot mode the 9th column repeats the 8th for 0xC0-0xDF and is blank otherwise.
+    pub fn draw_lga_preview(ui: &mut egui::Ui, font: &BitFont, lga: bool) -> egui::Response {
+        const SAMPLE: [[u8; 7]; 5] = [
+            [0xC9, 0xCD, 0xCB, 0xCD, 0xBB, 0xDA, 0xBF],
+            [0xBA, 0xB0, 0xBA, 0xB1, 0xBA, 0xC0, 0xD9],
+            [0xCC, 0xCD, 0xCE, 0xCD, 0xB9, 0xC4, 0xC5],
+            [0xBA, 0xDC, 0xBA, 0xDF, 0xBA, 0xB3, 0xDB],
+            [0xC8, 0xCD, 0xCA, 0xCD, 0xBC, 0xDD, 0xDE],
+        ];
+        let scale = 2.;
+        let s = font.size;
+        let cell_width = s.width + i32::from(lga);
+        let (id, rect) = ui.allocate_space(Vec2::new(
+            scale * (cell_width * SAMPLE[0].len() as i32) as f32,
+            scale * (s.height * SAMPLE.len() as i32) as f32,
+        ));
+        let response = ui.interact(rect, id, Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, Rounding::ZERO, Color32::BLACK);
+        for (row, line) in SAMPLE.iter().enumerate() {
+            for (col, code) in line.iter().enumerate() {
+                let Some(glyph) = font.get_glyph(char::from(*code)) else {
+                    continue;
+                };
+                for y in 0..s.height {
+                    let bits = glyph.data.get(y as usize).copied().unwrap_or_default();
+                    for x in 0..cell_width {
+                        if lga_pixel(u32::from(*code), bits, s.width, x) {
+                            painter.rect_filled(
+                                Rect::from_min_size(
+                                    Pos2::new(
+                                        rect.left() + ((col as i32 * cell_width + x) as f32) * scale,
+                                        rect.top() + ((row as i32 * s.height + y) as f32) * scale,
+                                    ),
+                                    Vec2::new(scale, scale),
+                                ),
+                                Rounding::ZERO,
+                                Color32::GRAY,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        response
+    }
+
     pub fn update_tile_area(&mut self) {
         let lock = &mut self.buffer_view.lock();
         let buf = lock.get_buffer_mut();
@@ -126,7 +277,41 @@ impl BitFontEditor {
             }
         }
         self.send_update_message = true;
+        self.change_count += 1;
+        self.coverage = None;
         lock.redraw_view();
+
+        let preview = &mut self.preview_view.lock();
+        preview.get_buffer_mut().set_font(0, self.font.clone());
+        preview.redraw_view();
+    }
+
+    fn set_preview_text(&mut self) {
+        let lines: Vec<&str> = self.preview_text.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or_default().clamp(1, 255);
+        let mut buffer = Buffer::new(Size::new(width as i32, lines.len().max(1) as i32));
+        buffer.is_terminal_buffer = false;
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().take(width).enumerate() {
+                let ch = self.code_page.unicode_to_glyph(ch);
+                buffer.layers[0].set_char((x as i32, y as i32), AttributedChar::new(ch, TextAttribute::default()));
+            }
+        }
+        self.set_preview_buffer(buffer);
+    }
+
+    fn load_preview_sample(&mut self, path: &Path) -> TerminalResult<()> {
+        let data = std::fs::read(path)?;
+        let buffer = Buffer::from_bytes(path, true, &data)?;
+        self.set_preview_buffer(buffer);
+        Ok(())
+    }
+
+    fn set_preview_buffer(&mut self, mut buffer: Buffer) {
+        buffer.set_font(0, self.font.clone());
+        let preview = &mut self.preview_view.lock();
+        *preview.get_buffer_mut() = buffer;
+        preview.redraw_view();
     }
 
     pub fn edit_glyph(&mut self) -> impl egui::Widget + '_ {
@@ -168,43 +353,45 @@ impl BitFontEditor {
                     }
                 }
             } else { */
+            let pixel_pos = |pos: Pos2| {
+                (
+                    ((pos.x - top_ruler - stroke_rect.left()) / (scale + border)) as i32,
+                    ((pos.y - left_ruler - stroke_rect.top()) / (scale + border)) as i32,
+                )
+            };
+
             if response.drag_started_by(egui::PointerButton::Primary) || response.drag_started_by(egui::PointerButton::Secondary) {
                 self.start_edit();
+                self.drag_start = response.hover_pos().map(pixel_pos);
             }
 
             if response.drag_released_by(egui::PointerButton::Primary) || response.drag_released_by(egui::PointerButton::Secondary) {
                 self.end_edit();
             }
 
-            if response.dragged_by(egui::PointerButton::Primary) {
-                if let Some(pos) = response.hover_pos() {
-                    if let Some(number) = self.selected_char_opt {
-                        if let Some(glyph) = self.font.get_glyph_mut(number) {
-                            let y = ((pos.y - left_ruler - stroke_rect.top()) / (scale + border)) as usize;
-                            let x = ((pos.x - top_ruler - stroke_rect.left()) / (scale + border)) as usize;
-                            if y < glyph.data.len() && x < 8 {
-                                glyph.data[y] |= 128 >> x;
-                                self.update_tile_area();
-                                response.mark_changed();
-                            }
-                        }
-                    }
+            let dragged = if response.dragged_by(egui::PointerButton::Primary) {
+                Some(true)
+            } else if response.dragged_by(egui::PointerButton::Secondary) {
+                Some(false)
+            } else {
+                None
+            };
+            if let (Some(value), Some(pos)) = (dragged, response.hover_pos()) {
+                if self.apply_tool(pixel_pos(pos), value) {
+                    self.update_tile_area();
+                    response.mark_changed();
                 }
             }
 
-            if response.dragged_by(egui::PointerButton::Secondary) {
+            if response.clicked_by(egui::PointerButton::Primary) || response.clicked_by(egui::PointerButton::Secondary) {
                 if let Some(pos) = response.hover_pos() {
-                    if let Some(number) = self.selected_char_opt {
-                        if let Some(glyph) = self.font.get_glyph_mut(number) {
-                            let y = ((pos.y - left_ruler - stroke_rect.top()) / (scale + border)) as usize;
-                            let x = ((pos.x - top_ruler - stroke_rect.left()) / (scale + border)) as usize;
-                            if y < glyph.data.len() && x < 8 {
-                                glyph.data[y] &= !(128 >> x);
-                                self.update_tile_area();
-                                response.mark_changed();
-                            }
-                        }
+                    self.start_edit();
+                    self.drag_start = Some(pixel_pos(pos));
+                    if self.apply_tool(pixel_pos(pos), response.clicked_by(egui::PointerButton::Primary)) {
+                        self.update_tile_area();
+                        response.mark_changed();
                     }
+                    self.end_edit();
                 }
             }
             if let Some(number) = self.selected_char_opt {
@@ -313,82 +500,259 @@ impl BitFontEditor {
         Ok(())
     }
 
-    fn clear_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::ClearGlyph::new(number);
-            self.push_undo(Box::new(op))?;
+    /// The glyphs operations are applied to.
+    fn selected_glyphs(&self) -> Vec<char> {
+        if self.selected_glyphs.is_empty() {
+            self.selected_char_opt.into_iter().collect()
+        } else {
+            self.selected_glyphs.clone()
         }
-        Ok(())
     }
 
-    fn inverse_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::InverseGlyph::new(number);
-            self.push_undo(Box::new(op))?;
+    /// Shift selects a range starting at the current glyph, ctrl adds/removes single glyphs.
+    fn select_glyph(&mut self, ch: char, modifiers: egui::Modifiers) {
+        if modifiers.shift {
+            if let Some(anchor) = self.selected_char_opt {
+                self.selected_glyphs = (anchor.min(ch)..=anchor.max(ch)).collect();
+                return;
+            }
         }
-        Ok(())
+        if modifiers.command {
+            let mut selection = self.selected_glyphs();
+            if let Some(i) = selection.iter().position(|c| *c == ch) {
+                selection.remove(i);
+                self.selected_glyphs = selection;
+                return;
+            }
+            selection.push(ch);
+            selection.sort_unstable();
+            self.selected_glyphs = selection;
+        } else {
+            self.selected_glyphs.clear();
+        }
+        self.selected_char_opt = Some(ch);
+        self.update_tile_area();
     }
 
-    fn left_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::LeftGlyph::new(number);
+    fn transform_selected_glyphs(&mut self, transform: GlyphTransform) -> EngineResult<()> {
+        let chars = self.selected_glyphs();
+        if !chars.is_empty() {
+            let op = undo::TransformGlyphs::new(chars, transform);
             self.push_undo(Box::new(op))?;
         }
         Ok(())
     }
 
-    fn right_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::RightGlyph::new(number);
-            self.push_undo(Box::new(op))?;
+    /// Applies the current tool to the edited glyph, shapes are drawn from the drag start to `pos`.
+    /// Returns true if the glyph changed.
+    fn apply_tool(&mut self, pos: (i32, i32), value: bool) -> bool {
+        let Some(number) = self.selected_char_opt else {
+            return false;
+        };
+        let size = self.font.size;
+        let tool = self.tool;
+        let start = self.drag_start.unwrap_or(pos);
+        let old_data = self.old_data.clone();
+        let Some(glyph) = self.font.get_glyph_mut(number) else {
+            return false;
+        };
+        let before = glyph.data.clone();
+        if tool != GlyphTool::Pencil {
+            if let Some(old_data) = old_data {
+                glyph.data = old_data;
+            }
         }
-        Ok(())
+        match tool {
+            GlyphTool::Pencil => set_pixel(&mut glyph.data, size, pos.0, pos.1, value),
+            GlyphTool::Line => draw_line(&mut glyph.data, size, start, pos, value),
+            GlyphTool::Rectangle => draw_rectangle(&mut glyph.data, size, start, pos, false, value),
+            GlyphTool::FilledRectangle => draw_rectangle(&mut glyph.data, size, start, pos, true, value),
+            GlyphTool::Fill => flood_fill(&mut glyph.data, size, start.0, start.1, value),
+        }
+        glyph.data != before
     }
 
-    fn up_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::UpGlyph::new(number);
-            self.push_undo(Box::new(op))?;
-        }
+    fn resize_font(&mut self) -> EngineResult<()> {
+        let old_font = self.font.clone();
+        let new_font = scale_font(&self.font, Size::new(self.width, self.height), self.scale_mode);
+
+        let op = undo::ResizeFont::new(old_font, new_font);
+        self.push_undo(Box::new(op))?;
         Ok(())
     }
 
-    fn down_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::DownGlyph::new(number);
-            self.push_undo(Box::new(op))?;
-        }
+    fn import_png(&mut self, path: &Path) -> TerminalResult<()> {
+        let data = std::fs::read(path)?;
+        let glyphs = import_png_sheet(&self.font, &data, &self.png_settings)?;
+        let op = undo::SetGlyphs::new(glyphs, fl!(crate::LANGUAGE_LOADER, "undo-bitfont-import"));
+        self.push_undo(Box::new(op))?;
         Ok(())
     }
 
-    fn flip_x_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::FlipX::new(number);
-            self.push_undo(Box::new(op))?;
-        }
+    fn export_font(&self, path: &Path, format: BitFontFormat) -> TerminalResult<()> {
+        let bytes = format.to_bytes(&self.font)?;
+        std::fs::write(path, bytes)?;
         Ok(())
     }
 
-    fn flip_y_selected_glyph(&mut self) -> EngineResult<()> {
-        if let Some(number) = self.selected_char_opt {
-            let op = undo::FlipY::new(number);
-            self.push_undo(Box::new(op))?;
+    fn show_file_dialogs(&mut self, ctx: &egui::Context) -> Option<Message> {
+        let mut message = None;
+        if let Some(mut dialog) = self.import_dialog.take() {
+            if dialog.show(ctx).selected() {
+                if let Some(path) = dialog.path() {
+                    if let Err(err) = self.import_png(path) {
+                        message = Some(Message::ShowError(format!("Could not import: {err}")));
+                    }
+                }
+            } else if dialog.visible() {
+                self.import_dialog = Some(dialog);
+            }
         }
-        Ok(())
-    }
 
-    fn resize_font(&mut self) -> EngineResult<()> {
-        let old_font = self.font.clone();
-        let mut new_font = self.font.clone();
+        if let Some(mut dialog) = self.sample_dialog.take() {
+            if dialog.show(ctx).selected() {
+                if let Some(path) = dialog.path() {
+                    if let Err(err) = self.load_preview_sample(path) {
+                        message = Some(Message::ShowError(format!("Could not load sample: {err}")));
+                    }
+                }
+            } else if dialog.visible() {
+                self.sample_dialog = Some(dialog);
+            }
+        }
 
-        for glyph in new_font.glyphs.values_mut() {
-            glyph.data.resize(self.height as usize, 0);
+        if let Some((mut dialog, format)) = self.export_dialog.take() {
+            if dialog.show(ctx).selected() {
+                if let Some(path) = dialog.path() {
+                    let path = path.with_extension(format.extension());
+                    if let Err(err) = self.export_font(&path, format) {
+                        message = Some(Message::ShowError(format!("Could not export: {err}")));
+                    }
+                }
+            } else if dialog.visible() {
+                self.export_dialog = Some((dialog, format));
+            }
         }
-        new_font.size = Size::new(self.width, self.height);
+        message
+    }
 
-        let op = undo::ResizeFont::new(old_font, new_font);
-        self.push_undo(Box::new(op))?;
-        Ok(())
+    fn show_import_export(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing(fl!(crate::LANGUAGE_LOADER, "font-editor-import_png"), |ui| {
+            egui::Grid::new("png_import_grid").num_columns(2).spacing([4.0, 8.0]).show(ui, |ui| {
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-cell_size"));
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.png_settings.cell_width).clamp_range(1..=64));
+                    ui.label("x");
+                    ui.add(egui::DragValue::new(&mut self.png_settings.cell_height).clamp_range(1..=64));
+                });
+                ui.end_row();
+
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-grid_offset"));
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.png_settings.offset_x).clamp_range(0..=1024));
+                    ui.label("x");
+                    ui.add(egui::DragValue::new(&mut self.png_settings.offset_y).clamp_range(0..=1024));
+                });
+                ui.end_row();
+
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-threshold"));
+                });
+                ui.add(egui::Slider::new(&mut self.png_settings.threshold, 1..=255));
+                ui.end_row();
+
+                ui.label("");
+                ui.checkbox(&mut self.png_settings.invert, fl!(crate::LANGUAGE_LOADER, "font-editor-invert"));
+                ui.end_row();
+            });
+
+            if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-import_png_button")).clicked() {
+                let mut dialog = FileDialog::open_file(None).show_files_filter(Box::new(|path| {
+                    path.extension().map_or(false, |ext| ext.to_string_lossy().eq_ignore_ascii_case("png"))
+                }));
+                dialog.open();
+                self.import_dialog = Some(dialog);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-export"));
+            for format in BitFontFormat::ALL {
+                if ui.button(format.label()).clicked() {
+                    let mut dialog = FileDialog::save_file(None);
+                    dialog.open();
+                    self.export_dialog = Some((dialog, format));
+                }
+            }
+        });
+    }
+
+    fn show_preview(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-preview"));
+            if ui.text_edit_singleline(&mut self.preview_text).changed() {
+                self.set_preview_text();
+            }
+            if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-preview_load_sample")).clicked() {
+                let mut dialog = FileDialog::open_file(None);
+                dialog.open();
+                self.sample_dialog = Some(dialog);
+            }
+        });
+        let opt = icy_engine_gui::TerminalOptions {
+            stick_to_bottom: false,
+            scale: Some(Vec2::new(1.0, 1.0)),
+            monitor_settings: unsafe { SETTINGS.monitor_settings.clone() },
+            marker_settings: unsafe { SETTINGS.marker_settings.clone() },
+            id: Some(Id::new(self.id + 20001)),
+            ..Default::default()
+        };
+        self.preview_view.lock().get_caret_mut().set_is_visible(false);
+        let (_, _) = show_terminal_area(ui, self.preview_view.clone(), opt);
+    }
+
+    fn show_coverage(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-coverage-code_page"));
+            let old_code_page = self.code_page;
+            egui::ComboBox::from_id_source("coverage_code_page")
+                .selected_text(self.code_page.label())
+                .show_ui(ui, |ui| {
+                    for code_page in CodePage::ALL {
+                        ui.selectable_value(&mut self.code_page, code_page, code_page.label());
+                    }
+                });
+            if old_code_page != self.code_page {
+                self.set_preview_text();
+                self.coverage = None;
+            }
+        });
+
+        let coverage = self.coverage.take().unwrap_or_else(|| font_coverage(&self.font, self.code_page));
+        for group in &coverage {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(fl!(
+                    crate::LANGUAGE_LOADER,
+                    "font-editor-coverage-group",
+                    name = group.class.label(),
+                    covered = group.covered,
+                    total = group.total()
+                ));
+                for (ch, unicode) in &group.missing {
+                    let response = ui
+                        .small_button(unicode.to_string())
+                        .on_hover_text(format!("{0}/0x{0:02X} U+{1:04X}", *ch as u32, *unicode as u32));
+                    if response.clicked() {
+                        self.select_glyph(*ch, egui::Modifiers::NONE);
+                    }
+                }
+            });
+        }
+        self.coverage = Some(coverage);
     }
 
     fn start_edit(&mut self) {
@@ -400,6 +764,7 @@ impl BitFontEditor {
     }
 
     fn end_edit(&mut self) {
+        self.drag_start = None;
         if self.old_data.is_none() {
             return;
         }
@@ -423,10 +788,8 @@ impl ClipboardHandler for BitFontEditor {
     }
 
     fn copy(&mut self) -> EngineResult<()> {
-        if let Some(ch) = self.selected_char_opt {
-            if let Some(data) = self.font.get_clipboard_data(ch) {
-                push_data(BITFONT_GLYPH, &data)?;
-            }
+        if let Some(data) = copy_glyphs(&self.font, &self.selected_glyphs()) {
+            push_data(BITFONT_GLYPH, &data)?;
         }
         Ok(())
     }
@@ -441,6 +804,14 @@ impl ClipboardHandler for BitFontEditor {
 
     fn paste(&mut self) -> EngineResult<()> {
         if let Some(data) = pop_data(BITFONT_GLYPH) {
+            if let Some(start) = self.selected_glyphs().first() {
+                // ranges are pasted consecutively starting at the first selected glyph
+                if let Some(glyphs) = paste_glyph_range(&self.font, &data, *start) {
+                    let op = undo::SetGlyphs::new(glyphs, fl!(crate::LANGUAGE_LOADER, "undo-paste-glyph"));
+                    self.push_undo(Box::new(op))?;
+                    return Ok(());
+                }
+            }
             let (_, g) = Glyph::from_clipbard_data(&data);
             if let Some(ch) = self.selected_char_opt {
                 let op = undo::Paste::new(ch, g);
@@ -484,6 +855,8 @@ impl UndoHandler for BitFontEditor {
         if let Some(mut op) = self.redo_stack.pop() {
             op.redo(self)?;
             self.undo_stack.lock().push(op);
+            self.change_count += 1;
+            self.coverage = None;
             return Ok(None);
         }
         self.update_tile_area();
@@ -502,7 +875,7 @@ impl Document for BitFontEditor {
     }
 
     fn show_ui(&mut self, ui: &mut eframe::egui::Ui, _cur_tool: &mut Box<dyn Tool>, _selected_tool: usize, _options: &DocumentOptions) -> Option<Message> {
-        let mut message = None;
+        let mut message = self.show_file_dialogs(ui.ctx());
         ui.add_space(16.);
         ui.vertical_centered(|ui| {
             ui.horizontal(|ui| {
@@ -511,12 +884,18 @@ impl Document for BitFontEditor {
 
                 ui.vertical(|ui| {
                     ui.add_space(20.);
+                    ui.horizontal(|ui| {
+                        for tool in GlyphTool::ALL {
+                            ui.selectable_value(&mut self.tool, tool, tool.label());
+                        }
+                    });
+                    ui.add_space(8.);
                     ui.horizontal(|ui| {
                         if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-clear")).clicked() {
-                            message = to_message(self.clear_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Clear));
                         }
                         if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-inverse")).clicked() {
-                            message = to_message(self.inverse_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Inverse));
                         }
                     });
                     ui.add_space(8.);
@@ -524,17 +903,17 @@ impl Document for BitFontEditor {
                         ui.add_space(14.);
 
                         if ui.button("⬆").clicked() {
-                            message = to_message(self.up_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Up));
                         }
                     });
 
                     ui.horizontal(|ui| {
                         if ui.button("⬅").clicked() {
-                            message = to_message(self.left_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Left));
                         }
 
                         if ui.button("➡").clicked() {
-                            message = to_message(self.right_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Right));
                         }
                     });
 
@@ -542,18 +921,30 @@ impl Document for BitFontEditor {
                         ui.add_space(14.);
 
                         if ui.button("⬇").clicked() {
-                            message = to_message(self.down_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Down));
                         }
                     });
                     ui.add_space(8.);
 
                     ui.horizontal(|ui| {
                         if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-flip_x")).clicked() {
-                            message = to_message(self.flip_x_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::FlipX));
                         }
 
                         if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-flip_y")).clicked() {
-                            message = to_message(self.flip_y_selected_glyph());
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::FlipY));
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-bold")).clicked() {
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Bold));
+                        }
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-italic")).clicked() {
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Italic));
+                        }
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "font-editor-underline")).clicked() {
+                            message = to_message(self.transform_selected_glyphs(GlyphTransform::Underline));
                         }
                     });
 
@@ -569,11 +960,39 @@ impl Document for BitFontEditor {
                         });
                         ui.add(egui::Slider::new(&mut self.height, 2..=19));
                         ui.end_row();
+
+                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-scale_mode"));
+                        });
+                        egui::ComboBox::from_id_source("bitfont_scale_mode")
+                            .selected_text(self.scale_mode.label())
+                            .show_ui(ui, |ui| {
+                                for mode in ScaleMode::ALL {
+                                    ui.selectable_value(&mut self.scale_mode, mode, mode.label());
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(&mut self.lga_preview, fl!(crate::LANGUAGE_LOADER, "font-editor-lga_preview"));
+                        ui.end_row();
                     });
 
-                    if (self.width != self.font.size.width || self.height != self.font.size.height) && ui.button("Resize").clicked() {
+                    let is_resized = self.width != self.font.size.width || self.height != self.font.size.height;
+                    if is_resized {
+                        let preview_font = self
+                            .scaled_preview
+                            .get(&self.font, self.change_count, Size::new(self.width, self.height), self.scale_mode);
+                        BitFontEditor::draw_lga_preview(ui, preview_font, self.lga_preview);
+                    } else {
+                        BitFontEditor::draw_lga_preview(ui, &self.font, self.lga_preview);
+                    }
+
+                    if is_resized && ui.button("Resize").clicked() {
                         message = to_message(self.resize_font());
                     }
+                    ui.add_space(8.);
+                    self.show_import_export(ui);
                 });
 
                 ui.vertical(|ui| {
@@ -596,12 +1015,27 @@ impl Document for BitFontEditor {
             });
         });
 
+        egui::TopBottomPanel::bottom("bitfont_preview_panel")
+            .resizable(true)
+            .default_height(200.0)
+            .show_inside(ui, |ui| {
+                egui::ScrollArea::vertical().id_source("bitfont_preview_scroll").show(ui, |ui| {
+                    self.show_preview(ui);
+                    ui.collapsing(fl!(crate::LANGUAGE_LOADER, "font-editor-coverage"), |ui| {
+                        self.show_coverage(ui);
+                    });
+                });
+            });
+
         ui.label(fl!(crate::LANGUAGE_LOADER, "font-editor-table", length = (self.font.length - 1).to_string()));
         egui::ScrollArea::vertical().show(ui, |ui| {
             ui.horizontal_wrapped(|ui| {
                 for i in 0..self.font.length {
                     let ch = unsafe { char::from_u32_unchecked(i as u32) };
                     let mut style = DrawGlyphStyle::Normal;
+                    if self.selected_glyphs.contains(&ch) {
+                        style = DrawGlyphStyle::Marked
+                    }
                     if let Some(ch2) = self.selected_char_opt {
                         if ch == ch2 {
                             style = DrawGlyphStyle::Selected
@@ -609,8 +1043,8 @@ impl Document for BitFontEditor {
                     }
                     let response = BitFontEditor::draw_glyph(ui, &self.font, style, ch);
                     if response.clicked() {
-                        self.selected_char_opt = Some(ch);
-                        self.update_tile_area();
+                        let modifiers = ui.input(|i| i.modifiers);
+                        self.select_glyph(ch, modifiers);
                     }
 
                     response.on_hover_ui(|ui| {
@@ -658,6 +1092,37 @@ impl Document for BitFontEditor {
 
     fn destroy(&self, gl: &glow::Context) -> Option<Message> {
         self.buffer_view.lock().destroy(gl);
+        self.preview_view.lock().destroy(gl);
         Some(Message::UpdateFont(Box::new((self.last_updated_font.clone(), self.original_font.clone()))))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::BitFont;
+
+    use super::{copy_glyphs, paste_glyph_range};
+
+    #[test]
+    fn test_paste_glyph_range_into_other_font() {
+        let source = BitFont::default();
+        let target = BitFont::default();
+        let data = copy_glyphs(&source, &['A', 'B', 'C']).unwrap();
+
+        let pasted = paste_glyph_range(&target, &data, 'a').unwrap();
+        let expected: Vec<(char, Vec<u8>)> = ['A', 'B', 'C']
+            .iter()
+            .zip(['a', 'b', 'c'])
+            .map(|(src, dst)| (dst, source.get_glyph(*src).unwrap().data.clone()))
+            .collect();
+        assert_eq!(pasted, expected);
+
+        // stops at the end of the font
+        let last = unsafe { char::from_u32_unchecked(target.length as u32 - 2) };
+        assert_eq!(paste_glyph_range(&target, &data, last).unwrap().len(), 2);
+
+        // a single glyph copy replaces the range
+        let data = copy_glyphs(&source, &['A']).unwrap();
+        assert!(paste_glyph_range(&target, &data, 'a').is_none());
+    }
+}
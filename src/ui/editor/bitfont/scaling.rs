@@ -0,0 +1,229 @@
+use i18n_embed_fl::fl;
+use icy_engine::{BitFont, Size};
+
+/// How glyphs are converted when the font size changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Rows and columns are cut off or padded
+    Crop,
+    /// Each target pixel takes the nearest source pixel
+    Nearest,
+    /// Like nearest, but when shrinking a target pixel is set if any of its source pixels is set, thin lines don't vanish
+    LinePreserving,
+    /// Line preserving, box drawing & block glyphs (0xB0-0xDF) drop or repeat rows/columns of their strokes so they stay connected
+    RowDropping,
+}
+
+impl ScaleMode {
+    pub const ALL: [ScaleMode; 4] = [ScaleMode::Crop, ScaleMode::Nearest, ScaleMode::LinePreserving, ScaleMode::RowDropping];
+
+    pub fn label(self) -> String {
+        match self {
+            ScaleMode::Crop => fl!(crate::LANGUAGE_LOADER, "font-editor-scale-crop"),
+            ScaleMode::Nearest => fl!(crate::LANGUAGE_LOADER, "font-editor-scale-nearest"),
+            ScaleMode::LinePreserving => fl!(crate::LANGUAGE_LOADER, "font-editor-scale-line_preserving"),
+            ScaleMode::RowDropping => fl!(crate::LANGUAGE_LOADER, "font-editor-scale-row_dropping"),
+        }
+    }
+}
+
+/// Shade glyphs (░▒▓) are dither patterns, they're tiled instead of scaled.
+const SHADES: std::ops::RangeInclusive<u32> = 0xB0..=0xB2;
+/// Line drawing and block glyphs that need to connect to their neighbours.
+const LINE_DRAWING: std::ops::RangeInclusive<u32> = 0xB3..=0xDF;
+
+/// Glyphs VGA in 9 dot mode extends by repeating the 8th column, the others get a blank 9th column.
+const LGA_REPEAT: std::ops::RangeInclusive<u32> = 0xC0..=0xDF;
+
+type Bitmap = Vec<Vec<bool>>;
+
+fn to_bitmap(data: &[u8], size: Size) -> Bitmap {
+    (0..size.height as usize)
+        .map(|y| {
+            let row = data.get(y).copied().unwrap_or_default();
+            (0..size.width.min(8)).map(|x| row & (128 >> x) != 0).collect()
+        })
+        .collect()
+}
+
+fn from_bitmap(bitmap: &Bitmap) -> Vec<u8> {
+    bitmap
+        .iter()
+        .map(|row| row.iter().enumerate().fold(0, |acc, (x, set)| if *set { acc | (128 >> x) } else { acc }))
+        .collect()
+}
+
+fn transpose(bitmap: &Bitmap, width: usize) -> Bitmap {
+    (0..width).map(|x| bitmap.iter().map(|row| row[x]).collect()).collect()
+}
+
+/// Source range [start, end) that is mapped to target index i.
+fn source_range(i: usize, from: usize, to: usize) -> (usize, usize) {
+    let start = i * from / to;
+    let end = ((i + 1) * from / to).max(start + 1);
+    (start, end.min(from))
+}
+
+fn scale_lines(lines: &[Vec<bool>], new_len: usize, pool: bool) -> Bitmap {
+    let len = lines.len();
+    if len == 0 {
+        return Vec::new();
+    }
+    (0..new_len)
+        .map(|i| {
+            if pool && new_len < len {
+                let (start, end) = source_range(i, len, new_len);
+                let mut line = lines[start].clone();
+                for src in &lines[start + 1..end] {
+                    line.iter_mut().zip(src).for_each(|(a, b)| *a |= *b);
+                }
+                line
+            } else {
+                lines[((2 * i + 1) * len / (2 * new_len)).min(len - 1)].clone()
+            }
+        })
+        .collect()
+}
+
+/// Removes or repeats lines inside the longest runs of identical lines.
+/// Strokes that run through the glyph are such runs, so edges & crossings stay intact.
+fn resize_runs(lines: &[Vec<bool>], new_len: usize) -> Bitmap {
+    let mut lines = lines.to_vec();
+    if lines.is_empty() {
+        return lines;
+    }
+    while lines.len() != new_len {
+        let mut best = (0, 1);
+        let mut start = 0;
+        for i in 1..=lines.len() {
+            if i == lines.len() || lines[i] != lines[start] {
+                if i - start > best.1 {
+                    best = (start, i - start);
+                }
+                start = i;
+            }
+        }
+        let middle = best.0 + best.1 / 2;
+        if lines.len() > new_len {
+            lines.remove(middle);
+        } else {
+            let line = lines[middle].clone();
+            lines.insert(middle, line);
+        }
+    }
+    lines
+}
+
+pub fn scale_glyph(code: u32, data: &[u8], from: Size, to: Size, mode: ScaleMode) -> Vec<u8> {
+    let from_width = from.width.min(8) as usize;
+    let to_width = to.width.min(8) as usize;
+    let to_height = to.height as usize;
+    let bitmap = to_bitmap(data, from);
+
+    let scaled = match mode {
+        ScaleMode::Crop => {
+            let mut result = data.to_vec();
+            result.resize(to_height, 0);
+            return result;
+        }
+        ScaleMode::Nearest | ScaleMode::LinePreserving => {
+            let pool = mode == ScaleMode::LinePreserving;
+            let rows = scale_lines(&bitmap, to_height, pool);
+            transpose(&scale_lines(&transpose(&rows, from_width), to_width, pool), to_height)
+        }
+        ScaleMode::RowDropping => {
+            if SHADES.contains(&code) {
+                (0..to_height)
+                    .map(|y| (0..to_width).map(|x| bitmap[y % bitmap.len()][x % from_width]).collect())
+                    .collect()
+            } else if LINE_DRAWING.contains(&code) {
+                let rows = resize_runs(&bitmap, to_height);
+                transpose(&resize_runs(&transpose(&rows, from_width), to_width), to_height)
+            } else {
+                let rows = scale_lines(&bitmap, to_height, true);
+                transpose(&scale_lines(&transpose(&rows, from_width), to_width, true), to_height)
+            }
+        }
+    };
+    from_bitmap(&scaled)
+}
+
+/// Converts all glyphs of the font to the new size.
+pub fn scale_font(font: &BitFont, size: Size, mode: ScaleMode) -> BitFont {
+    let mut result = font.clone();
+    if size.width < 1 || size.height < 1 || font.size.width < 1 || font.size.height < 1 {
+        return result;
+    }
+    for (ch, glyph) in &mut result.glyphs {
+        glyph.data = scale_glyph(*ch as u32, &glyph.data, font.size, size, mode);
+    }
+    result.size = size;
+    result
+}
+
+/// Pixel x of a glyph row in a LGA cell, x == width is the extra 9th column.
+pub fn lga_pixel(code: u32, bits: u8, width: i32, x: i32) -> bool {
+    if x < width {
+        bits & (128 >> x) != 0
+    } else {
+        LGA_REPEAT.contains(&code) && bits & (128 >> (width - 1)) != 0
+    }
+}
+
+/// Keeps the last scaled font, the preview would otherwise scale the whole font every frame.
+/// The entry is keyed on the change count of the editor, comparing the fonts is as slow as scaling them.
+#[derive(Default)]
+pub struct ScaledFontCache {
+    entry: Option<(usize, Size, ScaleMode, BitFont)>,
+}
+
+impl ScaledFontCache {
+    pub fn get(&mut self, font: &BitFont, change_count: usize, size: Size, mode: ScaleMode) -> &BitFont {
+        let is_valid = matches!(&self.entry, Some((c, s, m, _)) if *c == change_count && *s == size && *m == mode);
+        if !is_valid {
+            self.entry = Some((change_count, size, mode, scale_font(font, size, mode)));
+        }
+        &self.entry.as_ref().unwrap().3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::Size;
+
+    use super::{lga_pixel, scale_glyph, ScaleMode};
+
+    #[test]
+    fn test_crop_keeps_pixels_in_place() {
+        let data = [0x81, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x81];
+        let padded = scale_glyph(u32::from(b'X'), &data, Size::new(8, 8), Size::new(8, 10), ScaleMode::Crop);
+        assert_eq!(padded, vec![0x81, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x81, 0, 0]);
+
+        let cropped = scale_glyph(u32::from(b'X'), &data, Size::new(8, 8), Size::new(8, 6), ScaleMode::Crop);
+        assert_eq!(cropped, vec![0x81, 0x42, 0x24, 0x18, 0x18, 0x24]);
+    }
+
+    #[test]
+    fn test_nearest_doubles_pixels() {
+        let data = [0x80, 0x40, 0x20, 0x10];
+        let scaled = scale_glyph(u32::from(b'X'), &data, Size::new(4, 4), Size::new(8, 8), ScaleMode::Nearest);
+        assert_eq!(scaled, vec![0xC0, 0xC0, 0x30, 0x30, 0x0C, 0x0C, 0x03, 0x03]);
+    }
+
+    #[test]
+    fn test_lga_9th_column() {
+        // ─ repeats the 8th column, │ doesn't
+        assert!(lga_pixel(0xC4, 0xFF, 8, 8));
+        assert!(!lga_pixel(0xB3, 0xFF, 8, 8));
+        // the range is 0xC0-0xDF
+        assert!(lga_pixel(0xC0, 0x01, 8, 8));
+        assert!(lga_pixel(0xDF, 0x01, 8, 8));
+        assert!(!lga_pixel(0xBF, 0x01, 8, 8));
+        assert!(!lga_pixel(0xE0, 0x01, 8, 8));
+        // only the 8th column is repeated
+        assert!(!lga_pixel(0xC4, 0xFE, 8, 8));
+        // the other columns are the glyph pixels
+        assert!(lga_pixel(0xB3, 0x10, 8, 3));
+        assert!(!lga_pixel(0xB3, 0x10, 8, 4));
+    }
+}
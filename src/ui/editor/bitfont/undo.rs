@@ -3,6 +3,8 @@ use icy_engine::{BitFont, EngineResult, Glyph};
 
 use crate::BitFontEditor;
 
+use super::glyph_ops::GlyphTransform;
+
 pub trait UndoOperation: Send {
     fn get_description(&self) -> String;
 
@@ -59,152 +61,64 @@ impl UndoOperation for Paste {
     }
 }
 
-pub struct FlipY {
-    ch: char,
+pub struct TransformGlyphs {
+    chars: Vec<char>,
+    transform: GlyphTransform,
+    old_data: Vec<(char, Vec<u8>)>,
 }
 
-impl FlipY {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch }
-    }
-}
-
-impl UndoOperation for FlipY {
-    fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-flip-y")
-    }
-
-    fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = glyph.data.iter().rev().copied().collect();
-        }
-        Ok(())
-    }
-
-    fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = glyph.data.iter().rev().copied().collect();
+impl TransformGlyphs {
+    pub(crate) fn new(chars: Vec<char>, transform: GlyphTransform) -> Self {
+        Self {
+            chars,
+            transform,
+            old_data: Vec::new(),
         }
-        Ok(())
-    }
-}
-
-pub struct FlipX {
-    ch: char,
-}
-
-impl FlipX {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch }
     }
 }
 
-impl UndoOperation for FlipX {
+impl UndoOperation for TransformGlyphs {
     fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-flip-x")
+        self.transform.get_description()
     }
 
     fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        let w = 8 - edit_state.font.size.width;
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            for i in 0..glyph.data.len() {
-                glyph.data[i] = glyph.data[i].reverse_bits() << w;
+        for (ch, data) in &self.old_data {
+            if let Some(glyph) = edit_state.font.get_glyph_mut(*ch) {
+                glyph.data = data.clone();
             }
         }
         Ok(())
     }
 
     fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        let w = 8 - edit_state.font.size.width;
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            for i in 0..glyph.data.len() {
-                glyph.data[i] = glyph.data[i].reverse_bits() << w;
+        let size = edit_state.font.size;
+        self.old_data.clear();
+        for ch in &self.chars {
+            if let Some(glyph) = edit_state.font.get_glyph_mut(*ch) {
+                self.old_data.push((*ch, glyph.data.clone()));
+                self.transform.apply(&mut glyph.data, size);
             }
         }
         Ok(())
     }
 }
 
-pub struct DownGlyph {
-    ch: char,
-    old_data: Vec<u8>,
-}
-
-impl DownGlyph {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch, old_data: Vec::new() }
-    }
-}
-
-impl UndoOperation for DownGlyph {
-    fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-down")
-    }
-
-    fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = self.old_data.clone();
-        }
-        Ok(())
-    }
-
-    fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            self.old_data = glyph.data.clone();
-            glyph.data.insert(0, 0);
-            glyph.data.pop();
-        }
-        Ok(())
-    }
-}
-
-pub struct UpGlyph {
-    ch: char,
-    old_data: Vec<u8>,
-}
-
-impl UpGlyph {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch, old_data: Vec::new() }
-    }
-}
-
-impl UndoOperation for UpGlyph {
-    fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-up")
-    }
-
-    fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = self.old_data.clone();
-        }
-        Ok(())
-    }
-
-    fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            self.old_data = glyph.data.clone();
-            glyph.data.remove(0);
-            glyph.data.push(0);
-        }
-        Ok(())
-    }
-}
-
-pub struct RightGlyph {
+pub struct Edit {
     ch: char,
     old_data: Vec<u8>,
+    data: Vec<u8>,
 }
 
-impl RightGlyph {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch, old_data: Vec::new() }
+impl Edit {
+    pub(crate) fn new(ch: char, data: Vec<u8>, old_data: Vec<u8>) -> Self {
+        Self { ch, data, old_data }
     }
 }
 
-impl UndoOperation for RightGlyph {
+impl UndoOperation for Edit {
     fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-right")
+        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-edit")
     }
 
     fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
@@ -216,170 +130,80 @@ impl UndoOperation for RightGlyph {
 
     fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
         if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            self.old_data = glyph.data.clone();
-            for i in 0..glyph.data.len() {
-                glyph.data[i] >>= 1;
-            }
+            glyph.data = self.data.clone();
         }
         Ok(())
     }
 }
 
-pub struct LeftGlyph {
-    ch: char,
-    old_data: Vec<u8>,
+pub struct ResizeFont {
+    old_font: BitFont,
+    new_font: BitFont,
 }
 
-impl LeftGlyph {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch, old_data: Vec::new() }
+impl ResizeFont {
+    pub(crate) fn new(old_font: BitFont, new_font: BitFont) -> Self {
+        Self { old_font, new_font }
     }
 }
 
-impl UndoOperation for LeftGlyph {
+impl UndoOperation for ResizeFont {
     fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-move-left")
+        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-resize")
     }
 
     fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = self.old_data.clone();
-        }
+        edit_state.font = self.old_font.clone();
         Ok(())
     }
 
     fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            self.old_data = glyph.data.clone();
-            for i in 0..glyph.data.len() {
-                glyph.data[i] <<= 1;
-            }
-        }
+        edit_state.font = self.new_font.clone();
         Ok(())
     }
 }
 
-pub struct ClearGlyph {
-    ch: char,
-    old_data: Vec<u8>,
+/// Replaces the data of several glyphs, used for importing and pasting glyph ranges.
+pub struct SetGlyphs {
+    glyphs: Vec<(char, Vec<u8>)>,
+    old_glyphs: Vec<(char, Vec<u8>)>,
+    description: String,
 }
 
-impl ClearGlyph {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch, old_data: Vec::new() }
-    }
-}
-
-impl UndoOperation for ClearGlyph {
-    fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-clear")
-    }
-
-    fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = self.old_data.clone();
-        }
-        Ok(())
-    }
-
-    fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            self.old_data = glyph.data.clone();
-            glyph.data.fill(0);
+impl SetGlyphs {
+    pub(crate) fn new(glyphs: Vec<(char, Vec<u8>)>, description: String) -> Self {
+        Self {
+            glyphs,
+            old_glyphs: Vec::new(),
+            description,
         }
-        Ok(())
-    }
-}
-
-pub struct InverseGlyph {
-    ch: char,
-}
-
-impl InverseGlyph {
-    pub(crate) fn new(ch: char) -> Self {
-        Self { ch }
     }
 }
 
-impl UndoOperation for InverseGlyph {
+impl UndoOperation for SetGlyphs {
     fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-inverse")
+        self.description.clone()
     }
 
     fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            for i in 0..glyph.data.len() {
-                glyph.data[i] ^= 0xFF;
+        for (ch, data) in &self.old_glyphs {
+            if let Some(glyph) = edit_state.font.get_glyph_mut(*ch) {
+                glyph.data = data.clone();
             }
         }
         Ok(())
     }
 
     fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            for i in 0..glyph.data.len() {
-                glyph.data[i] ^= 0xFF;
+        let len = edit_state.font.size.height as usize;
+        self.old_glyphs.clear();
+        for (ch, data) in &self.glyphs {
+            if let Some(glyph) = edit_state.font.get_glyph_mut(*ch) {
+                self.old_glyphs.push((*ch, glyph.data.clone()));
+                glyph.data = data.clone();
+                glyph.data.resize(len, 0);
             }
         }
         Ok(())
     }
 }
-
-pub struct Edit {
-    ch: char,
-    old_data: Vec<u8>,
-    data: Vec<u8>,
-}
-
-impl Edit {
-    pub(crate) fn new(ch: char, data: Vec<u8>, old_data: Vec<u8>) -> Self {
-        Self { ch, data, old_data }
-    }
-}
-
-impl UndoOperation for Edit {
-    fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-edit")
-    }
-
-    fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = self.old_data.clone();
-        }
-        Ok(())
-    }
-
-    fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        if let Some(glyph) = edit_state.font.get_glyph_mut(self.ch) {
-            glyph.data = self.data.clone();
-        }
-        Ok(())
-    }
-}
-
-pub struct ResizeFont {
-    old_font: BitFont,
-    new_font: BitFont,
-}
-
-impl ResizeFont {
-    pub(crate) fn new(old_font: BitFont, new_font: BitFont) -> Self {
-        Self { old_font, new_font }
-    }
-}
-
-impl UndoOperation for ResizeFont {
-    fn get_description(&self) -> String {
-        fl!(crate::LANGUAGE_LOADER, "undo-bitfont-resize")
-    }
-
-    fn undo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        edit_state.font = self.old_font.clone();
-        Ok(())
-    }
-
-    fn redo(&mut self, edit_state: &mut BitFontEditor) -> EngineResult<()> {
-        edit_state.font = self.new_font.clone();
-        Ok(())
-    }
-}
@@ -1,37 +1,64 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 
 use eframe::{
     egui::{self, Button, ScrollArea, SidePanel, TextEdit, TopBottomPanel},
     epaint::{mutex::Mutex, Vec2},
 };
 use egui::{load::SizedTexture, Image, Rect, TextureHandle};
+use egui_file::FileDialog;
 use i18n_embed_fl::fl;
-use icy_engine::{AttributedChar, BitFont, Buffer, EngineResult, FontGlyph, Layer, Size, TextAttribute, TextPane, TheDrawFont};
+use icy_engine::{
+    util::{pop_data, BUFFER_DATA},
+    AttributedChar, BitFont, Buffer, EngineResult, FontGlyph, FontType, Layer, Size, TextAttribute, TextPane, TheDrawFont,
+};
 use icy_engine_gui::{show_terminal_area, BufferView};
 
 use crate::{
     model::{click_imp::VALID_OUTLINE_CHARS, Tool},
+    util::figlet::{is_figlet_extension, load_figlet_font},
     AnsiEditor, BitFontEditor, ClipboardHandler, Document, DocumentOptions, DrawGlyphStyle, Message, SelectOutlineDialog, TerminalResult, UndoHandler,
     SETTINGS,
 };
 
+use self::undo::UndoOperation;
+mod undo;
+
 pub struct CharFontEditor {
     id: usize,
     font: BitFont,
     selected_char_opt: Option<char>,
-    old_selected_char_opt: Option<char>,
+    edited_glyph: Option<EditedGlyph>,
 
     outline_previewbuffer_view: Arc<Mutex<BufferView>>,
 
     ansi_editor: AnsiEditor,
     selected_font: usize,
     fonts: Vec<TheDrawFont>,
-    undostack_len: usize,
     last_update_preview: usize,
     last_update_preview_attr: TextAttribute,
     outline_selection: crate::SelectOutlineDialog,
     draw_outline_bg: bool,
     opt_cheat_sheet: Option<TextureHandle>,
+
+    undo_stack: Vec<Box<dyn UndoOperation>>,
+    redo_stack: Vec<Box<dyn UndoOperation>>,
+    /// Increased on every change, the undo stacks can't tell if the fonts changed since they are collapsed and truncated.
+    change_count: Cell<usize>,
+    /// Length of the ansi editor undo stack when the change count was last updated.
+    glyph_undo_len: Cell<usize>,
+    old_font_name: Option<String>,
+    old_font_spacing: Option<i32>,
+    /// Export file dialog and the font to export, None exports the whole font set.
+    export_dialog: Option<(FileDialog, Option<usize>)>,
+    import_figlet_dialog: Option<FileDialog>,
+    /// Reading the clipboard is slow, it's checked for buffer data only every few frames.
+    clipboard_has_buffer: bool,
+    last_clipboard_check: Instant,
 }
 
 impl ClipboardHandler for CharFontEditor {
@@ -61,28 +88,52 @@ impl ClipboardHandler for CharFontEditor {
 
 impl UndoHandler for CharFontEditor {
     fn undo_description(&self) -> Option<String> {
-        self.ansi_editor.undo_description()
+        if self.ansi_editor.can_undo() {
+            return self.ansi_editor.undo_description();
+        }
+        self.undo_stack.last().map(|op| op.get_description())
     }
 
     fn can_undo(&self) -> bool {
-        self.ansi_editor.can_undo()
+        self.ansi_editor.can_undo() || !self.undo_stack.is_empty()
     }
 
     fn undo(&mut self) -> EngineResult<Option<Message>> {
-        self.ansi_editor.undo()?;
+        if self.ansi_editor.can_undo() {
+            self.ansi_editor.undo()?;
+            return Ok(None);
+        }
+        let Some(mut op) = self.undo_stack.pop() else {
+            return Ok(None);
+        };
+        op.undo(self)?;
+        self.redo_stack.push(op);
+        self.mark_changed();
         Ok(None)
     }
 
     fn redo_description(&self) -> Option<String> {
-        self.ansi_editor.redo_description()
+        if self.ansi_editor.can_redo() {
+            return self.ansi_editor.redo_description();
+        }
+        self.redo_stack.last().map(|op| op.get_description())
     }
 
     fn can_redo(&self) -> bool {
-        self.ansi_editor.can_redo()
+        self.ansi_editor.can_redo() || !self.redo_stack.is_empty()
     }
 
     fn redo(&mut self) -> EngineResult<Option<Message>> {
-        self.ansi_editor.redo()?;
+        if self.ansi_editor.can_redo() {
+            self.ansi_editor.redo()?;
+            return Ok(None);
+        }
+        let Some(mut op) = self.redo_stack.pop() else {
+            return Ok(None);
+        };
+        op.redo(self)?;
+        self.undo_stack.push(op);
+        self.mark_changed();
         Ok(None)
     }
 }
@@ -93,16 +144,41 @@ impl Document for CharFontEditor {
     }
 
     fn undo_stack_len(&self) -> usize {
-        self.undostack_len
+        self.sync_glyph_changes();
+        self.change_count.get()
     }
 
     fn get_bytes(&mut self, _path: &Path) -> TerminalResult<Vec<u8>> {
-        self.undostack_len += 1;
         self.save_old_selected_char();
         TheDrawFont::create_font_bundle(&self.fonts)
     }
 
     fn show_ui(&mut self, ui: &mut egui::Ui, cur_tool: &mut Box<dyn Tool>, selected_tool: usize, options: &DocumentOptions) -> Option<Message> {
+        let mut message = None;
+        if let Some((mut dialog, font)) = self.export_dialog.take() {
+            if dialog.show(ui.ctx()).selected() {
+                if let Some(path) = dialog.path() {
+                    if let Err(err) = self.export_fonts(path, font) {
+                        message = Some(Message::ShowError(format!("Could not export: {err}")));
+                    }
+                }
+            } else if dialog.visible() {
+                self.export_dialog = Some((dialog, font));
+            }
+        }
+
+        if let Some(mut dialog) = self.import_figlet_dialog.take() {
+            if dialog.show(ui.ctx()).selected() {
+                if let Some(path) = dialog.path() {
+                    if let Err(err) = self.import_figlet_font(path) {
+                        message = Some(Message::ShowError(format!("Could not import: {err}")));
+                    }
+                }
+            } else if dialog.visible() {
+                self.import_figlet_dialog = Some(dialog);
+            }
+        }
+
         SidePanel::left("side_panel").default_width(200.0).show_inside(ui, |ui| {
             ui.add_space(4.0);
 
@@ -111,10 +187,8 @@ impl Document for CharFontEditor {
                     ui.style_mut().wrap = Some(false);
 
                     for i in 0..self.fonts.len() {
-                        if ui.selectable_value(&mut self.selected_font, i, &self.fonts[i].name).clicked() {
-                            self.save_old_selected_char();
+                        if ui.selectable_label(self.selected_font == i, &self.fonts[i].name).clicked() {
                             self.selected_font = i;
-                            self.old_selected_char_opt = None;
                             self.selected_char_opt = None;
                             self.show_selected_char();
                         }
@@ -124,37 +198,69 @@ impl Document for CharFontEditor {
             ui.separator();
 
             ui.horizontal(|ui| {
-                /*if ui.button("+").clicked() {
-                    self.fonts.push(TheDrawFont::new(
-                        "New Font",
-                        icy_engine::FontType::Color,
-                        1,
-                    ));
-                    self.selected_font = self.fonts.len() - 1;
-                    self.selected_char_opt = None;
-                    self.old_selected_char_opt = None;
-                    self.show_selected_char();
-                    self.undostack_len += 1;
-                }*/
+                ui.menu_button("+", |ui| {
+                    for font_type in [FontType::Block, FontType::Color, FontType::Outline] {
+                        if ui.button(font_type_label(font_type)).clicked() {
+                            let font = TheDrawFont::new(fl!(crate::LANGUAGE_LOADER, "tdf-editor-new_font_name"), font_type, 1);
+                            let op = undo::AddFont::new(self.fonts.len(), font, fl!(crate::LANGUAGE_LOADER, "undo-tdf-add-font"));
+                            if let Err(err) = self.push_undo(Box::new(op)) {
+                                message = Some(Message::ShowError(format!("{err}")));
+                            }
+                            ui.close_menu();
+                        }
+                    }
+                });
 
                 if ui.add_enabled(self.fonts.len() > 1, Button::new("🗑")).clicked() {
-                    self.fonts.remove(self.selected_font);
-                    self.selected_font = 0;
-                    self.selected_char_opt = None;
-                    self.old_selected_char_opt = None;
-                    self.show_selected_char();
-                    self.undostack_len += 1;
+                    let op = undo::DeleteFont::new(self.selected_font);
+                    if let Err(err) = self.push_undo(Box::new(op)) {
+                        message = Some(Message::ShowError(format!("{err}")));
+                    }
                 }
 
                 if ui.button(fl!(crate::LANGUAGE_LOADER, "tdf-editor-clone_button")).clicked() {
-                    self.fonts.push(self.fonts[self.selected_font].clone());
-                    self.selected_font = self.fonts.len() - 1;
-                    self.selected_char_opt = None;
-                    self.old_selected_char_opt = None;
-                    self.show_selected_char();
-                    self.undostack_len += 1;
+                    self.save_old_selected_char();
+                    let font = self.fonts[self.selected_font].clone();
+                    let op = undo::AddFont::new(self.fonts.len(), font, fl!(crate::LANGUAGE_LOADER, "undo-tdf-clone-font"));
+                    if let Err(err) = self.push_undo(Box::new(op)) {
+                        message = Some(Message::ShowError(format!("{err}")));
+                    }
                 }
             });
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        self.selected_font < self.fonts.len(),
+                        Button::new(fl!(crate::LANGUAGE_LOADER, "tdf-editor-export_font_button")),
+                    )
+                    .clicked()
+                {
+                    let file_name = format!("{}.tdf", self.fonts[self.selected_font].name.trim());
+                    let mut dialog = FileDialog::save_file(Some(PathBuf::from(file_name)));
+                    dialog.open();
+                    self.export_dialog = Some((dialog, Some(self.selected_font)));
+                }
+                if ui
+                    .add_enabled(!self.fonts.is_empty(), Button::new(fl!(crate::LANGUAGE_LOADER, "tdf-editor-export_all_button")))
+                    .clicked()
+                {
+                    let mut dialog = FileDialog::save_file(None);
+                    dialog.open();
+                    self.export_dialog = Some((dialog, None));
+                }
+            });
+            let import_button = ui.button(fl!(crate::LANGUAGE_LOADER, "tdf-editor-import_figlet_button"));
+            let import_button = import_button.on_hover_ui(|ui| {
+                ui.label(fl!(crate::LANGUAGE_LOADER, "tdf-editor-import_figlet_button-tooltip"));
+            });
+            if import_button.clicked() {
+                let mut dialog = FileDialog::open_file(None).show_files_filter(Box::new(|path| {
+                    path.extension()
+                        .map_or(false, |ext| is_figlet_extension(&ext.to_string_lossy().to_ascii_lowercase()))
+                }));
+                dialog.open();
+                self.import_figlet_dialog = Some(dialog);
+            }
         });
 
         TopBottomPanel::top("char_top_panel").exact_height(60.).show_inside(ui, |ui| {
@@ -170,43 +276,72 @@ impl Document for CharFontEditor {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_name_label"));
                     });
-                    if ui
-                        .add(
-                            TextEdit::singleline(&mut self.fonts[self.selected_font].name)
-                                .min_size(Vec2::new(200.0, 22.))
-                                .char_limit(12),
-                        )
-                        .changed()
-                    {
-                        self.undostack_len += 1;
+                    let response = ui.add(
+                        TextEdit::singleline(&mut self.fonts[self.selected_font].name)
+                            .min_size(Vec2::new(200.0, 22.))
+                            .char_limit(12),
+                    );
+                    if response.changed() {
+                        self.mark_changed();
+                    }
+                    if response.gained_focus() {
+                        self.old_font_name = Some(self.fonts[self.selected_font].name.clone());
+                    }
+                    if response.lost_focus() {
+                        // the whole edit is one rename operation
+                        if let Some(old_name) = self.old_font_name.take() {
+                            let new_name = self.fonts[self.selected_font].name.clone();
+                            if old_name != new_name {
+                                let op = undo::RenameFont::new(self.selected_font, old_name, new_name);
+                                if let Err(err) = self.push_undo(Box::new(op)) {
+                                    message = Some(Message::ShowError(format!("{err}")));
+                                }
+                            }
+                        }
                     }
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_label"));
                     });
 
-                    let text = match self.fonts[self.selected_font].font_type {
-                        icy_engine::FontType::Outline => {
-                            fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_outline")
-                        }
-                        icy_engine::FontType::Block => {
-                            fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_block")
-                        }
-                        icy_engine::FontType::Color => {
-                            fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_color")
+                    let cur_type = self.fonts[self.selected_font].font_type;
+                    let mut new_type = cur_type;
+                    egui::ComboBox::from_id_source("tdf_font_type_combobox")
+                        .selected_text(font_type_label(cur_type))
+                        .show_ui(ui, |ui| {
+                            for font_type in [FontType::Block, FontType::Color, FontType::Outline] {
+                                ui.selectable_value(&mut new_type, font_type, font_type_label(font_type));
+                            }
+                        });
+                    if new_type != cur_type {
+                        let old_font = self.fonts[self.selected_font].clone();
+                        let new_font = convert_font_type(&old_font, new_type);
+                        let op = undo::ReplaceFont::new(self.selected_font, old_font, new_font, fl!(crate::LANGUAGE_LOADER, "undo-tdf-font-type"));
+                        if let Err(err) = self.push_undo(Box::new(op)) {
+                            message = Some(Message::ShowError(format!("{err}")));
                         }
-                    };
-                    ui.label(text);
+                    }
 
                     ui.end_row();
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.label(fl!(crate::LANGUAGE_LOADER, "tdf-editor-spacing_label"));
                     });
-                    if ui
-                        .add(egui::DragValue::new(&mut self.fonts[self.selected_font].spaces).clamp_range(0.0..=40.0))
-                        .changed()
-                    {
-                        self.undostack_len += 1;
+                    let old_spaces = self.fonts[self.selected_font].spaces;
+                    let response = ui.add(egui::DragValue::new(&mut self.fonts[self.selected_font].spaces).clamp_range(0.0..=40.0));
+                    if response.changed() {
+                        self.old_font_spacing.get_or_insert(old_spaces);
+                        self.mark_changed();
+                    }
+                    if !response.dragged() && !response.has_focus() {
+                        if let Some(old_spaces) = self.old_font_spacing.take() {
+                            let new_spaces = self.fonts[self.selected_font].spaces;
+                            if old_spaces != new_spaces {
+                                let op = undo::SetFontSpacing::new(self.selected_font, old_spaces, new_spaces);
+                                if let Err(err) = self.push_undo(Box::new(op)) {
+                                    message = Some(Message::ShowError(format!("{err}")));
+                                }
+                            }
+                        }
                     }
                     ui.label("");
                     ui.label("");
@@ -221,13 +356,32 @@ impl Document for CharFontEditor {
             if self.selected_font < self.fonts.len() {
                 self.show_char_selector(ui);
                 ui.add_space(4.0);
-                if self.selected_char_opt.is_some() && ui.button(fl!(crate::LANGUAGE_LOADER, "tdf-editor-clear_char_button")).clicked() {
-                    self.fonts[self.selected_font].clear_glyph(self.selected_char_opt.unwrap());
-                    self.selected_char_opt = None;
-                    self.old_selected_char_opt = None;
-                    self.show_selected_char();
-                    self.undostack_len += 1;
-                }
+                ui.horizontal(|ui| {
+                    if let Some(ch) = self.selected_char_opt {
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "tdf-editor-clear_char_button")).clicked() {
+                            let op = undo::ClearGlyph::new(self.selected_font, ch);
+                            if let Err(err) = self.push_undo(Box::new(op)) {
+                                message = Some(Message::ShowError(format!("{err}")));
+                            }
+                        }
+                    }
+                    if self.last_clipboard_check.elapsed().as_millis() > 250 {
+                        self.clipboard_has_buffer = pop_data(BUFFER_DATA).is_some();
+                        self.last_clipboard_check = Instant::now();
+                    }
+                    let import_button = ui.add_enabled(
+                        self.selected_char_opt.is_some() && self.clipboard_has_buffer,
+                        Button::new(fl!(crate::LANGUAGE_LOADER, "tdf-editor-import_glyphs_button")),
+                    );
+                    let import_button = import_button.on_hover_ui(|ui| {
+                        ui.label(fl!(crate::LANGUAGE_LOADER, "tdf-editor-import_glyphs_button-tooltip"));
+                    });
+                    if import_button.clicked() {
+                        if let Err(err) = self.import_glyphs_from_clipboard() {
+                            message = Some(Message::ShowError(format!("{err}")));
+                        }
+                    }
+                });
             }
         });
 
@@ -395,7 +549,7 @@ impl Document for CharFontEditor {
             self.render_outline_preview();
         }
 
-        None
+        message
     }
 
     fn get_ansi_editor_mut(&mut self) -> Option<&mut AnsiEditor> {
@@ -442,16 +596,25 @@ impl CharFontEditor {
             font: BitFont::default(),
             ansi_editor,
             selected_char_opt: Some('A'),
-            old_selected_char_opt: None,
+            edited_glyph: None,
             fonts,
             selected_font: 0,
-            undostack_len: 0,
             outline_previewbuffer_view,
             outline_selection: SelectOutlineDialog::default(),
             last_update_preview: 0,
             opt_cheat_sheet: None,
             draw_outline_bg: true,
             last_update_preview_attr: TextAttribute::default(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            change_count: Cell::new(0),
+            glyph_undo_len: Cell::new(0),
+            old_font_name: None,
+            old_font_spacing: None,
+            export_dialog: None,
+            import_figlet_dialog: None,
+            clipboard_has_buffer: pop_data(BUFFER_DATA).is_some(),
+            last_clipboard_check: Instant::now(),
         };
         res.show_selected_char();
         res
@@ -511,7 +674,7 @@ impl CharFontEditor {
 
     fn show_selected_char(&mut self) {
         {
-            self.save_old_selected_char();
+            self.commit_glyph_edit();
             let font = &self.fonts[self.selected_font];
             self.ansi_editor.outline_font_mode = matches!(font.font_type, icy_engine::FontType::Outline);
             let lock = &mut self.ansi_editor.buffer_view.lock();
@@ -527,7 +690,12 @@ impl CharFontEditor {
             }
 
             edit_state.get_undo_stack().lock().unwrap().clear();
-            self.old_selected_char_opt = self.selected_char_opt;
+            self.glyph_undo_len.set(0);
+            self.edited_glyph = self.selected_char_opt.map(|ch| EditedGlyph {
+                font: self.selected_font,
+                ch,
+                old_glyph: font.get_glyph(ch).cloned(),
+            });
         }
         self.render_outline_preview();
     }
@@ -536,80 +704,244 @@ impl CharFontEditor {
         if self.ansi_editor.buffer_view.lock().get_edit_state().undo_stack_len() == 0 {
             return;
         }
-        self.undostack_len += 1;
-        if let Some(font) = self.fonts.get_mut(self.selected_font) {
-            if let Some(ch) = self.old_selected_char_opt {
-                match font.font_type {
-                    icy_engine::FontType::Outline => {
-                        let lock = &mut self.ansi_editor.buffer_view.lock();
-                        let buf = lock.get_buffer();
-                        let mut data = Vec::new();
-                        let mut w = 0;
-                        let mut h = 0;
-                        for y in 0..buf.get_line_count() {
-                            if y > 0 {
-                                data.push(13);
-                            }
-                            let lw = buf.get_line_length(y);
-                            for x in 0..lw {
-                                let ch = buf.get_char((x, y));
-                                if VALID_OUTLINE_CHARS.contains(ch.ch) {
-                                    data.push(ch.ch as u8);
-                                }
-                            }
-                            w = w.max(lw);
-                            h = y;
-                        }
+        if let Some(edited) = &self.edited_glyph {
+            if let Some(font) = self.fonts.get_mut(edited.font) {
+                let lock = &mut self.ansi_editor.buffer_view.lock();
+                let buf = lock.get_buffer();
+                let lines = (0..buf.get_line_count())
+                    .map(|y| (0..buf.get_line_length(y)).map(|x| buf.get_char((x, y))).collect())
+                    .collect::<Vec<Vec<AttributedChar>>>();
+                font.set_glyph(edited.ch, create_glyph(font.font_type, &lines));
+            }
+        }
+    }
 
-                        font.set_glyph(ch, FontGlyph { size: Size::new(w, h), data });
-                    }
-                    icy_engine::FontType::Block => {
-                        let lock = &mut self.ansi_editor.buffer_view.lock();
-                        let buf = lock.get_buffer();
-                        let mut data = Vec::new();
-                        let mut w = 0;
-                        let mut h = 0;
-                        for y in 0..buf.get_line_count() {
-                            if y > 0 {
-                                data.push(13);
-                            }
-                            let lw = buf.get_line_length(y);
-                            for x in 0..lw {
-                                let ch = buf.get_char((x, y));
-                                data.push(ch.ch as u8);
-                            }
-                            w = w.max(lw);
-                            h = y;
-                        }
+    /// Stores the changes made to the edited glyph as one undo operation.
+    /// The fine grained changes are on the ansi editor undo stack as long as the glyph is edited.
+    fn commit_glyph_edit(&mut self) {
+        if self.ansi_editor.buffer_view.lock().get_edit_state().undo_stack_len() == 0 {
+            return;
+        }
+        self.sync_glyph_changes();
+        self.save_old_selected_char();
+        self.ansi_editor
+            .buffer_view
+            .lock()
+            .get_edit_state_mut()
+            .get_undo_stack()
+            .lock()
+            .unwrap()
+            .clear();
+        // collapsing the glyph edit into one operation doesn't change the font
+        self.glyph_undo_len.set(0);
+        let Some(edited) = &mut self.edited_glyph else {
+            return;
+        };
+        let Some(font) = self.fonts.get(edited.font) else {
+            return;
+        };
+        let new_glyph = font.get_glyph(edited.ch).cloned();
+        let old_glyph = std::mem::replace(&mut edited.old_glyph, new_glyph.clone());
+        if let Some(new_glyph) = new_glyph {
+            self.undo_stack
+                .push(Box::new(undo::EditGlyph::new(edited.font, edited.ch, old_glyph, new_glyph)));
+            self.redo_stack.clear();
+        }
+    }
+
+    fn push_undo(&mut self, mut op: Box<dyn UndoOperation>) -> EngineResult<()> {
+        self.commit_glyph_edit();
+        op.redo(self)?;
+        self.undo_stack.push(op);
+        self.redo_stack.clear();
+        self.mark_changed();
+        Ok(())
+    }
+
+    fn mark_changed(&self) {
+        self.change_count.set(self.change_count.get() + 1);
+    }
+
+    /// Counts edits, undos and redos of the glyph in the ansi editor.
+    fn sync_glyph_changes(&self) {
+        let len = self.ansi_editor.buffer_view.lock().get_edit_state().undo_stack_len();
+        if len != self.glyph_undo_len.get() {
+            self.glyph_undo_len.set(len);
+            self.mark_changed();
+        }
+    }
+
+    /// Selects a font after the font set changed by an undo operation, the glyph in the editor is reloaded without saving it.
+    fn select_font(&mut self, index: usize) {
+        self.selected_font = index.min(self.fonts.len().saturating_sub(1));
+        self.edited_glyph = None;
+        if !self.fonts.is_empty() {
+            self.show_selected_char();
+        }
+    }
 
-                        font.set_glyph(ch, FontGlyph { size: Size::new(w, h), data });
+    fn select_glyph(&mut self, index: usize, ch: char) {
+        self.selected_char_opt = Some(ch);
+        self.select_font(index);
+    }
+
+    /// Imports the glyphs from the clipboard (a selection copied in an ansi document).
+    /// Glyphs are separated by empty columns, the first one goes to the selected char, the following to the next chars.
+    fn import_glyphs_from_clipboard(&mut self) -> TerminalResult<()> {
+        let Some(ch) = self.selected_char_opt else {
+            return Ok(());
+        };
+        let Some(data) = pop_data(BUFFER_DATA) else {
+            return Err(anyhow::anyhow!("No selection in clipboard"));
+        };
+        let Some(layer) = Layer::from_clipboard_data(&data) else {
+            return Err(anyhow::anyhow!("Invalid clipboard data"));
+        };
+        let font_type = self.fonts[self.selected_font].font_type;
+        let size = layer.get_size();
+        let is_empty_column = |x: i32| (0..size.height).all(|y| is_blank(layer.get_char((x, y))));
+
+        let mut glyphs = Vec::new();
+        let mut next_char = ch as u8;
+        let mut x = 0;
+        while x < size.width && next_char <= b'~' {
+            if is_empty_column(x) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < size.width && !is_empty_column(x) {
+                x += 1;
+            }
+            let mut lines = (0..size.height)
+                .map(|y| {
+                    let mut line: Vec<AttributedChar> = (start..x).map(|x| layer.get_char((x, y))).collect();
+                    while line.last().map_or(false, |c| is_blank(*c)) {
+                        line.pop();
                     }
-                    icy_engine::FontType::Color => {
-                        let lock = &mut self.ansi_editor.buffer_view.lock();
-                        let buf = lock.get_buffer();
-                        let mut data = Vec::new();
-                        let mut w = 0;
-                        let mut h = 0;
-                        for y in 0..buf.get_line_count() {
-                            if y > 0 {
-                                data.push(13);
-                            }
-                            let lw = buf.get_line_length(y);
-                            for x in 0..lw {
-                                let ch = buf.get_char((x, y));
-                                data.push(ch.ch as u8);
-                                data.push(ch.attribute.as_u8(icy_engine::IceMode::Ice));
-                            }
-                            w = w.max(lw);
-                            h = y;
-                        }
+                    line
+                })
+                .collect::<Vec<Vec<AttributedChar>>>();
+            while lines.last().map_or(false, |l| l.is_empty()) {
+                lines.pop();
+            }
+            while lines.first().map_or(false, |l| l.is_empty()) {
+                lines.remove(0);
+            }
+            glyphs.push((next_char as char, create_glyph(font_type, &lines)));
+            next_char += 1;
+        }
+        if glyphs.is_empty() {
+            return Err(anyhow::anyhow!("Selection contains no glyphs"));
+        }
+        self.push_undo(Box::new(undo::ImportGlyphs::new(self.selected_font, glyphs)))?;
+        Ok(())
+    }
+
+    /// Converts a FIGlet font to a block font and adds it to the font set.
+    fn import_figlet_font(&mut self, path: &Path) -> TerminalResult<()> {
+        let data = std::fs::read(path)?;
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let font = load_figlet_font(&name, &data)?;
+        let op = undo::AddFont::new(self.fonts.len(), font, fl!(crate::LANGUAGE_LOADER, "undo-tdf-import-figlet"));
+        self.push_undo(Box::new(op))?;
+        Ok(())
+    }
+
+    fn export_fonts(&self, path: &Path, font: Option<usize>) -> TerminalResult<()> {
+        let bytes = match font {
+            Some(i) => TheDrawFont::create_font_bundle(&self.fonts[i..=i])?,
+            None => TheDrawFont::create_font_bundle(&self.fonts)?,
+        };
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+}
+
+/// The glyph loaded into the ansi editor, with the glyph data before editing.
+struct EditedGlyph {
+    font: usize,
+    ch: char,
+    old_glyph: Option<FontGlyph>,
+}
+
+fn is_blank(ch: AttributedChar) -> bool {
+    !ch.is_visible() || ch.ch == ' ' || ch.ch == '\0'
+}
 
-                        font.set_glyph(ch, FontGlyph { size: Size::new(w, h), data });
+fn font_type_label(font_type: FontType) -> String {
+    match font_type {
+        FontType::Outline => fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_outline"),
+        FontType::Block => fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_block"),
+        FontType::Color => fl!(crate::LANGUAGE_LOADER, "tdf-editor-font_type_color"),
+    }
+}
+
+/// Creates the glyph data for the given font type, lines are separated by 13.
+fn create_glyph(font_type: FontType, lines: &[Vec<AttributedChar>]) -> FontGlyph {
+    let mut data = Vec::new();
+    let mut w = 0;
+    let mut h = 0;
+    for (y, line) in lines.iter().enumerate() {
+        if y > 0 {
+            data.push(13);
+        }
+        for ch in line {
+            match font_type {
+                FontType::Outline => {
+                    if VALID_OUTLINE_CHARS.contains(ch.ch) {
+                        data.push(ch.ch as u8);
                     }
                 }
+                FontType::Block => {
+                    data.push(ch.ch as u8);
+                }
+                FontType::Color => {
+                    data.push(ch.ch as u8);
+                    data.push(ch.attribute.as_u8(icy_engine::IceMode::Ice));
+                }
             }
         }
+        w = w.max(line.len() as i32);
+        h = y as i32;
+    }
+    FontGlyph { size: Size::new(w, h), data }
+}
+
+/// Converts the glyphs of a font to another font type.
+/// Outline glyphs don't map to block or color glyphs (and vice versa) so these get dropped.
+fn convert_font_type(font: &TheDrawFont, font_type: FontType) -> TheDrawFont {
+    let mut result = TheDrawFont::new(font.name.clone(), font_type, font.spaces);
+    for ch in b'!'..=b'~' {
+        let Some(glyph) = font.get_glyph(ch as char) else {
+            continue;
+        };
+        let data = match (font.font_type, font_type) {
+            (FontType::Block, FontType::Color) => {
+                let mut data = Vec::new();
+                for b in &glyph.data {
+                    data.push(*b);
+                    if *b != 13 {
+                        data.push(TextAttribute::default().as_u8(icy_engine::IceMode::Ice));
+                    }
+                }
+                data
+            }
+            (FontType::Color, FontType::Block) => {
+                let mut data = Vec::new();
+                let mut i = 0;
+                while i < glyph.data.len() {
+                    data.push(glyph.data[i]);
+                    i += if glyph.data[i] == 13 { 1 } else { 2 };
+                }
+                data
+            }
+            (FontType::Block, FontType::Block) | (FontType::Color, FontType::Color) | (FontType::Outline, FontType::Outline) => glyph.data.clone(),
+            _ => continue,
+        };
+        result.set_glyph(ch as char, FontGlyph { size: glyph.size, data });
     }
+    result
 }
 
 fn set_up_layers(buffer: &mut Buffer) {
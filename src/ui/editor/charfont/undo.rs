@@ -0,0 +1,295 @@
+use i18n_embed_fl::fl;
+use icy_engine::{EngineResult, FontGlyph, TheDrawFont};
+
+use crate::CharFontEditor;
+
+pub trait UndoOperation: Send {
+    fn get_description(&self) -> String;
+
+    /// .
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if .
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()>;
+    /// .
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if .
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()>;
+}
+
+pub struct AddFont {
+    index: usize,
+    font: TheDrawFont,
+    description: String,
+}
+
+impl AddFont {
+    pub(crate) fn new(index: usize, font: TheDrawFont, description: String) -> Self {
+        Self { index, font, description }
+    }
+}
+
+impl UndoOperation for AddFont {
+    fn get_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts.remove(self.index);
+        edit_state.select_font(self.index.saturating_sub(1));
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts.insert(self.index, self.font.clone());
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+}
+
+pub struct DeleteFont {
+    index: usize,
+    font: Option<TheDrawFont>,
+}
+
+impl DeleteFont {
+    pub(crate) fn new(index: usize) -> Self {
+        Self { index, font: None }
+    }
+}
+
+impl UndoOperation for DeleteFont {
+    fn get_description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "undo-tdf-delete-font")
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        if let Some(font) = self.font.take() {
+            edit_state.fonts.insert(self.index, font);
+        }
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        self.font = Some(edit_state.fonts.remove(self.index));
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+}
+
+pub struct RenameFont {
+    index: usize,
+    old_name: String,
+    new_name: String,
+}
+
+impl RenameFont {
+    pub(crate) fn new(index: usize, old_name: String, new_name: String) -> Self {
+        Self { index, old_name, new_name }
+    }
+}
+
+impl UndoOperation for RenameFont {
+    fn get_description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "undo-tdf-rename-font")
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index].name = self.old_name.clone();
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index].name = self.new_name.clone();
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+}
+
+pub struct SetFontSpacing {
+    index: usize,
+    old_spaces: i32,
+    new_spaces: i32,
+}
+
+impl SetFontSpacing {
+    pub(crate) fn new(index: usize, old_spaces: i32, new_spaces: i32) -> Self {
+        Self { index, old_spaces, new_spaces }
+    }
+}
+
+impl UndoOperation for SetFontSpacing {
+    fn get_description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "undo-tdf-font-spacing")
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index].spaces = self.old_spaces;
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index].spaces = self.new_spaces;
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+}
+
+/// Replaces a whole font, used for operations that touch all glyphs like changing the font type.
+pub struct ReplaceFont {
+    index: usize,
+    old_font: TheDrawFont,
+    new_font: TheDrawFont,
+    description: String,
+}
+
+impl ReplaceFont {
+    pub(crate) fn new(index: usize, old_font: TheDrawFont, new_font: TheDrawFont, description: String) -> Self {
+        Self {
+            index,
+            old_font,
+            new_font,
+            description,
+        }
+    }
+}
+
+impl UndoOperation for ReplaceFont {
+    fn get_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index] = self.old_font.clone();
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index] = self.new_font.clone();
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+}
+
+pub struct ImportGlyphs {
+    index: usize,
+    new_glyphs: Vec<(char, FontGlyph)>,
+    old_glyphs: Vec<(char, Option<FontGlyph>)>,
+}
+
+impl ImportGlyphs {
+    pub(crate) fn new(index: usize, new_glyphs: Vec<(char, FontGlyph)>) -> Self {
+        Self {
+            index,
+            new_glyphs,
+            old_glyphs: Vec::new(),
+        }
+    }
+}
+
+impl UndoOperation for ImportGlyphs {
+    fn get_description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "undo-tdf-import-glyphs")
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        let font = &mut edit_state.fonts[self.index];
+        for (ch, glyph) in &self.old_glyphs {
+            match glyph {
+                Some(glyph) => font.set_glyph(*ch, glyph.clone()),
+                None => font.clear_glyph(*ch),
+            }
+        }
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        let font = &mut edit_state.fonts[self.index];
+        self.old_glyphs = self.new_glyphs.iter().map(|(ch, _)| (*ch, font.get_glyph(*ch).cloned())).collect();
+        for (ch, glyph) in &self.new_glyphs {
+            font.set_glyph(*ch, glyph.clone());
+        }
+        edit_state.select_font(self.index);
+        Ok(())
+    }
+}
+
+pub struct EditGlyph {
+    index: usize,
+    ch: char,
+    old_glyph: Option<FontGlyph>,
+    new_glyph: FontGlyph,
+}
+
+impl EditGlyph {
+    pub(crate) fn new(index: usize, ch: char, old_glyph: Option<FontGlyph>, new_glyph: FontGlyph) -> Self {
+        Self {
+            index,
+            ch,
+            old_glyph,
+            new_glyph,
+        }
+    }
+}
+
+impl UndoOperation for EditGlyph {
+    fn get_description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "undo-tdf-edit-glyph", glyph = self.ch.to_string())
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        match &self.old_glyph {
+            Some(glyph) => edit_state.fonts[self.index].set_glyph(self.ch, glyph.clone()),
+            None => edit_state.fonts[self.index].clear_glyph(self.ch),
+        }
+        edit_state.select_glyph(self.index, self.ch);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        edit_state.fonts[self.index].set_glyph(self.ch, self.new_glyph.clone());
+        edit_state.select_glyph(self.index, self.ch);
+        Ok(())
+    }
+}
+
+pub struct ClearGlyph {
+    index: usize,
+    ch: char,
+    old_glyph: Option<FontGlyph>,
+}
+
+impl ClearGlyph {
+    pub(crate) fn new(index: usize, ch: char) -> Self {
+        Self { index, ch, old_glyph: None }
+    }
+}
+
+impl UndoOperation for ClearGlyph {
+    fn get_description(&self) -> String {
+        fl!(crate::LANGUAGE_LOADER, "undo-tdf-clear-glyph", glyph = self.ch.to_string())
+    }
+
+    fn undo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        if let Some(glyph) = &self.old_glyph {
+            edit_state.fonts[self.index].set_glyph(self.ch, glyph.clone());
+        }
+        edit_state.select_glyph(self.index, self.ch);
+        Ok(())
+    }
+
+    fn redo(&mut self, edit_state: &mut CharFontEditor) -> EngineResult<()> {
+        self.old_glyph = edit_state.fonts[self.index].get_glyph(self.ch).cloned();
+        edit_state.fonts[self.index].clear_glyph(self.ch);
+        edit_state.select_glyph(self.index, self.ch);
+        Ok(())
+    }
+}
@@ -68,6 +68,7 @@ impl<'a> MainWindow<'a> {
             selected_font: Arc::new(Mutex::new(0)),
             fonts: Arc::new(Mutex::new(Vec::new())),
             sizes: Vec::new(),
+            layout: crate::util::figlet::FigletLayout::FullWidth,
         };
         fnt.load_fonts();
         fnt.install_watcher();
@@ -195,6 +196,47 @@ impl<'a> MainWindow<'a> {
                 return;
             }
 
+            if "cast" == ext {
+                match crate::AsciiCastRecording::from_bytes(data) {
+                    Ok(recording) => {
+                        self.open_dialog(crate::ImportAsciiCastDialog::new(full_path, recording));
+                    }
+                    Err(err) => {
+                        self.show_error(format!("{err}"));
+                    }
+                }
+                return;
+            }
+
+            if crate::util::figlet::is_figlet_extension(&ext) {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                match crate::util::figlet::load_figlet_font(&name, data) {
+                    Ok(font) => {
+                        // converted to a TheDraw font, so it needs to be saved under a new name
+                        let id = self.create_id();
+                        add_child(&mut self.document_tree, None, Box::new(CharFontEditor::new(&self.gl, id, vec![font])));
+                    }
+                    Err(err) => {
+                        self.show_error(format!("{err}"));
+                    }
+                }
+                return;
+            }
+
+            if crate::util::petscii::is_c64_extension(&ext) {
+                match crate::util::petscii::load_c64_file(&ext, data) {
+                    Ok(buf) => {
+                        let id = self.create_id();
+                        let editor = AnsiEditor::new(&self.gl, id, buf);
+                        add_child(&mut self.document_tree, Some(full_path), Box::new(editor));
+                    }
+                    Err(err) => {
+                        self.show_error(format!("{err}"));
+                    }
+                }
+                return;
+            }
+
             if "tdf" == ext {
                 let file_name = path.file_name();
                 if file_name.is_none() {
@@ -242,8 +284,9 @@ impl<'a> MainWindow<'a> {
             });
             return;
         }
-        let load_path = if load_autosave {
-            autosave::get_autosave_file(path)
+        let snapshot = if load_autosave { autosave::get_latest_snapshot(path) } else { None };
+        let load_path = if let Some(snapshot) = &snapshot {
+            snapshot.file.clone()
         } else {
             path.to_path_buf()
         };
@@ -251,6 +294,9 @@ impl<'a> MainWindow<'a> {
         match fs::read(load_path) {
             Ok(data) => {
                 self.open_data(path, &data);
+                if snapshot.is_some() {
+                    self.mark_unsaved(path);
+                }
             }
             Err(err) => {
                 log::error!("error loading file {path:?}: {err}");
@@ -259,6 +305,47 @@ impl<'a> MainWindow<'a> {
         }
     }
 
+    /// Replaces the document with an autosave snapshot, the current state is kept as a new snapshot.
+    pub fn restore_snapshot(&mut self, path: &Path, snapshot: &Path) {
+        let data = match fs::read(snapshot) {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("error loading snapshot {snapshot:?}: {err}");
+                self.toasts.error(format!("{err}")).set_duration(Some(Duration::from_secs(5)));
+                return;
+            }
+        };
+
+        let mut open_id = None;
+        self.enumerate_documents(|id, pane| {
+            if pane.get_path().as_deref() == Some(path) {
+                open_id = Some(id);
+            }
+        });
+        let mut msg = None;
+        if let Some(id) = open_id {
+            if let Some(egui_tiles::Tile::Pane(pane)) = self.document_tree.tiles.get_mut(id) {
+                if let Ok(bytes) = pane.doc.lock().get_bytes(path) {
+                    autosave::store_snapshot(path, &bytes);
+                }
+                msg = pane.destroy(&self.gl);
+            }
+            self.document_tree.tiles.remove(id);
+        }
+        self.open_data(path, &data);
+        self.mark_unsaved(path);
+        self.handle_message(msg);
+    }
+
+    /// The loaded content differs from the file on disk.
+    fn mark_unsaved(&mut self, path: &Path) {
+        self.enumerate_documents(|_, pane| {
+            if pane.get_path().as_deref() == Some(path) {
+                pane.mark_unsaved();
+            }
+        });
+    }
+
     pub fn get_active_pane_mut(&mut self) -> Option<&mut DocumentTab> {
         let mut stack = vec![];
 
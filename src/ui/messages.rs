@@ -10,7 +10,7 @@ use icy_engine::{util::pop_data, BitFont, EngineResult, IceMode, Layer, PaletteM
 
 use crate::{
     util::autosave::{self},
-    AnsiEditor, MainWindow, NewFileDialog, SaveFileDialog, SelectCharacterDialog, SelectOutlineDialog, Settings, MRU_FILES, PLUGINS, SETTINGS,
+    AnsiEditor, MainWindow, NewFileDialog, SaveFileDialog, SelectCharacterDialog, SelectOutlineDialog, Settings, SymmetryMode, MRU_FILES, PLUGINS, SETTINGS,
 };
 
 #[derive(Clone)]
@@ -36,6 +36,13 @@ pub enum Message {
     Undo,
     Redo,
     EditSauce,
+    ShowBatchSauceDialog,
+    ShowReleasePackDialog,
+    ShowFindReplaceDialog,
+    ShowRemapColorsDialog,
+    ShowConvertPaletteDialog,
+    ShowCompareDialog,
+    ShowDocumentHistoryDialog,
     SetCanvasSize,
     SelectAll,
     SelectNothing,
@@ -56,11 +63,15 @@ pub enum Message {
     Center,
     FlipX,
     FlipY,
+    Rotate90,
+    Rotate180,
+    Rotate270,
     Crop,
     Paste,
     ResizeBuffer(bool, i32, i32),
     PasteAsNewImage,
     PasteAsBrush,
+    SaveSelectionAsBrush,
     Copy,
     Cut,
     RemoveFloatingLayer,
@@ -87,6 +98,8 @@ pub enum Message {
     ScrollAreaDown,
     ScrollAreaLeft,
     ScrollAreaRight,
+    OffsetByHalf,
+    ToggleTileMode,
 
     SetReferenceImage,
     ToggleReferenceImage,
@@ -113,10 +126,14 @@ pub enum Message {
     OpenTdfDirectory,
     OpenPalettesDirectory,
     ToggleMirrorMode,
+    SetSymmetryMode(SymmetryMode),
+    ResetSymmetryCenter,
     ClearRecentOpenFiles,
     SetGuide(i32, i32),
     SetRaster(i32, i32),
     LoadFile(PathBuf, bool),
+    /// Document path & snapshot file
+    RestoreSnapshot(PathBuf, PathBuf),
     TryLoadFile(PathBuf),
     ClearLayer(usize),
     InverseSelection,
@@ -187,8 +204,7 @@ impl<'a> MainWindow<'a> {
             }
 
             Message::TryLoadFile(path) => {
-                let auto_save = autosave::get_autosave_file(&path);
-                if auto_save.exists() {
+                if autosave::needs_recovery(&path) {
                     self.open_dialog(crate::AutoSaveDialog::new(path));
                     return;
                 }
@@ -198,6 +214,9 @@ impl<'a> MainWindow<'a> {
             Message::LoadFile(path, load_autosave) => {
                 self.open_file(&path, load_autosave);
             }
+            Message::RestoreSnapshot(path, snapshot) => {
+                self.restore_snapshot(&path, &snapshot);
+            }
 
             Message::SaveFile => {
                 let msg = if let Some(pane) = self.get_active_pane_mut() {
@@ -286,6 +305,67 @@ impl<'a> MainWindow<'a> {
                 }
             }
 
+            Message::ShowBatchSauceDialog => {
+                self.open_dialog(crate::BatchSauceDialog::default());
+            }
+
+            Message::ShowFindReplaceDialog => {
+                self.run_editor_command(0, |window, editor, _| {
+                    window.open_dialog(crate::FindReplaceDialog::new(editor));
+                    None
+                });
+            }
+
+            Message::ShowRemapColorsDialog => {
+                self.run_editor_command(0, |window, editor, _| {
+                    window.open_dialog(crate::RemapColorsDialog::new(editor));
+                    None
+                });
+            }
+
+            Message::ShowConvertPaletteDialog => {
+                self.run_editor_command(0, |window, editor, _| {
+                    window.open_dialog(crate::ConvertPaletteDialog::new(editor));
+                    None
+                });
+            }
+
+            Message::ShowCompareDialog => {
+                let path = self.get_active_pane_mut().and_then(|pane| pane.get_path());
+                self.run_editor_command(path, |window, editor, path| {
+                    window.open_dialog(crate::CompareDialog::new(editor, path));
+                    None
+                });
+            }
+
+            Message::ShowDocumentHistoryDialog => {
+                if let Some(path) = self.get_active_pane_mut().and_then(|pane| pane.get_path()) {
+                    self.open_dialog(crate::DocumentHistoryDialog::new(path));
+                }
+            }
+
+            Message::ShowReleasePackDialog => {
+                let mut documents = Vec::new();
+                for (_, tile) in self.document_tree.tiles.iter() {
+                    if let egui_tiles::Tile::Pane(pane) = tile {
+                        let Some(path) = pane.get_path() else {
+                            continue;
+                        };
+                        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                        let mut doc = pane.doc.lock();
+                        // font editors and animations don't belong into an art pack
+                        if doc.get_ansi_editor().is_none() || !crate::util::release_pack::is_pack_document(&name) {
+                            continue;
+                        }
+                        match doc.get_bytes(&path) {
+                            Ok(data) => documents.push((name, data)),
+                            Err(err) => log::error!("Error getting document bytes: {err}"),
+                        }
+                    }
+                }
+                self.open_dialog(crate::ReleasePackDialog::new(documents));
+            }
+
             Message::SetCanvasSize => {
                 if let Some(doc) = self.get_active_document() {
                     if let Some(editor) = doc.lock().get_ansi_editor() {
@@ -489,17 +569,23 @@ impl<'a> MainWindow<'a> {
             }
 
             Message::FlipX => {
-                self.run_editor_command(0, |_, editor, _| {
-                    let mut lock = editor.buffer_view.lock();
-                    to_message(lock.get_edit_state_mut().flip_x())
-                });
+                self.run_editor_command(0, |_, editor, _| to_message(editor.flip_glyphs(true)));
             }
 
             Message::FlipY => {
-                self.run_editor_command(0, |_, editor, _| {
-                    let mut lock = editor.buffer_view.lock();
-                    to_message(lock.get_edit_state_mut().flip_y())
-                });
+                self.run_editor_command(0, |_, editor, _| to_message(editor.flip_glyphs(false)));
+            }
+
+            Message::Rotate90 => {
+                self.run_editor_command(1, |_, editor, turns| to_message(editor.rotate_glyphs(turns)));
+            }
+
+            Message::Rotate180 => {
+                self.run_editor_command(2, |_, editor, turns| to_message(editor.rotate_glyphs(turns)));
+            }
+
+            Message::Rotate270 => {
+                self.run_editor_command(3, |_, editor, turns| to_message(editor.rotate_glyphs(turns)));
             }
 
             Message::Crop => {
@@ -543,6 +629,14 @@ impl<'a> MainWindow<'a> {
                     }
                 }
             }
+            Message::SaveSelectionAsBrush => {
+                self.run_editor_command(0, |window, editor, _| {
+                    if editor.buffer_view.lock().get_edit_state().is_something_selected() {
+                        window.open_dialog(crate::SaveBrushDialog::default());
+                    }
+                    None
+                });
+            }
             Message::CloseWindow => {
                 self.is_closed = true;
             }
@@ -648,6 +742,15 @@ impl<'a> MainWindow<'a> {
                     to_message(lock.get_edit_state_mut().scroll_area_right())
                 });
             }
+            Message::OffsetByHalf => {
+                self.run_editor_command(0, |_, editor, _| to_message(editor.offset_by_half()));
+            }
+            Message::ToggleTileMode => {
+                self.run_editor_command(0, |_, editor, _| {
+                    editor.tile_mode = !editor.tile_mode;
+                    None
+                });
+            }
 
             Message::StampLayerDown => {
                 self.run_editor_command(0, |_, editor, _| {
@@ -657,10 +760,7 @@ impl<'a> MainWindow<'a> {
             }
 
             Message::RotateLayer => {
-                self.run_editor_command(0, |_, editor, _| {
-                    let mut lock = editor.buffer_view.lock();
-                    to_message(lock.get_edit_state_mut().rotate_layer())
-                });
+                self.run_editor_command(1, |_, editor, turns| to_message(editor.rotate_glyphs(turns)));
             }
 
             Message::MakeLayerTransparent => {
@@ -837,8 +937,21 @@ impl<'a> MainWindow<'a> {
 
             Message::ToggleMirrorMode => {
                 self.run_editor_command(0, |_, editor, _| {
-                    let mode = editor.buffer_view.lock().get_edit_state_mut().get_mirror_mode();
-                    editor.buffer_view.lock().get_edit_state_mut().set_mirror_mode(!mode);
+                    editor.symmetry.toggle();
+                    None
+                });
+            }
+
+            Message::SetSymmetryMode(mode) => {
+                self.run_editor_command(mode, |_, editor, mode| {
+                    editor.symmetry.set_mode(mode);
+                    None
+                });
+            }
+
+            Message::ResetSymmetryCenter => {
+                self.run_editor_command(0, |_, editor, _| {
+                    editor.symmetry.center = None;
                     None
                 });
             }
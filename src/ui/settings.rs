@@ -11,7 +11,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{plugins::Plugin, TerminalResult};
+use crate::{plugins::Plugin, util::glyph_map::GlyphMapping, TerminalResult};
 
 const MAX_RECENT_FILES: usize = 10;
 
@@ -32,6 +32,76 @@ pub struct Settings {
 
     #[serde(default)]
     scale: Vec2,
+
+    #[serde(default)]
+    pub web_export_options: WebExportOptions,
+
+    #[serde(default)]
+    pub terminal_export_options: TerminalExportOptions,
+
+    #[serde(default)]
+    pub auto_save_options: AutoSaveOptions,
+}
+
+/// Options of the HTML & SVG export, SaveOptions only covers the file formats of the engine.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebExportOptions {
+    /// Inline style attributes instead of CSS classes
+    pub inline_styles: bool,
+    /// Embed the document font as truetype data URI
+    pub embed_font: bool,
+    /// Animate blinking characters with CSS
+    pub blink: bool,
+}
+
+impl Default for WebExportOptions {
+    fn default() -> Self {
+        Self {
+            inline_styles: false,
+            embed_font: true,
+            blink: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalColorMode {
+    TrueColor,
+    Xterm256,
+}
+
+/// Options of the UTF-8 terminal export.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TerminalExportOptions {
+    pub color_mode: TerminalColorMode,
+    /// Cut lines at this column
+    pub clip_width: Option<i32>,
+}
+
+impl Default for TerminalExportOptions {
+    fn default() -> Self {
+        Self {
+            color_mode: TerminalColorMode::TrueColor,
+            clip_width: None,
+        }
+    }
+}
+
+/// Rolling autosave snapshots kept per document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AutoSaveOptions {
+    pub snapshot_count: usize,
+    /// Minutes a snapshot is updated before a new one is started
+    pub snapshot_interval: u64,
+}
+
+impl Default for AutoSaveOptions {
+    fn default() -> Self {
+        Self {
+            snapshot_count: 10,
+            snapshot_interval: 5,
+        }
+    }
 }
 
 impl Default for Settings {
@@ -46,6 +116,9 @@ impl Default for Settings {
             marker_settings: Default::default(),
             save_options: Default::default(),
             scale: Vec2::splat(2.0),
+            web_export_options: Default::default(),
+            terminal_export_options: Default::default(),
+            auto_save_options: Default::default(),
         }
     }
 }
@@ -124,6 +197,39 @@ impl CharacterSets {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GlyphMappings {
+    pub glyph_mappings: Vec<GlyphMapping>,
+}
+
+impl GlyphMappings {
+    pub fn get_glyph_mappings_file() -> TerminalResult<PathBuf> {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "GitHub", "icy_draw") {
+            let dir = proj_dirs.config_dir().join("glyph_mappings.json");
+            return Ok(dir);
+        }
+        Err(IcyDrawError::ErrorCreatingDirectory("glyph_mappings".to_string()).into())
+    }
+
+    pub fn load(path: &PathBuf) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        let u = serde_json::from_reader(reader)?;
+        Ok(u)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Ok(path) = GlyphMappings::get_glyph_mappings_file() else {
+            return Ok(());
+        };
+
+        let file = File::create(path)?;
+        let writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CharSetMapping {
     pub font_checksum: u32,
@@ -225,6 +331,18 @@ impl Settings {
         Err(IcyDrawError::ErrorCreatingDirectory("font directory".to_string()).into())
     }
 
+    pub(crate) fn get_brush_directory() -> TerminalResult<PathBuf> {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "GitHub", "icy_draw") {
+            let dir = proj_dirs.config_dir().join("data/brushes");
+
+            if !dir.exists() && fs::create_dir_all(&dir).is_err() {
+                return Err(IcyDrawError::ErrorCreatingDirectory(format!("{dir:?}")).into());
+            }
+            return Ok(dir);
+        }
+        Err(IcyDrawError::ErrorCreatingDirectory("brush directory".to_string()).into())
+    }
+
     pub(crate) fn get_auto_save_diretory() -> TerminalResult<PathBuf> {
         if let Some(proj_dirs) = ProjectDirs::from("com", "GitHub", "icy_draw") {
             let dir = proj_dirs.config_dir().join("autosave");
@@ -392,6 +510,8 @@ pub static mut KEYBINDINGS: KeyBindings = KeyBindings { key_bindings: Vec::new()
 
 pub static mut CHARACTER_SETS: CharacterSets = CharacterSets { character_sets: Vec::new() };
 
+pub static mut GLYPH_MAPPINGS: GlyphMappings = GlyphMappings { glyph_mappings: Vec::new() };
+
 pub static mut MRU_FILES: MostRecentlyUsedFiles = MostRecentlyUsedFiles { files: Vec::new() };
 
 pub static mut SETTINGS: Settings = Settings {
@@ -425,6 +545,19 @@ pub static mut SETTINGS: Settings = Settings {
         guide_color: Color::new(0xAB, 0xAB, 0xAB),
     },
     scale: Vec2::splat(2.0),
+    web_export_options: WebExportOptions {
+        inline_styles: false,
+        embed_font: true,
+        blink: true,
+    },
+    terminal_export_options: TerminalExportOptions {
+        color_mode: TerminalColorMode::TrueColor,
+        clip_width: None,
+    },
+    auto_save_options: AutoSaveOptions {
+        snapshot_count: 10,
+        snapshot_interval: 5,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -6,7 +6,7 @@ use eframe::{
 };
 use egui::{load::SizedTexture, mutex::Mutex, Context, Image, TextureHandle};
 use i18n_embed_fl::fl;
-use icy_engine::{AttributedChar, BitFont, Buffer, TextAttribute};
+use icy_engine::{AttributedChar, BitFont, Buffer, BufferType, TextAttribute};
 
 use crate::{create_image, AnsiEditor, Document, Message, ToolWindow};
 
@@ -16,6 +16,8 @@ pub struct CharTableToolWindow {
     hover_char_image: TextureHandle,
     char_table: TextureHandle,
     buffer_width: usize,
+    /// PETSCII fonts are ordered by screen code, the hover info shows the matching PETSCII code
+    is_petscii: bool,
 }
 
 impl CharTableToolWindow {
@@ -29,6 +31,7 @@ impl CharTableToolWindow {
             hover_char: None,
             hover_char_image,
             buffer_width,
+            is_petscii: false,
         }
     }
 
@@ -101,6 +104,15 @@ impl CharTableToolWindow {
                 ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "font-view-char_label")).small());
                 ui.label(RichText::new(format!("{0}/0x{0:02X}", ch as u32)).small().color(Color32::WHITE));
             });
+            if self.is_petscii {
+                ui.horizontal(|ui| {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(fl!(crate::LANGUAGE_LOADER, "font-view-petscii_label")).small());
+                    let petscii = crate::util::petscii::screen_code_to_petscii(ch as u8);
+                    let reverse = if (ch as u32) >= 0x80 { " RVS" } else { "" };
+                    ui.label(RichText::new(format!("0x{petscii:02X}{reverse}")).small().color(Color32::WHITE));
+                });
+            }
         } else {
             ui.horizontal(|ui| {
                 ui.label("   ");
@@ -119,6 +131,7 @@ impl CharTableToolWindow {
         let mut result = None;
 
         let font_page = editor.buffer_view.lock().get_caret().get_font_page();
+        self.is_petscii = matches!(editor.buffer_view.lock().get_buffer().buffer_type, BufferType::Petscii);
         let font_count = editor.buffer_view.lock().get_buffer().font_count();
         if let Some(cur_font) = editor.buffer_view.lock().get_buffer().get_font(font_page) {
             if cur_font.name != self.font.name {
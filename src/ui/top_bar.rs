@@ -11,7 +11,7 @@ use icy_engine::{
     FontMode, IceMode, PaletteMode,
 };
 
-use crate::{button_with_shortcut, MainWindow, Message, Settings, LATEST_VERSION, MRU_FILES, PLUGINS, SETTINGS, VERSION};
+use crate::{button_with_shortcut, MainWindow, Message, Settings, SymmetryMode, LATEST_VERSION, MRU_FILES, PLUGINS, SETTINGS, VERSION};
 
 lazy_static::lazy_static! {
     pub static ref DOCK_LEFT_SVG: Image<'static> = Image::new(egui::include_image!("../../data/icons/dock_left.svg"));
@@ -44,6 +44,7 @@ impl<'a> MainWindow<'a> {
             let mut has_reference_image = false;
             let mut cur_raster = Some(Vec2::new(f32::NAN, f32::NAN));
             let mut cur_guide = Some(Vec2::new(f32::NAN, f32::NAN));
+            let mut cur_symmetry = SymmetryMode::Off;
 
             if self.last_command_update.elapsed().as_millis() > 250 {
                 let mut c = self.commands.pop().unwrap();
@@ -61,6 +62,7 @@ impl<'a> MainWindow<'a> {
                     has_buffer = true;
                     cur_raster = editor.raster;
                     cur_guide = editor.guide;
+                    cur_symmetry = editor.symmetry.mode;
                     has_reference_image = editor.buffer_view.lock().has_reference_image();
                 } else {
                     has_buffer = false;
@@ -92,6 +94,12 @@ impl<'a> MainWindow<'a> {
                 self.commands[0].save.ui(ui, &mut result);
                 self.commands[0].save_as.ui(ui, &mut result);
                 self.commands[0].export.ui(ui, &mut result);
+                self.commands[0].compare.ui(ui, &mut result);
+                self.commands[0].document_history.ui(ui, &mut result);
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "menu-release-pack")).clicked() {
+                    result = Some(Message::ShowReleasePackDialog);
+                    ui.close_menu();
+                }
                 ui.separator();
                 self.commands[0].show_settings.ui(ui, &mut result);
                 ui.separator();
@@ -169,6 +177,7 @@ impl<'a> MainWindow<'a> {
                     }
                 });
                 ui.separator();
+                self.commands[0].find_replace.ui(ui, &mut result);
                 ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-area_operations"), |ui| {
                     ui.style_mut().wrap = Some(false);
                     ui.set_min_width(300.0);
@@ -195,8 +204,42 @@ impl<'a> MainWindow<'a> {
                     self.commands[0].scroll_area_down.ui(ui, &mut result);
                     self.commands[0].scroll_area_left.ui(ui, &mut result);
                     self.commands[0].scroll_area_right.ui(ui, &mut result);
+                    self.commands[0].offset_by_half.ui(ui, &mut result);
                 });
                 self.commands[0].mirror_mode.ui(ui, &mut result);
+                ui.add_enabled_ui(has_buffer, |ui| {
+                    ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-symmetry"), |ui| {
+                        let modes = [
+                            (SymmetryMode::Off, fl!(crate::LANGUAGE_LOADER, "menu-symmetry-off")),
+                            (SymmetryMode::Horizontal, fl!(crate::LANGUAGE_LOADER, "menu-symmetry-horizontal")),
+                            (SymmetryMode::Vertical, fl!(crate::LANGUAGE_LOADER, "menu-symmetry-vertical")),
+                            (SymmetryMode::Quad, fl!(crate::LANGUAGE_LOADER, "menu-symmetry-quad")),
+                        ];
+                        for (mode, label) in modes {
+                            if ui.selectable_label(cur_symmetry == mode, label).clicked() {
+                                result = Some(Message::SetSymmetryMode(mode));
+                                ui.close_menu();
+                            }
+                        }
+                        for n in [3, 4, 6, 8] {
+                            if ui
+                                .selectable_label(
+                                    cur_symmetry == SymmetryMode::Radial(n),
+                                    fl!(crate::LANGUAGE_LOADER, "menu-symmetry-radial", count = n),
+                                )
+                                .clicked()
+                            {
+                                result = Some(Message::SetSymmetryMode(SymmetryMode::Radial(n)));
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                        if ui.button(fl!(crate::LANGUAGE_LOADER, "menu-symmetry-reset-center")).clicked() {
+                            result = Some(Message::ResetSymmetryCenter);
+                            ui.close_menu();
+                        }
+                    });
+                });
 
                 ui.separator();
                 if ui
@@ -206,6 +249,10 @@ impl<'a> MainWindow<'a> {
                     result = Some(Message::EditSauce);
                     ui.close_menu();
                 }
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "menu-batch-sauce")).clicked() {
+                    result = Some(Message::ShowBatchSauceDialog);
+                    ui.close_menu();
+                }
                 self.commands[0].lga_font.ui(ui, &mut result);
                 self.commands[0].aspect_ratio.ui(ui, &mut result);
                 ui.separator();
@@ -229,10 +276,18 @@ impl<'a> MainWindow<'a> {
                 self.commands[0].erase_selection.ui(ui, &mut result);
                 self.commands[0].flip_x.ui(ui, &mut result);
                 self.commands[0].flip_y.ui(ui, &mut result);
+                self.commands[0].rotate_90.ui(ui, &mut result);
+                self.commands[0].rotate_180.ui(ui, &mut result);
+                self.commands[0].rotate_270.ui(ui, &mut result);
                 self.commands[0].justifycenter.ui(ui, &mut result);
                 self.commands[0].justifyleft.ui(ui, &mut result);
                 self.commands[0].justifyright.ui(ui, &mut result);
                 self.commands[0].crop.ui(ui, &mut result);
+                ui.separator();
+                if ui.button(fl!(crate::LANGUAGE_LOADER, "menu-save-brush")).clicked() {
+                    result = Some(Message::SaveSelectionAsBrush);
+                    ui.close_menu();
+                }
             });
             ui.menu_button(fl!(crate::LANGUAGE_LOADER, "menu-colors"), |ui| {
                 ui.style_mut().wrap = Some(false);
@@ -328,6 +383,8 @@ impl<'a> MainWindow<'a> {
                 }
                 self.commands[0].select_palette.ui(ui, &mut result);
                 self.commands[0].open_palettes_directory.ui(ui, &mut result);
+                self.commands[0].remap_colors.ui(ui, &mut result);
+                self.commands[0].convert_palette.ui(ui, &mut result);
                 ui.separator();
 
                 self.commands[0].next_fg_color.ui(ui, &mut result);
@@ -522,6 +579,7 @@ impl<'a> MainWindow<'a> {
 
                 self.commands[0].show_layer_borders.ui(ui, &mut result);
                 self.commands[0].show_line_numbers.ui(ui, &mut result);
+                self.commands[0].tile_mode.ui(ui, &mut result);
 
                 self.commands[0].fullscreen.ui(ui, &mut result);
 
@@ -1,37 +1,270 @@
-//use rs_sha256::Sha256Hasher;
 use std::{
     fs,
-    //  hash::Hasher,
-    path::{self, Path, PathBuf},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use icy_engine::get_crc32;
+use serde::{Deserialize, Serialize};
 
-use crate::Settings;
+use super::date::civil_from_days;
+use crate::{Settings, TerminalResult, SETTINGS};
 
-pub fn get_autosave_file(path: &Path) -> PathBuf {
-    let auto_save_directory = Settings::get_auto_save_diretory().unwrap();
-    //    let mut sha256hasher = Sha256Hasher::default();
-    //    sha256hasher.write(path.as_os_str().to_str().unwrap().as_bytes());
-    //    let u64result = sha256hasher.finish();
+const INDEX_FILE: &str = "index.json";
+const SNAPSHOT_EXT: &str = "sav";
+/// Paths sharing a crc32 get numbered directories, more than a few are never expected.
+const MAX_COLLISIONS: usize = 64;
+/// Autosaves of documents that no longer exist are removed on startup once they weren't touched for this many days.
+const ORPHAN_EXPIRY_DAYS: u64 = 30;
 
-    // crc32 should be enough -if not the alternative rs_sha256 is available
-    let key = get_crc32(path.as_os_str().to_string_lossy().as_bytes());
-    auto_save_directory.join(path::Path::new(format!("{:x}.sav", key).as_str()))
+/// Stored next to the snapshots, maps an autosave directory back to its document.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct AutoSaveIndex {
+    path: PathBuf,
+    /// Set by autosaves, cleared when the document is saved or the changes are discarded.
+    needs_recovery: bool,
 }
 
-pub fn remove_autosave(path: &Path) {
-    let file = get_autosave_file(path);
-    if file.exists() {
-        if let Err(err) = fs::remove_file(file) {
-            log::error!("Failed to remove autosave file: {}", err);
+fn read_index(dir: &Path) -> Option<AutoSaveIndex> {
+    let data = fs::read(dir.join(INDEX_FILE)).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+fn write_index(dir: &Path, index: &AutoSaveIndex) -> TerminalResult<()> {
+    fs::write(dir.join(INDEX_FILE), serde_json::to_vec_pretty(index)?)?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub file: PathBuf,
+    /// Seconds since the epoch the snapshot was started
+    pub created: u64,
+    /// Seconds since the epoch of the last write
+    pub modified: u64,
+}
+
+impl Snapshot {
+    /// Last write as "YYYY-MM-DD HH:MM:SS UTC".
+    pub fn modified_text(&self) -> String {
+        let (year, month, day) = civil_from_days((self.modified / 86400) as i64);
+        let secs = self.modified % 86400;
+        format!("{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+fn modified_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    modified.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Newest modification time of a file or of a directory and its files.
+fn last_modified(path: &Path) -> u64 {
+    let own = modified_secs(path).unwrap_or_default();
+    let Ok(entries) = fs::read_dir(path) else {
+        return own;
+    };
+    entries.flatten().filter_map(|entry| modified_secs(&entry.path())).fold(own, u64::max)
+}
+
+/// Snapshot files are named by the second they were started, a restore may write twice in the same second.
+fn new_snapshot_file(dir: &Path, mut created: u64) -> PathBuf {
+    while dir.join(format!("{created}.{SNAPSHOT_EXT}")).exists() {
+        created += 1;
+    }
+    dir.join(format!("{created}.{SNAPSHOT_EXT}"))
+}
+
+/// Directory holding the snapshots of a document.
+/// The crc32 of the path is only the name, the path stored in the index decides which directory belongs to the document.
+pub fn get_autosave_dir(path: &Path) -> TerminalResult<PathBuf> {
+    let auto_save_directory = Settings::get_auto_save_diretory()?;
+    let crc = get_crc32(path.as_os_str().to_string_lossy().as_bytes());
+    let dir = find_autosave_dir(&auto_save_directory, &format!("{crc:08x}"), path)?;
+    let legacy = auto_save_directory.join(format!("{crc:x}.{SNAPSHOT_EXT}"));
+    if legacy.is_file() {
+        if let Err(err) = migrate_legacy_autosave(&legacy, &dir, path) {
+            log::error!("Failed to migrate autosave {}: {}", legacy.display(), err);
+        }
+    }
+    Ok(dir)
+}
+
+/// Older versions kept a single `{crc32}.sav` file per document that was removed on save, it becomes a snapshot that needs recovery.
+fn migrate_legacy_autosave(legacy: &Path, dir: &Path, path: &Path) -> TerminalResult<()> {
+    fs::create_dir_all(dir)?;
+    let created = modified_secs(legacy).unwrap_or_else(now);
+    fs::rename(legacy, new_snapshot_file(dir, created))?;
+    write_index(
+        dir,
+        &AutoSaveIndex {
+            path: path.to_path_buf(),
+            needs_recovery: true,
+        },
+    )
+}
+
+fn find_autosave_dir(auto_save_directory: &Path, key: &str, path: &Path) -> TerminalResult<PathBuf> {
+    let mut free = None;
+    for i in 0..MAX_COLLISIONS {
+        let dir = if i == 0 {
+            auto_save_directory.join(key)
+        } else {
+            auto_save_directory.join(format!("{key}_{i}"))
+        };
+        match read_index(&dir) {
+            Some(index) if index.path == path => return Ok(dir),
+            Some(_) => {}
+            None => {
+                if free.is_none() {
+                    free = Some(dir);
+                }
+            }
+        }
+    }
+    free.ok_or_else(|| anyhow::anyhow!("too many autosave collisions for {}", path.display()))
+}
+
+/// All snapshots of a document, newest first.
+pub fn get_snapshots(path: &Path) -> Vec<Snapshot> {
+    let Ok(dir) = get_autosave_dir(path) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut result: Vec<Snapshot> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.path();
+            if file.extension().map_or(true, |ext| ext != SNAPSHOT_EXT) {
+                return None;
+            }
+            let created = file.file_stem()?.to_str()?.parse::<u64>().ok()?;
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(created, |d| d.as_secs());
+            Some(Snapshot { file, created, modified })
+        })
+        .collect();
+    result.sort_by(|a, b| b.created.cmp(&a.created));
+    result
+}
+
+pub fn get_latest_snapshot(path: &Path) -> Option<Snapshot> {
+    get_snapshots(path).into_iter().next()
+}
+
+/// True if the document was autosaved and neither saved nor discarded afterwards.
+pub fn needs_recovery(path: &Path) -> bool {
+    let Ok(dir) = get_autosave_dir(path) else {
+        return false;
+    };
+    read_index(&dir).map_or(false, |index| index.needs_recovery) && get_latest_snapshot(path).is_some()
+}
+
+/// Keeps the snapshots for the history, only the recovery on the next load is cancelled.
+pub fn discard_recovery(path: &Path) {
+    let Ok(dir) = get_autosave_dir(path) else {
+        return;
+    };
+    if let Some(mut index) = read_index(&dir) {
+        index.needs_recovery = false;
+        if let Err(err) = write_index(&dir, &index) {
+            log::error!("Failed to update autosave index: {}", err);
         }
     }
 }
 
+/// Updates the newest snapshot or starts a new one once the configured interval has passed.
 pub fn store_auto_save(path: &Path, data: &[u8]) {
-    let auto_save = get_autosave_file(path);
-    if let Err(err) = fs::write(auto_save, data) {
+    let interval = unsafe { SETTINGS.auto_save_options.snapshot_interval } * 60;
+    let now = now();
+    let file = match get_latest_snapshot(path) {
+        Some(snapshot) if now.saturating_sub(snapshot.created) < interval => Some(snapshot.file),
+        _ => None,
+    };
+    if let Err(err) = write_snapshot(path, data, file) {
+        log::error!("Failed to save autosave file: {}", err);
+    }
+}
+
+/// Always starts a new snapshot, used before the document content gets replaced.
+pub fn store_snapshot(path: &Path, data: &[u8]) {
+    if let Err(err) = write_snapshot(path, data, None) {
         log::error!("Failed to save autosave file: {}", err);
     }
 }
+
+fn write_snapshot(path: &Path, data: &[u8], file: Option<PathBuf>) -> TerminalResult<()> {
+    let dir = get_autosave_dir(path)?;
+    fs::create_dir_all(&dir)?;
+    write_index(
+        &dir,
+        &AutoSaveIndex {
+            path: path.to_path_buf(),
+            needs_recovery: true,
+        },
+    )?;
+
+    let file = file.unwrap_or_else(|| new_snapshot_file(&dir, now()));
+    fs::write(file, data)?;
+
+    let count = unsafe { SETTINGS.auto_save_options.snapshot_count }.max(1);
+    for snapshot in get_snapshots(path).into_iter().skip(count) {
+        fs::remove_file(snapshot.file)?;
+    }
+    Ok(())
+}
+
+/// Removes autosaves of documents that no longer exist and returns the number of removed entries.
+/// Single `{crc32}.sav` files of older versions that weren't migrated yet are removed as well, they can't be mapped back to a document.
+pub fn cleanup_orphaned_autosaves() -> usize {
+    remove_orphaned_autosaves(true, 0)
+}
+
+/// Run on startup, removes autosaves of documents that no longer exist once they expired.
+/// Files of older versions are kept, they're migrated when their document is opened.
+pub fn expire_orphaned_autosaves() -> usize {
+    remove_orphaned_autosaves(false, ORPHAN_EXPIRY_DAYS * 24 * 60 * 60)
+}
+
+/// `min_age` is the time in seconds since the last change of an entry.
+fn remove_orphaned_autosaves(include_legacy: bool, min_age: u64) -> usize {
+    let Ok(auto_save_directory) = Settings::get_auto_save_diretory() else {
+        return 0;
+    };
+    let Ok(entries) = fs::read_dir(auto_save_directory) else {
+        return 0;
+    };
+    let now = now();
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if now.saturating_sub(last_modified(&entry_path)) < min_age {
+            continue;
+        }
+        let result = if entry_path.is_dir() {
+            if read_index(&entry_path).map_or(false, |index| index.path.exists()) {
+                continue;
+            }
+            fs::remove_dir_all(&entry_path)
+        } else if include_legacy && entry_path.extension().map_or(false, |ext| ext == SNAPSHOT_EXT) {
+            fs::remove_file(&entry_path)
+        } else {
+            continue;
+        };
+        match result {
+            Ok(()) => removed += 1,
+            Err(err) => log::error!("Failed to remove autosave {}: {}", entry_path.display(), err),
+        }
+    }
+    removed
+}
@@ -0,0 +1,94 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use icy_engine::{Buffer, Layer, SaveOptions, TextPane};
+
+use crate::{Settings, TerminalResult};
+
+const BRUSH_EXT: &str = "icy";
+
+pub struct LibraryBrush {
+    pub name: String,
+    pub path: PathBuf,
+    pub layer: Layer,
+}
+
+/// Brushes are stored as single layer icy draw files in the brush directory.
+pub fn load_brushes() -> Vec<LibraryBrush> {
+    let Ok(dir) = Settings::get_brush_directory() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut result = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| !ext.eq_ignore_ascii_case(BRUSH_EXT)) {
+            continue;
+        }
+        match load_brush(&path) {
+            Ok(layer) => {
+                let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                result.push(LibraryBrush { name, path, layer });
+            }
+            Err(err) => log::error!("Error loading brush {path:?}: {err}"),
+        }
+    }
+    result.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    result
+}
+
+fn load_brush(path: &Path) -> TerminalResult<Layer> {
+    let data = fs::read(path)?;
+    let buf = Buffer::from_bytes(path, true, &data)?;
+    let Some(layer) = buf.layers.first() else {
+        return Err(anyhow::anyhow!("brush contains no layer"));
+    };
+    Ok(layer.clone())
+}
+
+/// Characters that aren't allowed in file names are replaced by '_'.
+fn file_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if name.is_empty() {
+        "brush".to_string()
+    } else {
+        name
+    }
+}
+
+pub fn brush_path(name: &str) -> TerminalResult<PathBuf> {
+    Ok(Settings::get_brush_directory()?.join(format!("{}.{BRUSH_EXT}", file_name(name))))
+}
+
+/// Fails if a brush with the same file name exists, unless `overwrite` is set.
+pub fn save_brush(name: &str, layer: &Layer, overwrite: bool) -> TerminalResult<PathBuf> {
+    let path = brush_path(name)?;
+    if path.exists() && !overwrite {
+        return Err(anyhow::anyhow!("brush {path:?} already exists"));
+    }
+
+    let mut layer = layer.clone();
+    layer.set_offset((0, 0));
+    layer.role = icy_engine::Role::Normal;
+    layer.set_title(name);
+
+    let mut buf = Buffer::new(layer.get_size());
+    buf.layers.clear();
+    buf.layers.push(layer);
+    let bytes = buf.to_bytes(BRUSH_EXT, &SaveOptions::default())?;
+    fs::write(&path, bytes)?;
+    Ok(path)
+}
+
+pub fn delete_brush(brush: &LibraryBrush) -> TerminalResult<()> {
+    fs::remove_file(&brush.path)?;
+    Ok(())
+}
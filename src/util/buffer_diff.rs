@@ -0,0 +1,169 @@
+use icy_engine::{AttributedChar, Buffer, Layer, Position, TextAttribute, TextPane};
+
+/// What differs between two cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CellDifference {
+    pub ch: bool,
+    pub colors: bool,
+    pub font_page: bool,
+}
+
+impl CellDifference {
+    pub fn between(a: AttributedChar, b: AttributedChar) -> Self {
+        let (a, b) = (normalize(a), normalize(b));
+        Self {
+            ch: a.ch != b.ch,
+            colors: a.attribute.get_foreground() != b.attribute.get_foreground() || a.attribute.get_background() != b.attribute.get_background(),
+            font_page: a.attribute.get_font_page() != b.attribute.get_font_page(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.ch && !self.colors && !self.font_page
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            ch: self.ch || other.ch,
+            colors: self.colors || other.colors,
+            font_page: self.font_page || other.font_page,
+        }
+    }
+}
+
+/// Invisible cells and cells outside of the buffer look like a default space.
+pub fn normalize(ch: AttributedChar) -> AttributedChar {
+    if !ch.is_visible() {
+        return AttributedChar::new(' ', TextAttribute::default());
+    }
+    if ch.ch == '\0' {
+        return AttributedChar::new(' ', ch.attribute);
+    }
+    ch
+}
+
+/// Visible cell of a buffer, all layers merged.
+pub fn get_merged_char(buffer: &Buffer, pos: Position) -> AttributedChar {
+    if pos.x >= buffer.get_width() || pos.y >= buffer.get_height() {
+        return AttributedChar::invisible();
+    }
+    buffer.get_char(pos)
+}
+
+fn covers(layer: &Layer, pos: Position) -> bool {
+    let pos = pos - layer.get_offset();
+    pos.x >= 0 && pos.y >= 0 && pos.x < layer.get_width() && pos.y < layer.get_height()
+}
+
+/// Layer showing the cell at `pos`: the topmost visible layer with a visible char there.
+/// Empty cells belong to the lowest layer covering `pos`.
+pub fn get_visible_layer(buffer: &Buffer, pos: Position) -> Option<usize> {
+    buffer
+        .layers
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, layer)| layer.get_is_visible() && covers(layer, pos) && layer.get_char(pos - layer.get_offset()).is_visible())
+        .or_else(|| buffer.layers.iter().enumerate().find(|(_, layer)| covers(layer, pos)))
+        .map(|(i, _)| i)
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineDifference {
+    pub y: i32,
+    pub cells: Vec<(i32, CellDifference)>,
+}
+
+impl LineDifference {
+    pub fn summary(&self) -> CellDifference {
+        self.cells.iter().fold(CellDifference::default(), |sum, (_, diff)| sum.union(*diff))
+    }
+}
+
+pub fn compare_buffers(left: &Buffer, right: &Buffer) -> Vec<LineDifference> {
+    let width = left.get_width().max(right.get_width());
+    let height = left.get_height().max(right.get_height());
+    let mut result = Vec::new();
+    for y in 0..height {
+        let mut line = LineDifference { y, cells: Vec::new() };
+        for x in 0..width {
+            let pos = Position::new(x, y);
+            let diff = CellDifference::between(get_merged_char(left, pos), get_merged_char(right, pos));
+            if !diff.is_empty() {
+                line.cells.push((x, diff));
+            }
+        }
+        if !line.cells.is_empty() {
+            result.push(line);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CH: CellDifference = CellDifference {
+        ch: true,
+        colors: false,
+        font_page: false,
+    };
+    const COLORS: CellDifference = CellDifference {
+        ch: false,
+        colors: true,
+        font_page: false,
+    };
+
+    #[test]
+    fn test_compare_equal_buffers() {
+        assert!(compare_buffers(&Buffer::new((10, 5)), &Buffer::new((10, 5))).is_empty());
+    }
+
+    #[test]
+    fn test_compare_char_and_color() {
+        let left = Buffer::new((10, 5));
+        let mut right = Buffer::new((10, 5));
+        right.layers[0].set_char((3, 2), AttributedChar::new('A', TextAttribute::default()));
+        let mut attr = TextAttribute::default();
+        attr.set_foreground(4);
+        right.layers[0].set_char((5, 4), AttributedChar::new(' ', attr));
+
+        let diff = compare_buffers(&left, &right);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff[0].y, 2);
+        assert_eq!(diff[0].cells, vec![(3, CH)]);
+        assert_eq!(diff[1].y, 4);
+        assert_eq!(diff[1].cells, vec![(5, COLORS)]);
+        assert_eq!(diff[1].summary(), COLORS);
+    }
+
+    #[test]
+    fn test_compare_different_sizes() {
+        let left = Buffer::new((10, 5));
+        let mut right = Buffer::new((12, 6));
+        assert!(compare_buffers(&left, &right).is_empty());
+
+        right.layers[0].set_char((11, 5), AttributedChar::new('A', TextAttribute::default()));
+        let diff = compare_buffers(&left, &right);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].y, 5);
+        assert_eq!(diff[0].cells, vec![(11, CH)]);
+    }
+
+    #[test]
+    fn test_visible_layer() {
+        let mut buf = Buffer::new((10, 5));
+        let mut top = Layer::new("top", (4, 3));
+        top.set_offset((2, 1));
+        top.set_char((1, 1), AttributedChar::new('X', TextAttribute::default()));
+        buf.layers.push(top);
+
+        assert_eq!(get_visible_layer(&buf, Position::new(3, 2)), Some(1));
+        assert_eq!(get_visible_layer(&buf, Position::new(0, 0)), Some(0));
+        assert_eq!(get_visible_layer(&buf, Position::new(20, 20)), None);
+
+        buf.layers[1].properties.is_visible = false;
+        assert_eq!(get_visible_layer(&buf, Position::new(3, 2)), Some(0));
+    }
+}
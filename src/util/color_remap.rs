@@ -0,0 +1,60 @@
+use icy_engine::{AttributedChar, TextAttribute};
+
+/// How often a palette index is used as foreground and background color.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorUsage {
+    pub index: u32,
+    pub fg_count: usize,
+    pub bg_count: usize,
+}
+
+pub fn count_color_usage<'a>(chars: impl Iterator<Item = &'a AttributedChar>) -> Vec<ColorUsage> {
+    let mut usage: Vec<ColorUsage> = Vec::new();
+    let mut entry = |usage: &mut Vec<ColorUsage>, index: u32| -> usize {
+        if let Some(i) = usage.iter().position(|u| u.index == index) {
+            return i;
+        }
+        usage.push(ColorUsage { index, ..Default::default() });
+        usage.len() - 1
+    };
+    for ch in chars {
+        let i = entry(&mut usage, ch.attribute.get_foreground());
+        usage[i].fg_count += 1;
+        let i = entry(&mut usage, ch.attribute.get_background());
+        usage[i].bg_count += 1;
+    }
+    usage.sort_by_key(|u| u.index);
+    usage
+}
+
+/// Target index for every source index, foreground and background are mapped separately.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColorMapping {
+    pub foreground: Vec<(u32, u32)>,
+    pub background: Vec<(u32, u32)>,
+}
+
+impl ColorMapping {
+    pub fn identity(usage: &[ColorUsage]) -> Self {
+        Self {
+            foreground: usage.iter().filter(|u| u.fg_count > 0).map(|u| (u.index, u.index)).collect(),
+            background: usage.iter().filter(|u| u.bg_count > 0).map(|u| (u.index, u.index)).collect(),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.foreground.iter().chain(self.background.iter()).all(|(from, to)| from == to)
+    }
+
+    fn lookup(map: &[(u32, u32)], color: u32) -> u32 {
+        map.iter().find(|(from, _)| *from == color).map_or(color, |(_, to)| *to)
+    }
+
+    /// Same attribute operation the colorize brush does, only the colors change.
+    pub fn apply(&self, ch: AttributedChar) -> AttributedChar {
+        let mut attribute: TextAttribute = ch.attribute;
+        attribute.set_foreground(Self::lookup(&self.foreground, ch.attribute.get_foreground()));
+        attribute.set_background(Self::lookup(&self.background, ch.attribute.get_background()));
+        AttributedChar::new(ch.ch, attribute)
+    }
+}
@@ -0,0 +1,138 @@
+use icy_engine::{ascii::CP437Converter, FontGlyph, FontType, Size, TheDrawFont, UnicodeConverter};
+
+use crate::TerminalResult;
+
+/// Hard blanks are converted to this char (CP437 0xFF renders blank), it's not transparent so fitting/smushing can't cross it.
+pub const HARD_BLANK: char = '\u{FF}';
+
+/// Letter layout for fonts which don't define spacing (FIGlet fonts converted to block fonts)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FigletLayout {
+    /// Glyphs are placed with their full width
+    FullWidth,
+    /// Glyphs are moved together until they touch
+    Kerning,
+    /// Glyphs are moved together one column further and touching chars are merged
+    Smushing,
+}
+
+/// Loads a FIGlet (.flf) or TOIlet (.tlf) font and converts it to a TheDraw block font.
+/// Spec: <http://www.jave.de/figlet/figfont.html>
+pub fn load_figlet_font(name: &str, data: &[u8]) -> TerminalResult<TheDrawFont> {
+    let txt = String::from_utf8_lossy(data);
+    let mut lines = txt.lines();
+    let Some(header) = lines.next() else {
+        return Err(anyhow::anyhow!("empty figlet font"));
+    };
+    if !header.starts_with("flf2a") && !header.starts_with("tlf2a") {
+        return Err(anyhow::anyhow!("invalid figlet font signature"));
+    }
+    let Some(hard_blank) = header.chars().nth(5) else {
+        return Err(anyhow::anyhow!("invalid figlet header"));
+    };
+    let params: Vec<i32> = header[5 + hard_blank.len_utf8()..]
+        .split_whitespace()
+        .map(|p| p.parse::<i32>().unwrap_or_default())
+        .collect();
+    if params.len() < 5 {
+        return Err(anyhow::anyhow!("invalid figlet header"));
+    }
+    let height = params[0].max(1) as usize;
+    let comment_lines = params[4].max(0) as usize;
+    for _ in 0..comment_lines {
+        lines.next();
+    }
+
+    let converter = CP437Converter::default();
+    let mut font = TheDrawFont::new(name.to_string(), FontType::Block, 0);
+
+    // the required chars are 32-126 in order, code tagged chars follow but these don't fit into a TheDraw font.
+    for ch in b' '..=b'~' {
+        let mut rows = Vec::new();
+        for _ in 0..height {
+            let Some(line) = lines.next() else {
+                return Ok(font);
+            };
+            rows.push(strip_end_marks(line));
+        }
+        if ch == b' ' {
+            continue;
+        }
+
+        let mut data = Vec::new();
+        let mut width = 0;
+        for (y, row) in rows.iter().enumerate() {
+            if y > 0 {
+                data.push(13);
+            }
+            for c in row.chars() {
+                let c = if c == hard_blank {
+                    HARD_BLANK
+                } else if (c as u32) < 0x80 {
+                    c
+                } else {
+                    converter.convert_from_unicode(c, 0)
+                };
+                data.push(c as u8);
+            }
+            width = width.max(row.chars().count());
+        }
+        if width > 0 {
+            font.set_glyph(
+                ch as char,
+                FontGlyph {
+                    size: Size::new(width as i32, height as i32),
+                    data,
+                },
+            );
+        }
+    }
+    Ok(font)
+}
+
+/// Each line ends with an end mark (usually '@'), the last line of a char with two.
+fn strip_end_marks(line: &str) -> String {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let Some(end_mark) = line.chars().last() else {
+        return String::new();
+    };
+    line.trim_end_matches(end_mark).to_string()
+}
+
+pub fn is_figlet_extension(ext: &str) -> bool {
+    ext == "flf" || ext == "tlf"
+}
+
+/// Applies the FIGlet controlled smushing rules (equal char, underscore, hierarchy, opposite pair, big x, hard blank).
+/// Returns None if the two chars can't be smushed.
+pub fn smush(left: char, right: char) -> Option<char> {
+    if left == HARD_BLANK || right == HARD_BLANK {
+        return if left == right { Some(HARD_BLANK) } else { None };
+    }
+    if left == right {
+        return Some(left);
+    }
+    const UNDERSCORE_REPLACEMENTS: &str = "|/\\[]{}()<>";
+    if left == '_' && UNDERSCORE_REPLACEMENTS.contains(right) {
+        return Some(right);
+    }
+    if right == '_' && UNDERSCORE_REPLACEMENTS.contains(left) {
+        return Some(left);
+    }
+
+    const HIERARCHY: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+    let class = |c: char| HIERARCHY.iter().position(|h| h.contains(c));
+    if let (Some(l), Some(r)) = (class(left), class(right)) {
+        if l != r {
+            return Some(if l > r { left } else { right });
+        }
+    }
+
+    match (left, right) {
+        ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '(') => Some('|'),
+        ('/', '\\') => Some('|'),
+        ('\\', '/') => Some('Y'),
+        ('>', '<') => Some('X'),
+        _ => None,
+    }
+}
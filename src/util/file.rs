@@ -0,0 +1,19 @@
+use std::{fs, path::Path};
+
+use crate::TerminalResult;
+
+/// Lets `write` create a temporary file next to `path` and moves it over `path` when it succeeded.
+/// An existing file stays intact if writing fails, the temporary file is removed then.
+pub fn write_atomically<T>(path: &Path, write: impl FnOnce(&Path) -> TerminalResult<T>) -> TerminalResult<T> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_file = path.with_file_name(tmp_name);
+    let result = write(&tmp_file).and_then(|value| {
+        fs::rename(&tmp_file, path)?;
+        Ok(value)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_file);
+    }
+    result
+}
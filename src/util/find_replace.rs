@@ -0,0 +1,251 @@
+use icy_engine::{editor::EditState, AttributedChar, BufferType, Position, TextAttribute, TextPane};
+
+use super::unicode::{from_unicode, to_unicode};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CharClass {
+    #[default]
+    Any,
+    Char(char),
+    /// ░ ▒ ▓
+    Shade,
+    /// Full & half blocks
+    Block,
+    BoxDrawing,
+    Whitespace,
+    Letter,
+    Digit,
+}
+
+impl CharClass {
+    /// `ch` is the unicode representation of the glyph.
+    fn matches(&self, ch: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Char(c) => *c == ch,
+            CharClass::Shade => matches!(ch, '░' | '▒' | '▓'),
+            CharClass::Block => matches!(ch, '█' | '▀' | '▄' | '▌' | '▐' | '■'),
+            CharClass::BoxDrawing => ('\u{2500}'..='\u{257F}').contains(&ch),
+            CharClass::Whitespace => ch == ' ' || ch == '\0' || ch == '\u{A0}',
+            CharClass::Letter => ch.is_alphabetic(),
+            CharClass::Digit => ch.is_ascii_digit(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FindScope {
+    Selection,
+    #[default]
+    Layer,
+    AllLayers,
+}
+
+/// Properties a cell needs to match, `None` matches everything.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FindPattern {
+    pub char_class: CharClass,
+    pub foreground: Option<u32>,
+    pub background: Option<u32>,
+    pub font_page: Option<usize>,
+    pub blink: Option<bool>,
+}
+
+impl FindPattern {
+    pub fn matches(&self, buffer_type: BufferType, ch: AttributedChar) -> bool {
+        if !ch.is_visible() {
+            return false;
+        }
+        let attr = ch.attribute;
+        self.char_class.matches(to_unicode(buffer_type, ch.ch))
+            && self.foreground.map_or(true, |fg| attr.get_foreground() == fg)
+            && self.background.map_or(true, |bg| attr.get_background() == bg)
+            && self.font_page.map_or(true, |page| attr.get_font_page() == page)
+            && self.blink.map_or(true, |blink| attr.is_blinking() == blink)
+    }
+}
+
+/// Properties written to the matched cells, `None` keeps the property.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replacement {
+    pub ch: Option<char>,
+    pub foreground: Option<u32>,
+    pub background: Option<u32>,
+    pub font_page: Option<usize>,
+    pub blink: Option<bool>,
+}
+
+impl Replacement {
+    pub fn is_empty(&self) -> bool {
+        *self == Replacement::default()
+    }
+
+    pub fn apply(&self, buffer_type: BufferType, ch: AttributedChar) -> AttributedChar {
+        let mut attr: TextAttribute = ch.attribute;
+        if let Some(fg) = self.foreground {
+            attr.set_foreground(fg);
+        }
+        if let Some(bg) = self.background {
+            attr.set_background(bg);
+        }
+        if let Some(page) = self.font_page {
+            attr.set_font_page(page);
+        }
+        if let Some(blink) = self.blink {
+            attr.set_is_blinking(blink);
+        }
+        let new_ch = self.ch.map_or(ch.ch, |c| from_unicode(buffer_type, c));
+        AttributedChar::new(new_ch, attr)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FindMatch {
+    pub layer: usize,
+    /// Position inside of the layer
+    pub pos: Position,
+}
+
+pub fn find_matches(edit_state: &EditState, cur_layer: usize, pattern: &FindPattern, scope: FindScope) -> Vec<FindMatch> {
+    let buffer = edit_state.get_buffer();
+    let layers = match scope {
+        FindScope::AllLayers => 0..buffer.layers.len(),
+        _ => cur_layer.min(buffer.layers.len())..(cur_layer + 1).min(buffer.layers.len()),
+    };
+    let mut result = Vec::new();
+    for layer_idx in layers {
+        let layer = &buffer.layers[layer_idx];
+        let offset = layer.get_offset();
+        for y in 0..layer.get_height() {
+            for x in 0..layer.get_width() {
+                let pos = Position::new(x, y);
+                if scope == FindScope::Selection && !edit_state.get_is_selected(pos + offset) {
+                    continue;
+                }
+                if pattern.matches(buffer.buffer_type, layer.get_char(pos)) {
+                    result.push(FindMatch { layer: layer_idx, pos });
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::{Buffer, Layer, Rectangle};
+
+    use super::*;
+
+    fn colored(ch: char, fg: u32, blink: bool) -> AttributedChar {
+        let mut attr = TextAttribute::default();
+        attr.set_foreground(fg);
+        attr.set_is_blinking(blink);
+        AttributedChar::new(ch, attr)
+    }
+
+    #[test]
+    fn test_char_class() {
+        assert!(CharClass::Char('x').matches('x'));
+        assert!(!CharClass::Char('x').matches('X'));
+        assert!(CharClass::Shade.matches('▒'));
+        assert!(!CharClass::Shade.matches('█'));
+        assert!(CharClass::Block.matches('▀'));
+        assert!(CharClass::BoxDrawing.matches('╬'));
+        assert!(!CharClass::BoxDrawing.matches('+'));
+        assert!(CharClass::Whitespace.matches('\0'));
+        assert!(CharClass::Letter.matches('ä'));
+        assert!(!CharClass::Letter.matches('7'));
+        assert!(CharClass::Digit.matches('7'));
+    }
+
+    #[test]
+    fn test_pattern_matches_glyphs_as_unicode() {
+        let pattern = FindPattern {
+            char_class: CharClass::Shade,
+            ..Default::default()
+        };
+        assert!(pattern.matches(BufferType::CP437, AttributedChar::new('\u{B1}', TextAttribute::default())));
+        assert!(!pattern.matches(BufferType::CP437, AttributedChar::new('\u{DB}', TextAttribute::default())));
+    }
+
+    #[test]
+    fn test_pattern_attribute_combinations() {
+        let pattern = FindPattern {
+            foreground: Some(4),
+            blink: Some(true),
+            ..Default::default()
+        };
+        assert!(pattern.matches(BufferType::CP437, colored('A', 4, true)));
+        assert!(!pattern.matches(BufferType::CP437, colored('A', 4, false)));
+        assert!(!pattern.matches(BufferType::CP437, colored('A', 5, true)));
+        assert!(!pattern.matches(BufferType::CP437, AttributedChar::invisible()));
+
+        let pattern = FindPattern {
+            char_class: CharClass::Char('A'),
+            background: Some(0),
+            font_page: Some(0),
+            ..Default::default()
+        };
+        assert!(pattern.matches(BufferType::CP437, colored('A', 7, false)));
+        assert!(!pattern.matches(BufferType::CP437, colored('B', 7, false)));
+    }
+
+    #[test]
+    fn test_replacement_apply() {
+        let replacement = Replacement {
+            ch: Some('█'),
+            background: Some(1),
+            blink: Some(false),
+            ..Default::default()
+        };
+        let result = replacement.apply(BufferType::CP437, colored('A', 4, true));
+        assert_eq!(result.ch, '\u{DB}');
+        assert_eq!(result.attribute.get_foreground(), 4);
+        assert_eq!(result.attribute.get_background(), 1);
+        assert!(!result.attribute.is_blinking());
+
+        // empty replacements keep the cell
+        assert!(Replacement::default().is_empty());
+        assert_eq!(Replacement::default().apply(BufferType::CP437, colored('A', 4, true)), colored('A', 4, true));
+    }
+
+    #[test]
+    fn test_find_matches_scope() {
+        let mut buffer = Buffer::new((4, 2));
+        for pos in [(0, 0), (2, 0), (1, 1)] {
+            buffer.layers[0].set_char(pos, AttributedChar::new('A', TextAttribute::default()));
+        }
+        let mut top = Layer::new("top", (4, 2));
+        top.set_char((3, 1), AttributedChar::new('A', TextAttribute::default()));
+        buffer.layers.push(top);
+
+        let mut edit_state = EditState::from_buffer(buffer);
+        let pattern = FindPattern {
+            char_class: CharClass::Char('A'),
+            ..Default::default()
+        };
+
+        let matches = find_matches(&edit_state, 0, &pattern, FindScope::Layer);
+        let positions: Vec<_> = matches.iter().map(|m| (m.layer, m.pos)).collect();
+        assert_eq!(positions, vec![(0, Position::new(0, 0)), (0, Position::new(2, 0)), (0, Position::new(1, 1))]);
+
+        assert_eq!(find_matches(&edit_state, 0, &pattern, FindScope::AllLayers).len(), 4);
+        assert_eq!(
+            find_matches(&edit_state, 1, &pattern, FindScope::Layer),
+            vec![FindMatch {
+                layer: 1,
+                pos: Position::new(3, 1)
+            }]
+        );
+
+        edit_state.set_selection(Rectangle::from(1, 0, 2, 1)).unwrap();
+        assert_eq!(
+            find_matches(&edit_state, 0, &pattern, FindScope::Selection),
+            vec![FindMatch {
+                layer: 0,
+                pos: Position::new(2, 0)
+            }]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+use icy_engine::BitFont;
+
+/// Fonts are the same if they have the same size and glyphs, the name doesn't matter.
+pub fn is_same_font(a: &BitFont, b: &BitFont) -> bool {
+    a.size == b.size
+        && a.glyphs.len() == b.glyphs.len()
+        && a.glyphs
+            .iter()
+            .all(|(ch, glyph)| b.get_glyph(*ch).map_or(false, |other| other.data == glyph.data))
+}
@@ -0,0 +1,394 @@
+use icy_engine::{AttributedChar, Buffer, BufferType, Layer, TextPane};
+use serde::{Deserialize, Serialize};
+
+use super::unicode::{from_unicode, to_unicode};
+use crate::GLYPH_MAPPINGS;
+
+/// Glyphs that swap when mirrored left/right.
+const CP437_MIRROR_X: [(u8, u8); 24] = [
+    (0xDD, 0xDE), // ▌ ▐
+    (0xDA, 0xBF), // ┌ ┐
+    (0xC0, 0xD9), // └ ┘
+    (0xC3, 0xB4), // ├ ┤
+    (0xC9, 0xBB), // ╔ ╗
+    (0xC8, 0xBC), // ╚ ╝
+    (0xCC, 0xB9), // ╠ ╣
+    (0xD5, 0xB8), // ╒ ╕
+    (0xD6, 0xB7), // ╓ ╖
+    (0xD4, 0xBE), // ╘ ╛
+    (0xD3, 0xBD), // ╙ ╜
+    (0xC6, 0xB5), // ╞ ╡
+    (0xC7, 0xB6), // ╟ ╢
+    (b'/', b'\\'),
+    (b'(', b')'),
+    (b'[', b']'),
+    (b'{', b'}'),
+    (b'<', b'>'),
+    (0x10, 0x11), // ► ◄
+    (0x1A, 0x1B), // → ←
+    (0xAE, 0xAF), // « »
+    (0xF2, 0xF3), // ≥ ≤
+    (b'b', b'd'),
+    (b'p', b'q'),
+];
+
+/// Glyphs that swap when mirrored top/bottom.
+const CP437_MIRROR_Y: [(u8, u8); 18] = [
+    (0xDF, 0xDC), // ▀ ▄
+    (0xDA, 0xC0), // ┌ └
+    (0xBF, 0xD9), // ┐ ┘
+    (0xC2, 0xC1), // ┬ ┴
+    (0xC9, 0xC8), // ╔ ╚
+    (0xBB, 0xBC), // ╗ ╝
+    (0xCB, 0xCA), // ╦ ╩
+    (0xD5, 0xD4), // ╒ ╘
+    (0xB8, 0xBE), // ╕ ╛
+    (0xD6, 0xD3), // ╓ ╙
+    (0xB7, 0xBD), // ╖ ╜
+    (0xD1, 0xCF), // ╤ ╧
+    (0xD2, 0xD0), // ╥ ╨
+    (b'/', b'\\'),
+    (0x1E, 0x1F), // ▲ ▼
+    (0x18, 0x19), // ↑ ↓
+    (b'b', b'p'),
+    (b'd', b'q'),
+];
+
+/// Cycles of glyphs under a clockwise quarter turn.
+const CP437_ROTATE: [&[u8]; 16] = [
+    &[0xDF, 0xDE, 0xDC, 0xDD], // ▀ ▐ ▄ ▌
+    &[0xC4, 0xB3],             // ─ │
+    &[0xCD, 0xBA],             // ═ ║
+    &[0xD8, 0xD7],             // ╪ ╫
+    &[0xDA, 0xBF, 0xD9, 0xC0], // ┌ ┐ ┘ └
+    &[0xC3, 0xC2, 0xB4, 0xC1], // ├ ┬ ┤ ┴
+    &[0xC9, 0xBB, 0xBC, 0xC8], // ╔ ╗ ╝ ╚
+    &[0xCC, 0xCB, 0xB9, 0xCA], // ╠ ╦ ╣ ╩
+    &[0xD5, 0xB7, 0xBE, 0xD3], // ╒ ╖ ╛ ╙
+    &[0xD6, 0xB8, 0xBD, 0xD4], // ╓ ╕ ╜ ╘
+    &[0xC6, 0xD2, 0xB5, 0xD0], // ╞ ╥ ╡ ╨
+    &[0xC7, 0xD1, 0xB6, 0xCF], // ╟ ╤ ╢ ╧
+    &[0x1E, 0x10, 0x1F, 0x11], // ▲ ► ▼ ◄
+    &[0x18, 0x1A, 0x19, 0x1B], // ↑ → ↓ ←
+    &[b'/', b'\\'],
+    &[b'-', b'|'],
+];
+
+/// ATASCII inverse glyphs (bit 7) are mirrored like their normal counterparts.
+const ATASCII_MIRROR_X: [(u8, u8); 16] = [
+    (0x01, 0x04), // ┣ ┫
+    (0x03, 0x1A), // ┛ ┗
+    (0x05, 0x11), // ┓ ┏
+    (0x06, 0x07), // ╱ ╲
+    (0x08, 0x0A), // ◢ ◣
+    (0x09, 0x0F), // ▗ ▖
+    (0x0B, 0x0C), // ▝ ▘
+    (0x02, 0x16), // ▕ ▎
+    (0x19, 0x99), // ▌ ▐
+    (0x1E, 0x1F), // ← →
+    (0x7E, 0x7F), // ◀ ▶
+    (b'/', b'\\'),
+    (b'(', b')'),
+    (b'[', b']'),
+    (b'<', b'>'),
+    (b'b', b'd'),
+];
+
+const ATASCII_MIRROR_Y: [(u8, u8); 13] = [
+    (0x03, 0x05), // ┛ ┓
+    (0x1A, 0x11), // ┗ ┏
+    (0x17, 0x18), // ┳ ┻
+    (0x06, 0x07), // ╱ ╲
+    (0x08, 0x8A), // ◢ ◥
+    (0x0A, 0x88), // ◣ ◤
+    (0x09, 0x0B), // ▗ ▝
+    (0x0F, 0x0C), // ▖ ▘
+    (0x0D, 0x0E), // ▔ ▁
+    (0x15, 0x95), // ▄ ▀
+    (0x1C, 0x1D), // ↑ ↓
+    (b'/', b'\\'),
+    (b'b', b'p'),
+];
+
+const ATASCII_ROTATE: [&[u8]; 9] = [
+    &[0x95, 0x99, 0x15, 0x19], // ▀ ▐ ▄ ▌
+    &[0x01, 0x17, 0x04, 0x18], // ┣ ┳ ┫ ┻
+    &[0x11, 0x05, 0x03, 0x1A], // ┏ ┓ ┛ ┗
+    &[0x08, 0x0A, 0x88, 0x8A], // ◢ ◣ ◤ ◥
+    &[0x09, 0x0F, 0x0C, 0x0B], // ▗ ▖ ▘ ▝
+    &[0x0D, 0x02, 0x0E, 0x16], // ▔ ▕ ▁ ▎
+    &[0x1C, 0x1F, 0x1D, 0x1E], // ↑ → ↓ ←
+    &[0x12, 0x7C],             // ━ ┃
+    &[0x06, 0x07],             // ╱ ╲
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum GlyphCodePage {
+    Cp437,
+    Atascii,
+}
+
+impl GlyphCodePage {
+    pub fn from_buffer_type(buffer_type: BufferType) -> Option<Self> {
+        match buffer_type {
+            BufferType::CP437 => Some(GlyphCodePage::Cp437),
+            BufferType::Atascii => Some(GlyphCodePage::Atascii),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GlyphCodePage::Cp437 => "CP437",
+            GlyphCodePage::Atascii => "ATASCII",
+        }
+    }
+
+    pub fn buffer_type(&self) -> BufferType {
+        match self {
+            GlyphCodePage::Cp437 => BufferType::CP437,
+            GlyphCodePage::Atascii => BufferType::Atascii,
+        }
+    }
+
+    pub fn to_unicode(&self, ch: char) -> char {
+        to_unicode(self.buffer_type(), ch)
+    }
+
+    pub fn from_unicode(&self, ch: char) -> char {
+        from_unicode(self.buffer_type(), ch)
+    }
+}
+
+/// Which glyphs replace each other when art is mirrored or rotated.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GlyphMapping {
+    /// 0 for the default mapping of the code page
+    pub font_checksum: u32,
+    pub code_page: GlyphCodePage,
+    pub mirror_x: Vec<(char, char)>,
+    pub mirror_y: Vec<(char, char)>,
+    /// Each entry is a cycle of glyphs under a clockwise quarter turn.
+    pub rotate: Vec<Vec<char>>,
+}
+
+fn to_pairs(pairs: &[(u8, u8)], inverse: bool) -> Vec<(char, char)> {
+    let mut result: Vec<(char, char)> = pairs.iter().map(|(a, b)| (*a as char, *b as char)).collect();
+    if inverse {
+        result.extend(pairs.iter().map(|(a, b)| ((*a ^ 0x80) as char, (*b ^ 0x80) as char)));
+    }
+    result
+}
+
+fn to_cycles(cycles: &[&[u8]], inverse: bool) -> Vec<Vec<char>> {
+    let mut result: Vec<Vec<char>> = cycles.iter().map(|c| c.iter().map(|ch| *ch as char).collect()).collect();
+    if inverse {
+        result.extend(cycles.iter().map(|c| c.iter().map(|ch| (*ch ^ 0x80) as char).collect()));
+    }
+    result
+}
+
+impl GlyphMapping {
+    pub fn cp437() -> Self {
+        Self {
+            font_checksum: 0,
+            code_page: GlyphCodePage::Cp437,
+            mirror_x: to_pairs(&CP437_MIRROR_X, false),
+            mirror_y: to_pairs(&CP437_MIRROR_Y, false),
+            rotate: to_cycles(&CP437_ROTATE, false),
+        }
+    }
+
+    pub fn atascii() -> Self {
+        Self {
+            font_checksum: 0,
+            code_page: GlyphCodePage::Atascii,
+            mirror_x: to_pairs(&ATASCII_MIRROR_X, true),
+            mirror_y: to_pairs(&ATASCII_MIRROR_Y, true),
+            rotate: to_cycles(&ATASCII_ROTATE, true),
+        }
+    }
+
+    pub fn defaults() -> Vec<Self> {
+        vec![Self::cp437(), Self::atascii()]
+    }
+
+    pub fn default_for(code_page: GlyphCodePage) -> Self {
+        match code_page {
+            GlyphCodePage::Cp437 => Self::cp437(),
+            GlyphCodePage::Atascii => Self::atascii(),
+        }
+    }
+
+    /// The mapping for the font of the buffer, falls back to the default of the code page.
+    pub fn for_buffer(buf: &Buffer) -> Option<Self> {
+        let code_page = GlyphCodePage::from_buffer_type(buf.buffer_type)?;
+        let checksum = buf.get_font(0).map_or(0, |f| f.get_checksum());
+        unsafe {
+            GLYPH_MAPPINGS
+                .glyph_mappings
+                .iter()
+                .find(|m| m.font_checksum != 0 && m.font_checksum == checksum)
+                .or_else(|| GLYPH_MAPPINGS.glyph_mappings.iter().find(|m| m.font_checksum == 0 && m.code_page == code_page))
+                .cloned()
+                .or_else(|| Some(Self::default_for(code_page)))
+        }
+    }
+
+    fn swap(pairs: &[(char, char)], ch: char) -> char {
+        for (a, b) in pairs {
+            if ch == *a {
+                return *b;
+            }
+            if ch == *b {
+                return *a;
+            }
+        }
+        ch
+    }
+
+    pub fn mirror_x_char(&self, ch: char) -> char {
+        Self::swap(&self.mirror_x, ch)
+    }
+
+    pub fn mirror_y_char(&self, ch: char) -> char {
+        Self::swap(&self.mirror_y, ch)
+    }
+
+    /// The glyph after the given number of clockwise quarter turns.
+    pub fn rotate_char(&self, ch: char, quarter_turns: usize) -> char {
+        for cycle in &self.rotate {
+            if let Some(i) = cycle.iter().position(|c| *c == ch) {
+                return cycle[(i + quarter_turns) % cycle.len()];
+            }
+        }
+        if quarter_turns % 4 == 2 {
+            // a half turn is a mirror on both axes
+            return self.mirror_y_char(self.mirror_x_char(ch));
+        }
+        ch
+    }
+
+    pub fn pairs_to_text(&self, pairs: &[(char, char)]) -> String {
+        let tokens: Vec<String> = pairs
+            .iter()
+            .map(|(a, b)| format!("{}{}", self.code_page.to_unicode(*a), self.code_page.to_unicode(*b)))
+            .collect();
+        tokens.chunks(8).map(|line| line.join(" ")).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Pairs are written as two glyph tokens separated by whitespace.
+    pub fn text_to_pairs(&self, text: &str) -> Vec<(char, char)> {
+        text.split_whitespace()
+            .filter_map(|token| {
+                let mut chars = token.chars();
+                match (chars.next(), chars.next(), chars.next()) {
+                    (Some(a), Some(b), None) => Some((self.code_page.from_unicode(a), self.code_page.from_unicode(b))),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn cycles_to_text(&self) -> String {
+        self.rotate
+            .iter()
+            .map(|cycle| cycle.iter().map(|ch| self.code_page.to_unicode(*ch)).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn text_to_cycles(&self, text: &str) -> Vec<Vec<char>> {
+        text.split_whitespace()
+            .map(|token| token.chars().map(|ch| self.code_page.from_unicode(ch)).collect::<Vec<_>>())
+            .filter(|cycle| cycle.len() > 1)
+            .collect()
+    }
+}
+
+fn map_char(ch: AttributedChar, f: impl Fn(char) -> char) -> AttributedChar {
+    if !ch.is_visible() {
+        return ch;
+    }
+    AttributedChar::new(f(ch.ch), ch.attribute)
+}
+
+pub fn flip_layer_x(layer: &Layer, mapping: Option<&GlyphMapping>) -> Layer {
+    let mut result = layer.clone();
+    let width = layer.get_width();
+    for y in 0..layer.get_height() {
+        for x in 0..width {
+            let ch = map_char(layer.get_char((x, y)), |ch| mapping.map_or(ch, |m| m.mirror_x_char(ch)));
+            result.set_char((width - 1 - x, y), ch);
+        }
+    }
+    result
+}
+
+pub fn flip_layer_y(layer: &Layer, mapping: Option<&GlyphMapping>) -> Layer {
+    let mut result = layer.clone();
+    let height = layer.get_height();
+    for y in 0..height {
+        for x in 0..layer.get_width() {
+            let ch = map_char(layer.get_char((x, y)), |ch| mapping.map_or(ch, |m| m.mirror_y_char(ch)));
+            result.set_char((x, height - 1 - y), ch);
+        }
+    }
+    result
+}
+
+/// Rotates the layer clockwise by 90°, width and height are swapped.
+pub fn rotate_layer_cw(layer: &Layer, mapping: Option<&GlyphMapping>) -> Layer {
+    let height = layer.get_height();
+    let mut result = Layer::new(layer.get_title().to_string(), (height, layer.get_width()));
+    for y in 0..height {
+        for x in 0..layer.get_width() {
+            let ch = map_char(layer.get_char((x, y)), |ch| mapping.map_or(ch, |m| m.rotate_char(ch, 1)));
+            result.set_char((height - 1 - y, x), ch);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_chars() -> impl Iterator<Item = char> {
+        (0..=255u8).map(|ch| ch as char)
+    }
+
+    #[test]
+    fn test_mirror_twice_is_identity() {
+        for mapping in GlyphMapping::defaults() {
+            for ch in all_chars() {
+                assert_eq!(mapping.mirror_x_char(mapping.mirror_x_char(ch)), ch, "{:?} {ch:?}", mapping.code_page);
+                assert_eq!(mapping.mirror_y_char(mapping.mirror_y_char(ch)), ch, "{:?} {ch:?}", mapping.code_page);
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_turn_is_identity() {
+        for mapping in GlyphMapping::defaults() {
+            for ch in all_chars() {
+                assert_eq!(mapping.rotate_char(ch, 4), ch, "{:?} {ch:?}", mapping.code_page);
+                assert_eq!(mapping.rotate_char(mapping.rotate_char(ch, 1), 3), ch, "{:?} {ch:?}", mapping.code_page);
+            }
+        }
+    }
+
+    #[test]
+    fn test_half_turn_mirrors_both_axes() {
+        for mapping in GlyphMapping::defaults() {
+            for ch in all_chars() {
+                assert_eq!(
+                    mapping.rotate_char(ch, 2),
+                    mapping.mirror_y_char(mapping.mirror_x_char(ch)),
+                    "{:?} {ch:?}",
+                    mapping.code_page
+                );
+            }
+        }
+    }
+}
@@ -1 +1,16 @@
 pub mod autosave;
+pub mod brush_library;
+pub mod buffer_diff;
+pub mod color_remap;
+pub mod date;
+pub mod figlet;
+pub mod file;
+pub mod find_replace;
+pub mod font;
+pub mod glyph_map;
+pub mod palette_convert;
+pub mod petscii;
+pub mod release_pack;
+pub mod sauce_batch;
+pub mod ttf;
+pub mod unicode;
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use icy_engine::{AttributedChar, BufferType, Palette};
+
+use super::unicode::{from_unicode, to_unicode};
+
+/// Color in the CIE L*a*b* space (D65 white point).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+fn to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl Lab {
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::from_linear_rgb(to_linear(r), to_linear(g), to_linear(b))
+    }
+
+    pub fn from_linear_rgb(r: f64, g: f64, b: f64) -> Self {
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+        let f = |t: f64| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        Self {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+/// Perceptual color difference, see "The CIEDE2000 Color-Difference Formula" by Sharma, Wu & Dalal.
+pub fn ciede2000(c1: &Lab, c2: &Lab) -> f64 {
+    let c_bar = ((c1.a * c1.a + c1.b * c1.b).sqrt() + (c2.a * c2.a + c2.b * c2.b).sqrt()) / 2.0;
+    let c_bar7 = c_bar.powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + 25f64.powi(7))).sqrt());
+
+    let a1 = (1.0 + g) * c1.a;
+    let a2 = (1.0 + g) * c2.a;
+    let cp1 = (a1 * a1 + c1.b * c1.b).sqrt();
+    let cp2 = (a2 * a2 + c2.b * c2.b).sqrt();
+    let hue = |b: f64, a: f64| {
+        if b == 0.0 && a == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let hp1 = hue(c1.b, a1);
+    let hp2 = hue(c2.b, a2);
+
+    let dl = c2.l - c1.l;
+    let dc = cp2 - cp1;
+    let dhp = if cp1 * cp2 == 0.0 {
+        0.0
+    } else if (hp2 - hp1).abs() <= 180.0 {
+        hp2 - hp1
+    } else if hp2 - hp1 > 180.0 {
+        hp2 - hp1 - 360.0
+    } else {
+        hp2 - hp1 + 360.0
+    };
+    let dh = 2.0 * (cp1 * cp2).sqrt() * (dhp / 2.0).to_radians().sin();
+
+    let l_bar = (c1.l + c2.l) / 2.0;
+    let cp_bar = (cp1 + cp2) / 2.0;
+    let hp_bar = if cp1 * cp2 == 0.0 {
+        hp1 + hp2
+    } else if (hp1 - hp2).abs() <= 180.0 {
+        (hp1 + hp2) / 2.0
+    } else if hp1 + hp2 < 360.0 {
+        (hp1 + hp2 + 360.0) / 2.0
+    } else {
+        (hp1 + hp2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (hp_bar - 30.0).to_radians().cos() + 0.24 * (2.0 * hp_bar).to_radians().cos() + 0.32 * (3.0 * hp_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * hp_bar - 63.0).to_radians().cos();
+    let d_theta = 30.0 * (-((hp_bar - 275.0) / 25.0).powi(2)).exp();
+    let cp_bar7 = cp_bar.powi(7);
+    let rc = 2.0 * (cp_bar7 / (cp_bar7 + 25f64.powi(7))).sqrt();
+    let l50 = (l_bar - 50.0).powi(2);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * cp_bar;
+    let sh = 1.0 + 0.015 * cp_bar * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    (l * l + c * c + h * h + rt * c * h).sqrt()
+}
+
+/// Shade glyphs used for dithering with the amount of foreground they cover.
+const SHADES: [(char, f64); 3] = [('░', 0.25), ('▒', 0.5), ('▓', 0.75)];
+
+#[derive(Clone, Copy, Debug)]
+struct Mix {
+    lab: Lab,
+    shade: char,
+    fg: u32,
+    bg: u32,
+}
+
+/// Maps the colors of cells to a 16 color target palette by perceptual distance.
+pub struct PaletteConverter {
+    source: Palette,
+    target: Vec<Lab>,
+    /// Background colors are limited to the first 8 entries when ice colors are off.
+    bg_colors: usize,
+    buffer_type: BufferType,
+    /// Spaces with a color that is only available as foreground become full blocks.
+    has_full_block: bool,
+    /// Empty when dithering is off or the font has no shade glyphs.
+    mixes: Vec<Mix>,
+    nearest_cache: HashMap<(u32, usize), u32>,
+    solid_cache: HashMap<u32, Option<Mix>>,
+}
+
+impl PaletteConverter {
+    pub fn new(source: &Palette, target: &Palette, bg_colors: usize, buffer_type: BufferType, dither: bool) -> Self {
+        let target_len = target.len().min(16) as u32;
+        let target_rgb: Vec<_> = (0..target_len).map(|i| target.get_color(i).get_rgb()).collect();
+        let target_lab = target_rgb.iter().map(|(r, g, b)| Lab::from_rgb(*r, *g, *b)).collect();
+        let bg_colors = bg_colors.min(target_rgb.len());
+
+        let has_glyph = |ch: char| to_unicode(buffer_type, from_unicode(buffer_type, ch)) == ch;
+        let has_shades = SHADES.iter().all(|(shade, _)| has_glyph(*shade));
+        let mut mixes = Vec::new();
+        if dither && has_shades {
+            for (fg, (fr, fg_g, fb)) in target_rgb.iter().enumerate() {
+                for (bg, (br, bg_g, bb)) in target_rgb.iter().enumerate().take(bg_colors) {
+                    if fg == bg {
+                        continue;
+                    }
+                    for (shade, amount) in SHADES {
+                        // shades blend in linear light
+                        let mix = |f: u8, b: u8| to_linear(f) * amount + to_linear(b) * (1.0 - amount);
+                        mixes.push(Mix {
+                            lab: Lab::from_linear_rgb(mix(*fr, *br), mix(*fg_g, *bg_g), mix(*fb, *bb)),
+                            shade,
+                            fg: fg as u32,
+                            bg: bg as u32,
+                        });
+                    }
+                }
+            }
+        }
+
+        Self {
+            source: source.clone(),
+            target: target_lab,
+            bg_colors,
+            buffer_type,
+            has_full_block: has_glyph('█'),
+            mixes,
+            nearest_cache: HashMap::new(),
+            solid_cache: HashMap::new(),
+        }
+    }
+
+    fn source_lab(&self, color: u32) -> Lab {
+        let (r, g, b) = self.source.get_color(color).get_rgb();
+        Lab::from_rgb(r, g, b)
+    }
+
+    /// Nearest of the first `count` target colors and its distance.
+    fn find_nearest(&self, lab: &Lab, count: usize) -> (u32, f64) {
+        self.target
+            .iter()
+            .take(count)
+            .enumerate()
+            .map(|(i, t)| (i as u32, ciede2000(lab, t)))
+            .fold((0, f64::MAX), |best, cur| if cur.1 < best.1 { cur } else { best })
+    }
+
+    fn nearest(&mut self, color: u32, count: usize) -> u32 {
+        if let Some(result) = self.nearest_cache.get(&(color, count)) {
+            return *result;
+        }
+        let result = self.find_nearest(&self.source_lab(color), count).0;
+        self.nearest_cache.insert((color, count), result);
+        result
+    }
+
+    /// Best shade mix for a solid color, `None` if a plain target color is closer.
+    fn dither(&mut self, color: u32) -> Option<Mix> {
+        if let Some(result) = self.solid_cache.get(&color) {
+            return *result;
+        }
+        let lab = self.source_lab(color);
+        let (_, plain) = self.find_nearest(&lab, self.target.len());
+        let result = self
+            .mixes
+            .iter()
+            .map(|mix| (mix, ciede2000(&lab, &mix.lab)))
+            .filter(|(_, d)| *d < plain)
+            .fold(None, |best: Option<(&Mix, f64)>, cur| match best {
+                Some(b) if b.1 <= cur.1 => Some(b),
+                _ => Some(cur),
+            })
+            .map(|(mix, _)| *mix);
+        self.solid_cache.insert(color, result);
+        result
+    }
+
+    pub fn convert(&mut self, ch: AttributedChar) -> AttributedChar {
+        if !ch.is_visible() {
+            return ch;
+        }
+        let mut attr = ch.attribute;
+        let fg = attr.get_foreground();
+        let bg = attr.get_background();
+
+        let fg_colors = self.target.len();
+        let bg_colors = self.bg_colors;
+        let is_space = matches!(to_unicode(self.buffer_type, ch.ch), ' ' | '\0' | '\u{A0}');
+
+        if !self.mixes.is_empty() {
+            // Only cells showing a single color can be dithered without changing the picture
+            let solid = if is_space {
+                Some(bg)
+            } else if to_unicode(self.buffer_type, ch.ch) == '█' {
+                Some(fg)
+            } else {
+                None
+            };
+            if let Some(mix) = solid.and_then(|color| self.dither(color)) {
+                attr.set_foreground(mix.fg);
+                attr.set_background(mix.bg);
+                if is_space {
+                    attr.set_is_blinking(false);
+                }
+                return AttributedChar::new(from_unicode(self.buffer_type, mix.shade), attr);
+            }
+        }
+
+        if is_space && self.has_full_block {
+            let nearest = self.nearest(bg, fg_colors);
+            if nearest as usize >= bg_colors {
+                attr.set_foreground(nearest);
+                attr.set_background(self.nearest(bg, bg_colors));
+                attr.set_is_blinking(false);
+                return AttributedChar::new(from_unicode(self.buffer_type, '█'), attr);
+            }
+        }
+
+        attr.set_foreground(self.nearest(fg, fg_colors));
+        attr.set_background(self.nearest(bg, bg_colors));
+        AttributedChar::new(ch.ch, attr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lab(l: f64, a: f64, b: f64) -> Lab {
+        Lab { l, a, b }
+    }
+
+    #[test]
+    fn test_ciede2000_reference_pairs() {
+        // test data from Sharma, Wu & Dalal
+        let pairs = [
+            (lab(50.0, 2.6772, -79.7751), lab(50.0, 0.0, -82.7485), 2.0425),
+            (lab(50.0, 3.1571, -77.2803), lab(50.0, 0.0, -82.7485), 2.8615),
+            (lab(50.0, 2.8361, -74.0200), lab(50.0, 0.0, -82.7485), 3.4412),
+            (lab(50.0, -1.3802, -84.2814), lab(50.0, 0.0, -82.7485), 1.0),
+            (lab(50.0, 0.0, 0.0), lab(50.0, -1.0, 2.0), 2.3669),
+            (lab(50.0, 2.49, -0.001), lab(50.0, -2.49, 0.0009), 7.1792),
+            (lab(50.0, 2.5, 0.0), lab(50.0, 3.1736, 0.5854), 1.0),
+            (lab(50.0, 2.5, 0.0), lab(73.0, 25.0, -18.0), 27.1492),
+            (lab(50.0, 2.5, 0.0), lab(56.0, -27.0, -3.0), 31.9030),
+            (lab(60.2574, -34.0099, 36.2677), lab(60.4626, -34.1751, 39.4387), 1.2644),
+            (lab(22.7233, 20.0904, -46.6940), lab(23.0331, 14.9730, -42.5619), 2.0373),
+            (lab(2.0776, 0.0795, -1.1350), lab(0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+        for (c1, c2, expected) in pairs {
+            assert!((ciede2000(&c1, &c2) - expected).abs() < 1e-4, "{c1:?} {c2:?}: {}", ciede2000(&c1, &c2));
+            assert!((ciede2000(&c2, &c1) - expected).abs() < 1e-4, "{c2:?} {c1:?}: {}", ciede2000(&c2, &c1));
+        }
+    }
+
+    #[test]
+    fn test_ciede2000_identical_colors() {
+        let c = Lab::from_rgb(0xAA, 0x55, 0x00);
+        assert_eq!(ciede2000(&c, &c), 0.0);
+    }
+}
@@ -0,0 +1,478 @@
+use icy_engine::{AttributedChar, BitFont, Buffer, BufferType, Palette, TextAttribute, TextPane, C64_DEFAULT_PALETTE, C64_LOWER, C64_UPPER};
+
+use crate::TerminalResult;
+
+pub const SCREEN_WIDTH: i32 = 40;
+pub const SCREEN_HEIGHT: i32 = 25;
+const SCREEN_SIZE: usize = (SCREEN_WIDTH * SCREEN_HEIGHT) as usize;
+
+const DEFAULT_BORDER: u8 = 14;
+const DEFAULT_BACKGROUND: u8 = 6;
+const DEFAULT_FOREGROUND: u8 = 14;
+
+/// Font page of the lower/upper case char set, page 0 is upper case/graphics.
+pub const LOWER_CASE_PAGE: usize = 1;
+
+/// Raw screen dump: 1000 bytes screen RAM, 1000 bytes color RAM, border & background color.
+pub const SCREEN_EXT: &str = "scr";
+/// PETSCII byte stream as printed by the kernal.
+pub const SEQ_EXT: &str = "seq";
+/// Self displaying program.
+pub const PRG_EXT: &str = "prg";
+
+pub fn is_c64_extension(ext: &str) -> bool {
+    ext == SCREEN_EXT || ext == SEQ_EXT || ext == PRG_EXT
+}
+
+/// Color control codes of the kernal screen editor, index = C64 color.
+const COLOR_CODES: [u8; 16] = [0x90, 0x05, 0x1C, 0x9F, 0x9C, 0x1E, 0x1F, 0x9E, 0x81, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A, 0x9B];
+const RVS_ON: u8 = 0x12;
+const RVS_OFF: u8 = 0x92;
+const CLEAR: u8 = 0x93;
+const HOME: u8 = 0x13;
+const LOWER_CASE: u8 = 0x0E;
+const UPPER_CASE: u8 = 0x8E;
+const CURSOR_LEFT: u8 = 0x9D;
+const INSERT: u8 = 0x94;
+
+/// Screen codes, colors & charset of a C64 text screen, at least 40x25 - seq files may be taller.
+pub struct C64Screen {
+    pub screen: Vec<u8>,
+    pub color: Vec<u8>,
+    pub border: u8,
+    pub background: u8,
+    pub lower_case: bool,
+}
+
+impl Default for C64Screen {
+    fn default() -> Self {
+        Self {
+            screen: vec![0x20; SCREEN_SIZE],
+            color: vec![DEFAULT_FOREGROUND; SCREEN_SIZE],
+            border: DEFAULT_BORDER,
+            background: DEFAULT_BACKGROUND,
+            lower_case: false,
+        }
+    }
+}
+
+/// Empty PETSCII document, the buffer stores screen codes and uses both C64 char sets as font pages.
+pub fn create_petscii_buffer(width: i32, height: i32) -> Buffer {
+    let mut buf = Buffer::create((width, height));
+    buf.buffer_type = BufferType::Petscii;
+    buf.ice_mode = icy_engine::IceMode::Ice;
+    buf.palette_mode = icy_engine::PaletteMode::Fixed16;
+    buf.font_mode = icy_engine::FontMode::Unlimited;
+    buf.clear_font_table();
+    buf.set_font(0, BitFont::from_bytes("C64 upper", C64_UPPER).unwrap());
+    buf.set_font(LOWER_CASE_PAGE, BitFont::from_bytes("C64 lower", C64_LOWER).unwrap());
+    buf.palette = Palette::from_slice(&C64_DEFAULT_PALETTE);
+
+    let mut attr = TextAttribute::default();
+    attr.set_foreground(DEFAULT_FOREGROUND as u32);
+    attr.set_background(DEFAULT_BACKGROUND as u32);
+    for y in 0..height {
+        for x in 0..width {
+            buf.layers[0].set_char((x, y), AttributedChar::new(' ', attr));
+        }
+    }
+    buf
+}
+
+pub fn petscii_to_screen_code(ch: u8) -> Option<u8> {
+    match ch {
+        0x20..=0x3F => Some(ch),
+        0x40..=0x5F => Some(ch - 0x40),
+        0x60..=0x7F => Some(ch - 0x20),
+        0xA0..=0xBF => Some(ch - 0x40),
+        0xC0..=0xFE => Some(ch - 0x80),
+        0xFF => Some(0x5E),
+        _ => None,
+    }
+}
+
+/// Maps the non reversed screen codes 0..0x7F to PETSCII.
+pub fn screen_code_to_petscii(code: u8) -> u8 {
+    match code & 0x7F {
+        c @ 0x00..=0x1F => c + 0x40,
+        c @ 0x20..=0x3F => c,
+        c @ 0x40..=0x5F => c + 0x80,
+        c => c + 0x40,
+    }
+}
+
+/// Space and reverse space look the same in both char sets.
+fn is_blank(code: u8) -> bool {
+    code & 0x7F == 0x20
+}
+
+impl C64Screen {
+    /// Fails if the buffer can't be shown on a C64 screen without losing anything:
+    /// it's wider than 40 columns, uses more than one background color or both char sets.
+    pub fn from_buffer(buf: &Buffer) -> TerminalResult<Self> {
+        if buf.get_width() > SCREEN_WIDTH {
+            return Err(anyhow::anyhow!(
+                "a C64 screen is {SCREEN_WIDTH} columns wide, the document has {} columns",
+                buf.get_width()
+            ));
+        }
+        let size = (SCREEN_WIDTH * buf.get_height().max(SCREEN_HEIGHT)) as usize;
+        let mut result = C64Screen {
+            screen: vec![0x20; size],
+            color: vec![DEFAULT_FOREGROUND; size],
+            ..Default::default()
+        };
+        let mut background = None;
+        let (mut upper_count, mut lower_count) = (0, 0);
+        for y in 0..buf.get_height() {
+            for x in 0..buf.get_width() {
+                let ch = buf.get_char((x, y));
+                let i = (y * SCREEN_WIDTH + x) as usize;
+                result.screen[i] = (ch.ch as u32 & 0xFF) as u8;
+                result.color[i] = (ch.attribute.get_foreground() & 0x0F) as u8;
+                let bg = (ch.attribute.get_background() & 0x0F) as u8;
+                if *background.get_or_insert(bg) != bg {
+                    return Err(anyhow::anyhow!("a C64 screen has one background color, the document uses more than one"));
+                }
+                if !is_blank(result.screen[i]) {
+                    if ch.get_font_page() == LOWER_CASE_PAGE {
+                        lower_count += 1;
+                    } else {
+                        upper_count += 1;
+                    }
+                }
+            }
+        }
+        if upper_count > 0 && lower_count > 0 {
+            return Err(anyhow::anyhow!(
+                "the C64 shows either the upper case or the lower case char set, the document uses both ({upper_count} upper case and {lower_count} lower case chars)"
+            ));
+        }
+        result.background = background.unwrap_or(DEFAULT_BACKGROUND);
+        result.border = result.background;
+        result.lower_case = lower_count > 0;
+        Ok(result)
+    }
+
+    pub fn lines(&self) -> usize {
+        self.screen.len() / SCREEN_WIDTH as usize
+    }
+
+    pub fn to_buffer(&self) -> Buffer {
+        let mut buf = create_petscii_buffer(SCREEN_WIDTH, self.lines() as i32);
+        for i in 0..self.screen.len() {
+            let mut attr = TextAttribute::default();
+            attr.set_foreground((self.color[i] & 0x0F) as u32);
+            attr.set_background(self.background as u32);
+            if self.lower_case {
+                attr.set_font_page(LOWER_CASE_PAGE);
+            }
+            let pos = ((i as i32) % SCREEN_WIDTH, (i as i32) / SCREEN_WIDTH);
+            buf.layers[0].set_char(pos, AttributedChar::new(char::from(self.screen[i]), attr));
+        }
+        buf
+    }
+
+    /// Accepts screen RAM only (1000 bytes), screen + color RAM (2000) and screen + color + border & background (2002).
+    pub fn from_dump(data: &[u8]) -> TerminalResult<Self> {
+        let mut result = C64Screen::default();
+        if data.len() < SCREEN_SIZE {
+            return Err(anyhow::anyhow!("screen dump too short: {} bytes, expected at least {SCREEN_SIZE}", data.len()));
+        }
+        result.screen.copy_from_slice(&data[..SCREEN_SIZE]);
+        if data.len() >= 2 * SCREEN_SIZE {
+            result.color = data[SCREEN_SIZE..2 * SCREEN_SIZE].iter().map(|c| c & 0x0F).collect();
+        }
+        if data.len() >= 2 * SCREEN_SIZE + 2 {
+            result.border = data[2 * SCREEN_SIZE] & 0x0F;
+            result.background = data[2 * SCREEN_SIZE + 1] & 0x0F;
+        }
+        Ok(result)
+    }
+
+    pub fn to_dump(&self) -> Vec<u8> {
+        let mut result = self.screen.clone();
+        result.extend_from_slice(&self.color);
+        result.push(self.border);
+        result.push(self.background);
+        result
+    }
+
+    /// Clears the screen and prints all lines.
+    /// Printing the bottom right char would scroll the screen, so it's printed one column left and shifted right by an insert.
+    pub fn to_seq(&self) -> Vec<u8> {
+        let mut result = vec![CLEAR, if self.lower_case { LOWER_CASE } else { UPPER_CASE }];
+        let mut color = None;
+        let mut reverse = false;
+        let mut set_attributes = |result: &mut Vec<u8>, i: usize| {
+            if color != Some(self.color[i]) {
+                result.push(COLOR_CODES[self.color[i] as usize & 0x0F]);
+                color = Some(self.color[i]);
+            }
+            let is_reverse = self.screen[i] >= 0x80;
+            if is_reverse != reverse {
+                result.push(if is_reverse { RVS_ON } else { RVS_OFF });
+                reverse = is_reverse;
+            }
+        };
+        let last = self.screen.len() - 1;
+        for i in 0..last - 1 {
+            set_attributes(&mut result, i);
+            result.push(screen_code_to_petscii(self.screen[i]));
+        }
+        set_attributes(&mut result, last);
+        result.push(screen_code_to_petscii(self.screen[last]));
+        // control codes following an insert are printed as symbols, the attributes need to be set before
+        set_attributes(&mut result, last - 1);
+        result.push(CURSOR_LEFT);
+        result.push(INSERT);
+        result.push(screen_code_to_petscii(self.screen[last - 1]));
+        result.push(HOME);
+        result
+    }
+
+    /// BASIC stub with SYS 2061, a copy loop for screen & color RAM and the screen data.
+    pub fn to_prg(&self) -> Vec<u8> {
+        const LOAD_ADDRESS: u16 = 0x0801;
+        const BASIC_STUB: [u8; 12] = [0x0B, 0x08, 0x0A, 0x00, 0x9E, b'2', b'0', b'6', b'1', 0x00, 0x00, 0x00];
+        let code_start = LOAD_ADDRESS + BASIC_STUB.len() as u16;
+        let code_len = self.display_code(0).len() as u16;
+        let data = code_start + code_len;
+
+        let mut result = LOAD_ADDRESS.to_le_bytes().to_vec();
+        result.extend_from_slice(&BASIC_STUB);
+        result.extend(self.display_code(data));
+        result.extend_from_slice(&self.screen);
+        result.extend_from_slice(&self.color);
+        result
+    }
+
+    fn display_code(&self, data: u16) -> Vec<u8> {
+        let charset = if self.lower_case { 0x17 } else { 0x15 };
+        let mut code = vec![
+            0x78, // SEI
+            0xA9,
+            self.border,
+            0x8D,
+            0x20,
+            0xD0, // LDA #border, STA $D020
+            0xA9,
+            self.background,
+            0x8D,
+            0x21,
+            0xD0, // LDA #background, STA $D021
+            0xA9,
+            charset,
+            0x8D,
+            0x18,
+            0xD0, // LDA #charset, STA $D018
+            0xA2,
+            0x00, // LDX #0
+        ];
+        let loop_start = code.len();
+        // the 1000 bytes are copied in 4 blocks of 250
+        for (src, dst) in [(data, 0x0400u16), (data + SCREEN_SIZE as u16, 0xD800)] {
+            for block in 0..4u16 {
+                let from = (src + block * 250).to_le_bytes();
+                let to = (dst + block * 250).to_le_bytes();
+                code.extend_from_slice(&[0xBD, from[0], from[1], 0x9D, to[0], to[1]]);
+                // LDA from,X  STA to,X
+            }
+        }
+        code.extend_from_slice(&[0xE8, 0xE0, 250]); // INX, CPX #250
+        let offset = loop_start as i32 - (code.len() as i32 + 2);
+        code.extend_from_slice(&[0xD0, offset as u8]); // BNE loop
+        let end = (data.wrapping_sub(3)).to_le_bytes();
+        code.extend_from_slice(&[0x4C, end[0], end[1]]); // JMP * (the JMP is the last instruction before the data)
+        code
+    }
+}
+
+/// Interprets a PETSCII stream on a 40 column screen, the document grows with the output.
+pub fn parse_seq(data: &[u8]) -> Buffer {
+    let mut lines: Vec<Vec<(u8, u8)>> = vec![vec![(0x20, DEFAULT_FOREGROUND); SCREEN_WIDTH as usize]; SCREEN_HEIGHT as usize];
+    let (mut x, mut y) = (0i32, 0i32);
+    let mut color = DEFAULT_FOREGROUND;
+    let mut reverse = false;
+    let mut lower_case = false;
+
+    for &b in data {
+        match b {
+            0x0D | 0x8D => {
+                x = 0;
+                y += 1;
+                reverse = false;
+            }
+            0x11 => y += 1,
+            0x91 => y = (y - 1).max(0),
+            0x1D => {
+                x += 1;
+                if x >= SCREEN_WIDTH {
+                    x = 0;
+                    y += 1;
+                }
+            }
+            0x9D => {
+                if x > 0 {
+                    x -= 1;
+                } else if y > 0 {
+                    x = SCREEN_WIDTH - 1;
+                    y -= 1;
+                }
+            }
+            HOME => (x, y) = (0, 0),
+            CLEAR => {
+                lines.iter_mut().for_each(|l| l.fill((0x20, color)));
+                (x, y) = (0, 0);
+            }
+            RVS_ON => reverse = true,
+            RVS_OFF => reverse = false,
+            LOWER_CASE => lower_case = true,
+            UPPER_CASE => lower_case = false,
+            0x14 => {
+                // delete
+                if x > 0 {
+                    x -= 1;
+                    let line = &mut lines[y as usize];
+                    line.remove(x as usize);
+                    line.push((0x20, color));
+                }
+            }
+            INSERT => {
+                while y as usize >= lines.len() {
+                    lines.push(vec![(0x20, color); SCREEN_WIDTH as usize]);
+                }
+                let line = &mut lines[y as usize];
+                line.insert(x as usize, (0x20, color));
+                line.pop();
+            }
+            _ => {
+                if let Some(c) = COLOR_CODES.iter().position(|code| *code == b) {
+                    color = c as u8;
+                } else if let Some(code) = petscii_to_screen_code(b) {
+                    while y as usize >= lines.len() {
+                        lines.push(vec![(0x20, color); SCREEN_WIDTH as usize]);
+                    }
+                    lines[y as usize][x as usize] = (if reverse { code | 0x80 } else { code }, color);
+                    x += 1;
+                    if x >= SCREEN_WIDTH {
+                        x = 0;
+                        y += 1;
+                    }
+                }
+            }
+        }
+        while y as usize >= lines.len() {
+            lines.push(vec![(0x20, color); SCREEN_WIDTH as usize]);
+        }
+    }
+    // the cursor moves to a new line after the last output, that line is usually empty
+    while lines.len() > SCREEN_HEIGHT as usize && lines.last().map_or(false, |l| l.iter().all(|(ch, _)| *ch == 0x20)) {
+        lines.pop();
+    }
+
+    let mut buf = create_petscii_buffer(SCREEN_WIDTH, lines.len() as i32);
+    for (y, line) in lines.iter().enumerate() {
+        for (x, (code, color)) in line.iter().enumerate() {
+            let mut attr = TextAttribute::default();
+            attr.set_foreground(*color as u32);
+            attr.set_background(DEFAULT_BACKGROUND as u32);
+            if lower_case {
+                attr.set_font_page(LOWER_CASE_PAGE);
+            }
+            buf.layers[0].set_char((x as i32, y as i32), AttributedChar::new(char::from(*code), attr));
+        }
+    }
+    buf
+}
+
+pub fn load_c64_file(ext: &str, data: &[u8]) -> TerminalResult<Buffer> {
+    match ext {
+        SCREEN_EXT => Ok(C64Screen::from_dump(data)?.to_buffer()),
+        SEQ_EXT => Ok(parse_seq(data)),
+        _ => Err(anyhow::anyhow!("{ext} files can't be loaded")),
+    }
+}
+
+/// Screen dumps and programs hold one 40x25 screen, seq files the whole document.
+pub fn save_c64_file(ext: &str, buf: &Buffer) -> TerminalResult<Vec<u8>> {
+    let screen = C64Screen::from_buffer(buf)?;
+    if ext != SEQ_EXT && screen.lines() > SCREEN_HEIGHT as usize {
+        return Err(anyhow::anyhow!(
+            "{ext} files hold one {SCREEN_WIDTH}x{SCREEN_HEIGHT} screen, the document has {} lines - save it as .{SEQ_EXT}",
+            screen.lines()
+        ));
+    }
+    match ext {
+        SCREEN_EXT => Ok(screen.to_dump()),
+        SEQ_EXT => Ok(screen.to_seq()),
+        PRG_EXT => Ok(screen.to_prg()),
+        _ => Err(anyhow::anyhow!("unsupported format {ext}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_code_round_trip() {
+        for code in 0..0x80u8 {
+            assert_eq!(petscii_to_screen_code(screen_code_to_petscii(code)), Some(code), "screen code {code:02X}");
+        }
+    }
+
+    #[test]
+    fn test_seq_round_trip() {
+        let mut data = (0..SCREEN_SIZE).map(|i| (i * 7 % 256) as u8).collect::<Vec<_>>();
+        data.extend((0..SCREEN_SIZE).map(|i| (i % 16) as u8));
+        let screen = C64Screen::from_dump(&data).unwrap();
+        let expected = screen.to_buffer();
+        let buf = parse_seq(&screen.to_seq());
+        assert_eq!(buf.get_height(), SCREEN_HEIGHT);
+        for y in 0..SCREEN_HEIGHT {
+            for x in 0..SCREEN_WIDTH {
+                let (a, b) = (expected.get_char((x, y)), buf.get_char((x, y)));
+                assert_eq!(a.ch, b.ch, "char at {x},{y}");
+                assert_eq!(a.attribute.get_foreground(), b.attribute.get_foreground(), "color at {x},{y}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_save_tall_document() {
+        let mut buf = create_petscii_buffer(SCREEN_WIDTH, 30);
+        let mut attr = TextAttribute::default();
+        attr.set_foreground(1);
+        attr.set_background(DEFAULT_BACKGROUND as u32);
+        buf.layers[0].set_char((5, 29), AttributedChar::new('A', attr));
+
+        assert!(save_c64_file(PRG_EXT, &buf).is_err());
+        assert!(save_c64_file(SCREEN_EXT, &buf).is_err());
+        let loaded = load_c64_file(SEQ_EXT, &save_c64_file(SEQ_EXT, &buf).unwrap()).unwrap();
+        assert_eq!(loaded.get_height(), 30);
+        assert_eq!(loaded.get_char((5, 29)).ch, 'A');
+    }
+
+    #[test]
+    fn test_save_refuses_lossy_documents() {
+        let mut attr = TextAttribute::default();
+        attr.set_background(DEFAULT_BACKGROUND as u32);
+
+        let mut buf = create_petscii_buffer(SCREEN_WIDTH, SCREEN_HEIGHT);
+        buf.layers[0].set_char((0, 0), AttributedChar::new('A', attr));
+        let mut lower = attr;
+        lower.set_font_page(LOWER_CASE_PAGE);
+        buf.layers[0].set_char((1, 0), AttributedChar::new('A', lower));
+        assert!(save_c64_file(SEQ_EXT, &buf).is_err());
+
+        let mut buf = create_petscii_buffer(SCREEN_WIDTH, SCREEN_HEIGHT);
+        let mut other_background = attr;
+        other_background.set_background(2);
+        buf.layers[0].set_char((0, 0), AttributedChar::new(' ', other_background));
+        assert!(save_c64_file(SCREEN_EXT, &buf).is_err());
+
+        let buf = create_petscii_buffer(SCREEN_WIDTH + 1, SCREEN_HEIGHT);
+        assert!(save_c64_file(SEQ_EXT, &buf).is_err());
+    }
+}
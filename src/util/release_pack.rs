@@ -0,0 +1,244 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+use icy_engine::{ascii::CP437Converter, UnicodeConverter};
+use icy_sauce::{SauceDataType, SauceInformation};
+use walkdir::WalkDir;
+
+use super::file::write_atomically;
+use crate::TerminalResult;
+
+pub const FILE_ID_NAME: &str = "FILE_ID.DIZ";
+pub const FILE_ID_WIDTH: usize = 44;
+pub const FILE_ID_HEIGHT: usize = 10;
+
+/// Widest character file that is displayed correctly by common viewers.
+const MAX_WIDTH: u16 = 80;
+
+/// Formats that belong into an art pack.
+const PACK_EXTENSIONS: [&str; 16] = [
+    "ans", "asc", "nfo", "txt", "pcb", "avt", "tnd", "xb", "adf", "idf", "bin", "rip", "png", "gif", "jpg", "icy",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackIssue {
+    MissingSauce,
+    TooWide(u16),
+    UnknownFormat,
+}
+
+pub struct PackFile {
+    /// Original file name
+    pub source: String,
+    /// Name inside the pack
+    pub name: String,
+    pub data: Vec<u8>,
+    pub issues: Vec<PackIssue>,
+    pub include: bool,
+}
+
+impl PackFile {
+    pub fn new(source: String, data: Vec<u8>) -> Self {
+        let issues = validate(&source, &data);
+        Self {
+            name: source.clone(),
+            source,
+            data,
+            issues,
+            include: true,
+        }
+    }
+}
+
+fn extension(name: &str) -> String {
+    Path::new(name).extension().unwrap_or_default().to_string_lossy().to_ascii_lowercase()
+}
+
+fn is_image(ext: &str) -> bool {
+    matches!(ext, "png" | "gif" | "jpg")
+}
+
+/// True for text mode art formats of the pack.
+pub fn is_pack_document(name: &str) -> bool {
+    let ext = extension(name);
+    PACK_EXTENSIONS.contains(&ext.as_str()) && !is_image(&ext)
+}
+
+pub fn validate(name: &str, data: &[u8]) -> Vec<PackIssue> {
+    let mut issues = Vec::new();
+    let ext = extension(name);
+    if !PACK_EXTENSIONS.contains(&ext.as_str()) {
+        issues.push(PackIssue::UnknownFormat);
+    }
+    if is_image(&ext) {
+        return issues;
+    }
+    match SauceInformation::read(data) {
+        Ok(Some(sauce)) => {
+            // TInfo1 is the width of character files
+            if sauce.header.data_type == SauceDataType::Character && sauce.header.t_info1 > MAX_WIDTH {
+                issues.push(PackIssue::TooWide(sauce.header.t_info1));
+            }
+        }
+        Ok(None) | Err(_) => issues.push(PackIssue::MissingSauce),
+    }
+    issues
+}
+
+/// Reads all files of a directory (not recursive), an existing FILE_ID.DIZ is returned separately.
+pub fn read_folder(path: &Path) -> TerminalResult<(Vec<PackFile>, Option<String>)> {
+    let mut files = Vec::new();
+    let mut file_id = None;
+    for entry in WalkDir::new(path).max_depth(1).sort_by_file_name().into_iter().flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let data = fs::read(entry.path())?;
+        if name.eq_ignore_ascii_case(FILE_ID_NAME) {
+            file_id = Some(decode_cp437(&data));
+        } else if !name.to_ascii_lowercase().ends_with(".zip") {
+            files.push(PackFile::new(name, data));
+        }
+    }
+    Ok((files, file_id))
+}
+
+/// Lower case, no spaces, prefixed with the pack prefix, e.g. "Some Logo.ANS" -> "grp-some_logo.ans".
+pub fn pack_file_name(prefix: &str, name: &str) -> String {
+    let clean = |s: &str| -> String {
+        s.to_ascii_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+    let name = clean(name);
+    let prefix = clean(prefix);
+    if prefix.is_empty() || name.starts_with(&format!("{prefix}-")) {
+        name
+    } else {
+        format!("{prefix}-{name}")
+    }
+}
+
+/// Applies `pack_file_name` to all files, duplicate names get a number.
+pub fn rename_files(files: &mut [PackFile], prefix: &str) {
+    let mut used: Vec<String> = Vec::new();
+    for file in files.iter_mut() {
+        let name = pack_file_name(prefix, &file.source);
+        let mut unique = name.clone();
+        let mut num = 1;
+        while used.contains(&unique) {
+            let path = Path::new(&name);
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            unique = match path.extension() {
+                Some(ext) => format!("{stem}{num}.{}", ext.to_string_lossy()),
+                None => format!("{stem}{num}"),
+            };
+            num += 1;
+        }
+        used.push(unique.clone());
+        file.name = unique;
+    }
+}
+
+fn center(text: &str) -> String {
+    let text: String = text.chars().take(FILE_ID_WIDTH).collect();
+    let pad = (FILE_ID_WIDTH - text.chars().count()) / 2;
+    format!("{}{text}", " ".repeat(pad)).trim_end().to_string()
+}
+
+/// Creates a FILE_ID.DIZ listing the pack contents.
+pub fn generate_file_id(pack_name: &str, group: &str, date: &str, files: &[&str]) -> String {
+    let mut lines = vec![center(&format!("-*- {pack_name} -*-"))];
+    if !group.is_empty() {
+        lines.push(center(group));
+    }
+    if date.len() == 8 {
+        lines.push(center(&format!("{}-{}", &date[0..4], &date[4..6])));
+    }
+    lines.push(String::new());
+    lines.push(center(&format!("{} files", files.len())));
+
+    let mut line = String::new();
+    for name in files {
+        if !line.is_empty() && line.len() + 1 + name.len() > FILE_ID_WIDTH {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(name);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines.truncate(FILE_ID_HEIGHT);
+    lines.join("\n")
+}
+
+/// Returns (lines, widest line) of a FILE_ID.DIZ.
+pub fn file_id_size(text: &str) -> (usize, usize) {
+    (text.lines().count(), text.lines().map(|l| l.chars().count()).max().unwrap_or(0))
+}
+
+fn decode_cp437(data: &[u8]) -> String {
+    let converter = CP437Converter::default();
+    data.iter()
+        .filter(|b| **b != b'\r' && **b != 0x1A)
+        .map(|b| {
+            if *b == b'\n' {
+                '\n'
+            } else {
+                converter.convert_to_unicode(icy_engine::AttributedChar::new(char::from(*b), icy_engine::TextAttribute::default()))
+            }
+        })
+        .collect()
+}
+
+fn encode_cp437(text: &str) -> Vec<u8> {
+    let converter = CP437Converter::default();
+    let mut result = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            result.extend_from_slice(b"\r\n");
+        }
+        result.extend(
+            line.chars()
+                .map(|ch| u8::try_from(u32::from(converter.convert_from_unicode(ch, 0))).unwrap_or(b'?')),
+        );
+    }
+    result.extend_from_slice(b"\r\n");
+    result
+}
+
+/// Writes the pack, FILE_ID.DIZ is the first entry.
+/// An existing pack stays intact if writing fails.
+pub fn write_pack(path: &Path, file_id: &str, files: &[PackFile]) -> TerminalResult<()> {
+    write_atomically(path, |tmp_file| write_zip(tmp_file, file_id, files))
+}
+
+fn write_zip(path: &Path, file_id: &str, files: &[PackFile]) -> TerminalResult<()> {
+    let mut writer = zip::ZipWriter::new(File::create(path)?);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    if !file_id.trim().is_empty() {
+        writer.start_file(FILE_ID_NAME, options)?;
+        writer.write_all(&encode_cp437(file_id))?;
+    }
+    for file in files.iter().filter(|f| f.include) {
+        writer.start_file(file.name.clone(), options)?;
+        writer.write_all(&file.data)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
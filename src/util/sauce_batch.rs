@@ -0,0 +1,319 @@
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use bstr::BString;
+use icy_engine::{Buffer, TextPane};
+use icy_sauce::{SauceDataType, SauceHeader, SauceInformation};
+use walkdir::WalkDir;
+
+use super::{date::today, file::write_atomically};
+use crate::TerminalResult;
+
+/// Files that get a new SAUCE record if they don't have one yet, with their character file type.
+/// Other files are only updated when they already carry a record.
+const CHARACTER_FILE_TYPES: [(&str, u8); 7] = [("ans", 1), ("asc", 0), ("txt", 0), ("nfo", 0), ("pcb", 4), ("avt", 5), ("tnd", 8)];
+
+const EOF_CHAR: u8 = 0x1A;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SauceFields {
+    pub title: String,
+    pub author: String,
+    pub group: String,
+    pub date: String,
+}
+
+impl SauceFields {
+    fn from_header(header: &SauceHeader) -> Self {
+        Self {
+            title: header.title.to_string(),
+            author: header.author.to_string(),
+            group: header.group.to_string(),
+            date: header.date.to_string(),
+        }
+    }
+}
+
+/// Fields to set, `None` keeps the current value.
+/// Values may contain `{filename}`, `{ext}`, `{title}`, `{author}`, `{group}` and `{date}` (today).
+#[derive(Debug, Clone, Default)]
+pub struct SauceTemplate {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub group: Option<String>,
+    pub date: Option<String>,
+}
+
+pub struct BatchEntry {
+    /// Path relative to the pack
+    pub name: String,
+    pub old: Option<SauceFields>,
+    pub new: SauceFields,
+}
+
+impl BatchEntry {
+    /// Files without a record only change if the template sets one of the text fields or the date.
+    pub fn is_changed(&self) -> bool {
+        match &self.old {
+            Some(old) => *old != self.new,
+            None => !self.new.title.is_empty() || !self.new.author.is_empty() || !self.new.group.is_empty() || !self.new.date.is_empty(),
+        }
+    }
+}
+
+fn expand(template: &str, name: &str, old: &SauceFields) -> String {
+    let path = Path::new(name);
+    template
+        .replace("{filename}", &path.file_stem().unwrap_or_default().to_string_lossy())
+        .replace("{ext}", &path.extension().unwrap_or_default().to_string_lossy())
+        .replace("{title}", &old.title)
+        .replace("{author}", &old.author)
+        .replace("{group}", &old.group)
+        .replace("{date}", &today())
+}
+
+fn character_file_type(name: &str) -> Option<u8> {
+    let ext = Path::new(name).extension()?.to_string_lossy().to_ascii_lowercase();
+    CHARACTER_FILE_TYPES.iter().find(|(e, _)| *e == ext).map(|(_, file_type)| *file_type)
+}
+
+/// Creates the record for a file without SAUCE, the size is taken from the parsed document.
+/// The date is set together with the first field, files the template doesn't touch stay unchanged.
+fn create_sauce(name: &str, data: &[u8]) -> Option<SauceInformation> {
+    let file_type = character_file_type(name)?;
+    let mut sauce = SauceInformation::default();
+    sauce.header.data_type = SauceDataType::Character;
+    sauce.header.file_type = file_type;
+    let (width, height) = match Buffer::from_bytes(Path::new(name), true, data) {
+        Ok(buf) => (buf.get_width(), buf.get_height()),
+        Err(_) => (80, 0),
+    };
+    sauce.header.t_info1 = width.clamp(0, u16::MAX as i32) as u16;
+    sauce.header.t_info2 = height.clamp(0, u16::MAX as i32) as u16;
+    Some(sauce)
+}
+
+/// The artwork without SAUCE record, comments and EOF marker.
+fn artwork(data: &[u8], sauce: Option<&SauceInformation>) -> &[u8] {
+    let mut len = data.len() - sauce.map_or(0, |sauce| sauce.info_len()).min(data.len());
+    if len > 0 && data[len - 1] == EOF_CHAR {
+        len -= 1;
+    }
+    &data[..len]
+}
+
+/// Applies the template to one file. Returns None if the file can't carry a SAUCE record.
+fn apply_template(name: &str, data: &[u8], template: &SauceTemplate) -> TerminalResult<Option<(BatchEntry, Vec<u8>)>> {
+    let old_sauce = SauceInformation::read(data)?;
+    let artwork = artwork(data, old_sauce.as_ref());
+    let old = old_sauce.as_ref().map(|sauce| SauceFields::from_header(&sauce.header));
+    let Some(mut sauce) = old_sauce.or_else(|| create_sauce(name, artwork)) else {
+        return Ok(None);
+    };
+
+    let current = old.clone().unwrap_or_default();
+    if let Some(title) = &template.title {
+        sauce.header.title = BString::from(expand(title, name, &current));
+    }
+    if let Some(author) = &template.author {
+        sauce.header.author = BString::from(expand(author, name, &current));
+    }
+    if let Some(group) = &template.group {
+        sauce.header.group = BString::from(expand(group, name, &current));
+    }
+    if let Some(date) = &template.date {
+        let date = expand(date, name, &current);
+        if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow::anyhow!("invalid SAUCE date '{date}', expected CCYYMMDD"));
+        }
+        sauce.header.date = BString::from(date);
+    }
+    if old.is_none() && sauce.header.date.is_empty() && !(sauce.header.title.is_empty() && sauce.header.author.is_empty() && sauce.header.group.is_empty()) {
+        sauce.header.date = BString::from(today());
+    }
+    sauce.header.file_size = u32::try_from(artwork.len()).unwrap_or(u32::MAX);
+
+    // icy_sauce writes the EOF marker, the comment block and the record, strings are converted to CP437
+    let mut result = artwork.to_vec();
+    sauce.write(&mut result)?;
+
+    let entry = BatchEntry {
+        name: name.to_string(),
+        old,
+        new: SauceFields::from_header(&sauce.header),
+    };
+    Ok(Some((entry, result)))
+}
+
+fn is_zip(path: &Path) -> bool {
+    path.is_file() && path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+fn should_skip(name: &str) -> bool {
+    // the FILE_ID.DIZ is read by BBS software, a SAUCE record would show up in file listings
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".diz") || name.ends_with(".zip")
+}
+
+/// Calls `f` with (relative name, data) for every file of a directory or zip.
+fn for_each_file(path: &Path, mut f: impl FnMut(&str, &[u8]) -> TerminalResult<()>) -> TerminalResult<()> {
+    if is_zip(path) {
+        let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let name = entry.name().to_string();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            f(&name, &data)?;
+        }
+    } else {
+        for entry in WalkDir::new(path).sort_by_file_name().into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let name = entry.path().strip_prefix(path).unwrap_or(entry.path()).to_string_lossy().to_string();
+            let data = fs::read(entry.path())?;
+            f(&name, &data)?;
+        }
+    }
+    Ok(())
+}
+
+/// Shows what `write_batch` would do.
+pub fn preview_batch(path: &Path, template: &SauceTemplate) -> TerminalResult<Vec<BatchEntry>> {
+    let mut result = Vec::new();
+    for_each_file(path, |name, data| {
+        if !should_skip(name) {
+            if let Some((entry, _)) = apply_template(name, data, template)? {
+                result.push(entry);
+            }
+        }
+        Ok(())
+    })?;
+    Ok(result)
+}
+
+/// Copies the zip to `tmp_file` with the template applied, returns the number of changed files.
+fn write_zip(path: &Path, tmp_file: &Path, template: &SauceTemplate) -> TerminalResult<usize> {
+    let mut changed = 0;
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+    let mut writer = zip::ZipWriter::new(File::create(tmp_file)?);
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let options = zip::write::FileOptions::default()
+            .compression_method(entry.compression())
+            .last_modified_time(entry.last_modified());
+        if entry.is_dir() {
+            writer.add_directory(entry.name(), options)?;
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        if !should_skip(&name) {
+            if let Some((batch_entry, new_data)) = apply_template(&name, &data, template)? {
+                if batch_entry.is_changed() {
+                    data = new_data;
+                    changed += 1;
+                }
+            }
+        }
+        writer.start_file(name, options)?;
+        writer.write_all(&data)?;
+    }
+    writer.finish()?;
+    Ok(changed)
+}
+
+/// Writes the SAUCE records, returns the number of changed files.
+pub fn write_batch(path: &Path, template: &SauceTemplate) -> TerminalResult<usize> {
+    if is_zip(path) {
+        return write_atomically(path, |tmp_file| write_zip(path, tmp_file, template));
+    }
+
+    let mut updates: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+    for_each_file(path, |name, data| {
+        if !should_skip(name) {
+            if let Some((entry, new_data)) = apply_template(name, data, template)? {
+                if entry.is_changed() {
+                    updates.push((path.join(name), new_data));
+                }
+            }
+        }
+        Ok(())
+    })?;
+    for (file, data) in &updates {
+        fs::write(file, data)?;
+    }
+    Ok(updates.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use icy_sauce::SauceInformation;
+
+    use super::{apply_template, SauceTemplate};
+
+    fn template(title: &str) -> SauceTemplate {
+        SauceTemplate {
+            title: Some(title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_fields() {
+        let (entry, data) = apply_template("logo.asc", b"Hello", &template("{filename} by me")).unwrap().unwrap();
+        assert!(entry.old.is_none());
+        assert!(entry.is_changed());
+        assert_eq!(entry.new.title, "logo by me");
+        assert_eq!(&data[..6], b"Hello\x1A");
+
+        let sauce = SauceInformation::read(&data).unwrap().unwrap();
+        assert_eq!(sauce.header.title.to_string(), "logo by me");
+        assert_eq!(sauce.header.file_size, 5);
+        assert_eq!(sauce.header.date.to_string().len(), 8);
+    }
+
+    #[test]
+    fn test_fields_are_cp437() {
+        let (_, data) = apply_template("logo.asc", b"Hello", &template("Café")).unwrap().unwrap();
+        // the record is the last 128 bytes, the title starts at offset 7
+        let record = &data[data.len() - 128..];
+        assert_eq!(&record[7..12], &[b'C', b'a', b'f', 0x82, b' ']);
+
+        let (entry, _) = apply_template("logo.asc", &data, &SauceTemplate::default()).unwrap().unwrap();
+        assert_eq!(entry.old.unwrap().title, "Café");
+    }
+
+    #[test]
+    fn test_replace_keeps_artwork() {
+        let (_, data) = apply_template("logo.ans", b"Hello", &template("Old")).unwrap().unwrap();
+        let (entry, data) = apply_template("logo.ans", &data, &template("New")).unwrap().unwrap();
+        assert_eq!(entry.old.as_ref().unwrap().title, "Old");
+        assert_eq!(entry.new.title, "New");
+        assert_eq!(&data[..6], b"Hello\x1A");
+        assert_eq!(data.len(), 6 + 128);
+    }
+
+    #[test]
+    fn test_untouched_files_are_unchanged() {
+        let (entry, _) = apply_template("logo.asc", b"Hello", &SauceTemplate::default()).unwrap().unwrap();
+        assert!(!entry.is_changed());
+
+        let (_, data) = apply_template("logo.asc", b"Hello", &template("Title")).unwrap().unwrap();
+        let (entry, _) = apply_template("logo.asc", &data, &template("Title")).unwrap().unwrap();
+        assert!(!entry.is_changed());
+    }
+
+    #[test]
+    fn test_unknown_files_get_no_record() {
+        assert!(apply_template("logo.exe", b"MZ", &template("Title")).unwrap().is_none());
+    }
+}
@@ -0,0 +1,347 @@
+use icy_engine::BitFont;
+
+/// Font units of one font pixel.
+const PIXEL_UNITS: i32 = 128;
+
+/// Horizontal runs of set pixels as (x, y, length) - each run becomes one rectangle contour.
+pub fn glyph_runs(data: &[u8], width: i32) -> Vec<(i32, i32, i32)> {
+    let mut runs = Vec::new();
+    for (y, row) in data.iter().enumerate() {
+        let mut x = 0;
+        while x < width.min(8) {
+            if row & (128 >> x) == 0 {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < width.min(8) && row & (128 >> x) != 0 {
+                x += 1;
+            }
+            runs.push((start, y as i32, x - start));
+        }
+    }
+    runs
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, v: u8) -> &mut Self {
+        self.0.push(v);
+        self
+    }
+    fn u16(&mut self, v: u16) -> &mut Self {
+        self.0.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+    fn i16(&mut self, v: i16) -> &mut Self {
+        self.0.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+    fn u32(&mut self, v: u32) -> &mut Self {
+        self.0.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+    fn bytes(&mut self, v: &[u8]) -> &mut Self {
+        self.0.extend_from_slice(v);
+        self
+    }
+    fn pad4(&mut self) {
+        while self.0.len() % 4 != 0 {
+            self.0.push(0);
+        }
+    }
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// (searchRange, entrySelector, rangeShift) for binary search headers.
+fn search_params(count: u16, size: u16) -> (u16, u16, u16) {
+    let mut selector = 0;
+    while 1 << (selector + 1) <= count {
+        selector += 1;
+    }
+    let range = (1 << selector) * size;
+    (range, selector, count * size - range)
+}
+
+/// Converts a bitmap font into a truetype font, each pixel becomes a square.
+/// `to_unicode` maps the glyph codes, the em square is exactly one character cell.
+pub fn bitfont_to_ttf(font: &BitFont, name: &str, to_unicode: impl Fn(char) -> char) -> Vec<u8> {
+    let width = font.size.width.clamp(1, 8);
+    let height = font.size.height.max(1);
+    let advance = (width * PIXEL_UNITS) as u16;
+    let em = (height * PIXEL_UNITS) as u16;
+
+    // glyph 0 is an empty .notdef, loca holds the start offsets of all glyphs and the end of the last one
+    let mut glyf = Writer(Vec::new());
+    let mut loca = vec![0u32, 0];
+    let mut hmtx = Writer(Vec::new());
+    hmtx.u16(advance).i16(0);
+    let mut mapping: Vec<(u16, u16)> = Vec::new();
+    let (mut max_points, mut max_contours) = (0, 0);
+
+    for code in 0..font.length {
+        let ch = char::from_u32(code as u32).unwrap_or_default();
+        let glyph_index = (code + 1) as u16;
+        let unicode = to_unicode(ch) as u32;
+        if unicode < 0xFFFF && !mapping.iter().any(|(u, _)| *u as u32 == unicode) {
+            mapping.push((unicode as u16, glyph_index));
+        }
+        let runs = font.get_glyph(ch).map(|g| glyph_runs(&g.data, width)).unwrap_or_default();
+        if runs.is_empty() {
+            hmtx.u16(advance).i16(0);
+            loca.push(glyf.0.len() as u32);
+            continue;
+        }
+        let rects: Vec<(i16, i16, i16, i16)> = runs
+            .iter()
+            .map(|(x, y, len)| {
+                let x0 = x * PIXEL_UNITS;
+                let x1 = (x + len) * PIXEL_UNITS;
+                let y1 = (height - y) * PIXEL_UNITS;
+                (x0 as i16, (y1 - PIXEL_UNITS) as i16, x1 as i16, y1 as i16)
+            })
+            .collect();
+        let x_min = rects.iter().map(|r| r.0).min().unwrap_or(0);
+        let y_min = rects.iter().map(|r| r.1).min().unwrap_or(0);
+        let x_max = rects.iter().map(|r| r.2).max().unwrap_or(0);
+        let y_max = rects.iter().map(|r| r.3).max().unwrap_or(0);
+        max_points = max_points.max(rects.len() * 4);
+        max_contours = max_contours.max(rects.len());
+
+        glyf.i16(rects.len() as i16).i16(x_min).i16(y_min).i16(x_max).i16(y_max);
+        for i in 0..rects.len() {
+            glyf.u16((i * 4 + 3) as u16);
+        }
+        glyf.u16(0); // no instructions
+        for _ in 0..rects.len() * 4 {
+            glyf.u8(0x01); // on curve, 16 bit deltas
+        }
+        // clockwise: bottom left, top left, top right, bottom right
+        let points: Vec<(i16, i16)> = rects
+            .iter()
+            .flat_map(|(x0, y0, x1, y1)| [(*x0, *y0), (*x0, *y1), (*x1, *y1), (*x1, *y0)])
+            .collect();
+        let mut last = 0;
+        for (x, _) in &points {
+            glyf.i16(x - last);
+            last = *x;
+        }
+        last = 0;
+        for (_, y) in &points {
+            glyf.i16(y - last);
+            last = *y;
+        }
+        glyf.pad4();
+        loca.push(glyf.0.len() as u32);
+        hmtx.u16(advance).i16(x_min);
+    }
+    let num_glyphs = (font.length + 1) as u16;
+
+    let mut head = Writer(Vec::new());
+    head.u32(0x0001_0000).u32(0x0001_0000).u32(0).u32(0x5F0F_3CF5).u16(0x000B).u16(em);
+    head.u32(0).u32(0).u32(0).u32(0); // created / modified
+    head.i16(0).i16(0).i16(advance as i16).i16(em as i16);
+    head.u16(0).u16(8).i16(2).i16(1).i16(0);
+
+    let mut hhea = Writer(Vec::new());
+    hhea.u32(0x0001_0000)
+        .i16(em as i16)
+        .i16(0)
+        .i16(0)
+        .u16(advance)
+        .i16(0)
+        .i16(0)
+        .i16(advance as i16);
+    hhea.i16(1).i16(0).i16(0).i16(0).i16(0).i16(0).i16(0).i16(0).u16(num_glyphs);
+
+    let mut maxp = Writer(Vec::new());
+    maxp.u32(0x0001_0000)
+        .u16(num_glyphs)
+        .u16(max_points as u16)
+        .u16(max_contours as u16)
+        .u16(0)
+        .u16(0)
+        .u16(2);
+    // maxTwilightPoints .. maxComponentDepth
+    for _ in 0..8 {
+        maxp.u16(0);
+    }
+
+    mapping.sort_unstable();
+    let first_char = mapping.first().map_or(0, |m| m.0);
+    let last_char = mapping.last().map_or(0, |m| m.0);
+
+    let mut os2 = Writer(Vec::new());
+    os2.u16(4).i16(advance as i16).u16(400).u16(5).u16(0);
+    for _ in 0..10 {
+        os2.i16(0);
+    }
+    os2.i16(0).bytes(&[0; 10]).u32(0).u32(0).u32(0).u32(0).bytes(b"ICYD").u16(0x40);
+    os2.u16(first_char).u16(last_char).i16(em as i16).i16(0).i16(0).u16(em).u16(0);
+    os2.u32(1).u32(0).i16(0).i16(0).u16(0).u16(32).u16(1);
+
+    // one segment per char, idDelta maps it to the glyph
+    let mut segments: Vec<(u16, u16)> = mapping.clone();
+    segments.push((0xFFFF, 0));
+    let seg_count = segments.len() as u16;
+    let (search_range, entry_selector, range_shift) = search_params(seg_count, 2);
+    let mut cmap = Writer(Vec::new());
+    cmap.u16(0).u16(1).u16(3).u16(1).u32(12);
+    cmap.u16(4)
+        .u16(16 + seg_count * 8)
+        .u16(0)
+        .u16(seg_count * 2)
+        .u16(search_range)
+        .u16(entry_selector)
+        .u16(range_shift);
+    for (c, _) in &segments {
+        cmap.u16(*c);
+    }
+    cmap.u16(0);
+    for (c, _) in &segments {
+        cmap.u16(*c);
+    }
+    for (c, g) in &segments {
+        cmap.u16(if *c == 0xFFFF { 1 } else { g.wrapping_sub(*c) });
+    }
+    for _ in &segments {
+        cmap.u16(0);
+    }
+
+    let names = [
+        (1, name.to_string()),
+        (2, "Regular".to_string()),
+        (3, name.to_string()),
+        (4, name.to_string()),
+        (6, name.replace(' ', "-")),
+    ];
+    let mut name_table = Writer(Vec::new());
+    let mut strings = Vec::new();
+    name_table.u16(0).u16(names.len() as u16).u16(6 + 12 * names.len() as u16);
+    for (id, text) in &names {
+        let encoded: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        name_table.u16(3).u16(1).u16(0x409).u16(*id).u16(encoded.len() as u16).u16(strings.len() as u16);
+        strings.extend(encoded);
+    }
+    name_table.bytes(&strings);
+
+    let mut post = Writer(Vec::new());
+    post.u32(0x0003_0000)
+        .u32(0)
+        .i16(-(PIXEL_UNITS as i16))
+        .i16(PIXEL_UNITS as i16)
+        .u32(1)
+        .u32(0)
+        .u32(0)
+        .u32(0)
+        .u32(0);
+
+    let mut loca_table = Writer(Vec::new());
+    for offset in &loca {
+        loca_table.u32(*offset);
+    }
+
+    let tables: [(&[u8; 4], Vec<u8>); 10] = [
+        (b"OS/2", os2.0),
+        (b"cmap", cmap.0),
+        (b"glyf", glyf.0),
+        (b"head", head.0),
+        (b"hhea", hhea.0),
+        (b"hmtx", hmtx.0),
+        (b"loca", loca_table.0),
+        (b"maxp", maxp.0),
+        (b"name", name_table.0),
+        (b"post", post.0),
+    ];
+
+    let mut result = Writer(Vec::new());
+    let (search_range, entry_selector, range_shift) = search_params(tables.len() as u16, 16);
+    result
+        .u32(0x0001_0000)
+        .u16(tables.len() as u16)
+        .u16(search_range)
+        .u16(entry_selector)
+        .u16(range_shift);
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = 0;
+    for (tag, data) in &tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        result.bytes(*tag).u32(checksum(data)).u32(offset as u32).u32(data.len() as u32);
+        offset += (data.len() + 3) & !3;
+    }
+    for (_, data) in &tables {
+        result.bytes(data);
+        result.pad4();
+    }
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&result.0));
+    result.0[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    result.0
+}
+
+#[cfg(test)]
+mod tests {
+    use icy_engine::BitFont;
+
+    use super::{bitfont_to_ttf, checksum, glyph_runs};
+
+    fn read_u16(data: &[u8], offset: usize) -> u16 {
+        u16::from_be_bytes([data[offset], data[offset + 1]])
+    }
+
+    fn read_u32(data: &[u8], offset: usize) -> u32 {
+        u32::from_be_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+    }
+
+    /// (offset, length) of a table from the table directory.
+    fn find_table(data: &[u8], tag: &[u8; 4]) -> (usize, usize) {
+        let count = read_u16(data, 4) as usize;
+        (0..count)
+            .map(|i| 12 + 16 * i)
+            .find(|entry| &data[*entry..*entry + 4] == tag)
+            .map(|entry| (read_u32(data, entry + 8) as usize, read_u32(data, entry + 12) as usize))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_glyph_runs() {
+        assert_eq!(glyph_runs(&[0b1100_0110, 0], 8), vec![(0, 0, 2), (5, 0, 2)]);
+        // pixels right of the font width are ignored
+        assert_eq!(glyph_runs(&[0b0000_0011], 7), vec![(6, 0, 1)]);
+    }
+
+    #[test]
+    fn test_maxp_table() {
+        let font = BitFont::default();
+        let ttf = bitfont_to_ttf(&font, "test", |ch| ch);
+        let (offset, len) = find_table(&ttf, b"maxp");
+        assert_eq!(len, 32);
+        assert_eq!(read_u32(&ttf, offset), 0x0001_0000);
+        assert_eq!(read_u16(&ttf, offset + 4) as i32, font.length + 1);
+        // maxZones
+        assert_eq!(read_u16(&ttf, offset + 14), 2);
+    }
+
+    #[test]
+    fn test_checksums() {
+        let ttf = bitfont_to_ttf(&BitFont::default(), "test", |ch| ch);
+        assert_eq!(checksum(&ttf), 0xB1B0_AFBA);
+        let count = read_u16(&ttf, 4) as usize;
+        for entry in (0..count).map(|i| 12 + 16 * i) {
+            if &ttf[entry..entry + 4] == b"head" {
+                continue;
+            }
+            let (offset, len) = (read_u32(&ttf, entry + 8) as usize, read_u32(&ttf, entry + 12) as usize);
+            assert_eq!(checksum(&ttf[offset..offset + len]), read_u32(&ttf, entry + 4));
+        }
+    }
+}
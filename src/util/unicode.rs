@@ -0,0 +1,24 @@
+use icy_engine::{ascii, atascii, petscii, viewdata, AttributedChar, BufferType, TextAttribute, UnicodeConverter};
+
+/// Maps a char of a buffer to the unicode char it displays, unicode buffers are passed through.
+pub fn to_unicode(buffer_type: BufferType, ch: char) -> char {
+    let ch = AttributedChar::new(ch, TextAttribute::default());
+    match buffer_type {
+        BufferType::Unicode => ch.ch,
+        BufferType::CP437 => ascii::CP437Converter::default().convert_to_unicode(ch),
+        BufferType::Petscii => petscii::CharConverter::default().convert_to_unicode(ch),
+        BufferType::Atascii => atascii::CharConverter::default().convert_to_unicode(ch),
+        BufferType::Viewdata => viewdata::CharConverter::default().convert_to_unicode(ch),
+    }
+}
+
+/// Maps a unicode char to the char of a buffer, always using font page 0.
+pub fn from_unicode(buffer_type: BufferType, ch: char) -> char {
+    match buffer_type {
+        BufferType::Unicode => ch,
+        BufferType::CP437 => ascii::CP437Converter::default().convert_from_unicode(ch, 0),
+        BufferType::Petscii => petscii::CharConverter::default().convert_from_unicode(ch, 0),
+        BufferType::Atascii => atascii::CharConverter::default().convert_from_unicode(ch, 0),
+        BufferType::Viewdata => viewdata::CharConverter::default().convert_from_unicode(ch, 0),
+    }
+}